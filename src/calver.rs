@@ -0,0 +1,362 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::error::VersioError;
+use crate::version::{Components, SchemeVersion};
+use crate::Result;
+use chrono::{Datelike, NaiveDate};
+use failure::{bail, Fail};
+use std::fmt;
+use std::result;
+use std::str::FromStr;
+
+/// The characters which separate the parts of a calendar version.
+const SEPARATORS: &[char] = &['.', '-', '_'];
+
+/// A part of a calendar version format, named as on calver.org.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Token {
+    /// `YYYY`, the full year, such as 2006.
+    FullYear,
+    /// `YY`, the year since 2000, such as 6 or 16.
+    ShortYear,
+    /// `0Y`, the zero-padded year since 2000, such as 06 or 16.
+    PaddedYear,
+    /// `MM`, the month, such as 1 or 11.
+    Month,
+    /// `0M`, the zero-padded month, such as 01 or 11.
+    PaddedMonth,
+    /// `WW`, the ISO week of the year, such as 1 or 33.
+    Week,
+    /// `0W`, the zero-padded ISO week of the year, such as 01 or 33.
+    PaddedWeek,
+    /// `DD`, the day of the month, such as 1 or 31.
+    Day,
+    /// `0D`, the zero-padded day of the month, such as 01 or 31.
+    PaddedDay,
+    /// `MICRO`, a counter of the releases made on the same date, starting at 0.
+    Micro,
+}
+
+impl Token {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "YYYY" => Some(Token::FullYear),
+            "YY" => Some(Token::ShortYear),
+            "0Y" => Some(Token::PaddedYear),
+            "MM" => Some(Token::Month),
+            "0M" => Some(Token::PaddedMonth),
+            "WW" => Some(Token::Week),
+            "0W" => Some(Token::PaddedWeek),
+            "DD" => Some(Token::Day),
+            "0D" => Some(Token::PaddedDay),
+            "MICRO" => Some(Token::Micro),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Token::FullYear => "YYYY",
+            Token::ShortYear => "YY",
+            Token::PaddedYear => "0Y",
+            Token::Month => "MM",
+            Token::PaddedMonth => "0M",
+            Token::Week => "WW",
+            Token::PaddedWeek => "0W",
+            Token::Day => "DD",
+            Token::PaddedDay => "0D",
+            Token::Micro => "MICRO",
+        }
+    }
+
+    /// Returns the value of the part for `date`, or `None` for the micro counter.
+    fn value(self, date: NaiveDate) -> Option<u64> {
+        let value = match self {
+            Token::FullYear => date.year() as u64,
+            Token::ShortYear | Token::PaddedYear => (date.year() - 2000) as u64,
+            Token::Month | Token::PaddedMonth => u64::from(date.month()),
+            Token::Week | Token::PaddedWeek => u64::from(date.iso_week().week()),
+            Token::Day | Token::PaddedDay => u64::from(date.day()),
+            Token::Micro => return None,
+        };
+
+        Some(value)
+    }
+
+    fn render(self, value: u64) -> String {
+        match self {
+            Token::PaddedYear | Token::PaddedMonth | Token::PaddedWeek | Token::PaddedDay => {
+                format!("{:02}", value)
+            }
+            _ => value.to_string(),
+        }
+    }
+}
+
+/// The format of a calendar version, such as `YYYY.MM.MICRO` or `YY.0M.DD`.
+///
+/// A format is made of date parts, optionally followed by a `MICRO` counter, separated by `.`,
+/// `-` or `_`.
+#[derive(Clone, Debug)]
+pub struct Format {
+    tokens: Vec<Token>,
+    separators: Vec<char>,
+}
+
+impl Format {
+    /// Returns the version which follows `current` on `today`.
+    ///
+    /// The date parts roll to `today`, resetting the micro counter to 0. When they are unchanged,
+    /// the micro counter is incremented instead, failing if the format has none.
+    pub fn bump(&self, current: &CalVer, today: NaiveDate) -> Result<CalVer> {
+        if current.parts.len() != self.tokens.len() {
+            bail!("version {} does not follow the format {}", current, self);
+        }
+        let unchanged = self
+            .tokens
+            .iter()
+            .zip(&current.parts)
+            .all(|(token, part)| *token == Token::Micro || token.value(today) == Some(*part));
+        if unchanged && !self.tokens.contains(&Token::Micro) {
+            bail!(
+                "version {} is already the version for today, and the format {} has no MICRO \
+                 counter to bump",
+                current,
+                self
+            );
+        }
+
+        let mut text = String::new();
+        for (index, token) in self.tokens.iter().enumerate() {
+            if index > 0 {
+                text.push(self.separators[index - 1]);
+            }
+            let value = match token.value(today) {
+                Some(value) => value,
+                None if unchanged => current.parts[index] + 1,
+                None => 0,
+            };
+            text.push_str(&token.render(value));
+        }
+
+        Ok(CalVer::parse(&text)?)
+    }
+}
+
+impl FromStr for Format {
+    type Err = failure::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let tokens = s
+            .split(SEPARATORS)
+            .map(|name| match Token::from_name(name) {
+                Some(token) => Ok(token),
+                None => bail!(
+                    "unknown part '{}' in calendar version format '{}', expected one of YYYY, \
+                     YY, 0Y, MM, 0M, WW, 0W, DD, 0D or MICRO",
+                    name,
+                    s
+                ),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        match tokens.iter().position(|token| *token == Token::Micro) {
+            Some(0) => bail!("calendar version format '{}' has no date part", s),
+            Some(index) if index != tokens.len() - 1 => {
+                bail!(
+                    "MICRO must be the last part of calendar version format '{}'",
+                    s
+                )
+            }
+            _ => {}
+        }
+
+        Ok(Format {
+            tokens,
+            separators: s.chars().filter(|c| SEPARATORS.contains(c)).collect(),
+        })
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, token) in self.tokens.iter().enumerate() {
+            if index > 0 {
+                write!(f, "{}", self.separators[index - 1])?;
+            }
+            f.write_str(token.name())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An error parsing a calendar version.
+#[derive(Debug)]
+pub struct ParseError;
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "not a calendar version")
+    }
+}
+
+impl Fail for ParseError {}
+
+/// A calendar version, such as `2024.10.3` or `24.01.05`, whose parts are all numbers.
+///
+/// The parts are compared by value, so `24.1.5` equals `24.01.05`.
+#[derive(Clone, Debug)]
+pub struct CalVer {
+    text: String,
+    parts: Vec<u64>,
+}
+
+impl PartialEq for CalVer {
+    fn eq(&self, other: &Self) -> bool {
+        self.parts == other.parts
+    }
+}
+
+impl FromStr for CalVer {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let parts = s
+            .split(SEPARATORS)
+            .map(|part| {
+                if part.chars().all(|c| c.is_ascii_digit()) {
+                    part.parse().map_err(|_| ParseError)
+                } else {
+                    Err(ParseError)
+                }
+            })
+            .collect::<result::Result<_, _>>()?;
+
+        Ok(CalVer {
+            text: s.to_string(),
+            parts,
+        })
+    }
+}
+
+impl fmt::Display for CalVer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl SchemeVersion for CalVer {
+    fn parse(text: &str) -> result::Result<Self, VersioError> {
+        CalVer::from_str(text).map_err(|cause| VersioError::InvalidVersion {
+            path: None,
+            text: text.to_string(),
+            cause: cause.into(),
+        })
+    }
+
+    fn components(&self) -> Components {
+        let part = |index: usize| self.parts.get(index).copied().unwrap_or(0);
+
+        Components {
+            major: part(0),
+            minor: part(1),
+            patch: part(2),
+            ..Components::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(s: &str) -> Format {
+        Format::from_str(s).unwrap()
+    }
+
+    fn version(s: &str) -> CalVer {
+        CalVer::from_str(s).unwrap()
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd(year, month, day)
+    }
+
+    fn bump(format_str: &str, current: &str, today: NaiveDate) -> String {
+        format(format_str)
+            .bump(&version(current), today)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn format_display() {
+        assert_eq!(format("YYYY.MM.MICRO").to_string(), "YYYY.MM.MICRO");
+        assert_eq!(format("YY.0M-0D_MICRO").to_string(), "YY.0M-0D_MICRO");
+    }
+
+    #[test]
+    fn format_err() {
+        assert_eq!(
+            Format::from_str("YYYY.MONTH").unwrap_err().to_string(),
+            "unknown part 'MONTH' in calendar version format 'YYYY.MONTH', expected one of YYYY, \
+             YY, 0Y, MM, 0M, WW, 0W, DD, 0D or MICRO"
+        );
+        assert!(Format::from_str("").is_err());
+        assert!(Format::from_str("MICRO").is_err());
+        assert!(Format::from_str("YYYY.MICRO.MM").is_err());
+    }
+
+    #[test]
+    fn bump_new_date() {
+        assert_eq!(
+            bump("YYYY.MM.MICRO", "2024.9.3", date(2024, 10, 18)),
+            "2024.10.0"
+        );
+        assert_eq!(bump("YY.0M.DD", "24.09.30", date(2024, 10, 1)), "24.10.1");
+        assert_eq!(bump("0Y.0M.0D", "24.09.30", date(2025, 1, 5)), "25.01.05");
+        assert_eq!(bump("YYYY.WW", "2024.41", date(2024, 10, 18)), "2024.42");
+    }
+
+    #[test]
+    fn bump_same_date() {
+        assert_eq!(
+            bump("YYYY.MM.MICRO", "2024.10.3", date(2024, 10, 18)),
+            "2024.10.4"
+        );
+        assert_eq!(bump("YY.0M.MICRO", "24.10.0", date(2024, 10, 1)), "24.10.1");
+    }
+
+    #[test]
+    fn bump_same_date_without_micro() {
+        assert_eq!(
+            format("YY.0M.0D")
+                .bump(&version("24.10.18"), date(2024, 10, 18))
+                .unwrap_err()
+                .to_string(),
+            "version 24.10.18 is already the version for today, and the format YY.0M.0D has no \
+             MICRO counter to bump"
+        );
+    }
+
+    #[test]
+    fn bump_other_format() {
+        assert_eq!(
+            format("YYYY.MM.MICRO")
+                .bump(&version("2024.10"), date(2024, 10, 18))
+                .unwrap_err()
+                .to_string(),
+            "version 2024.10 does not follow the format YYYY.MM.MICRO"
+        );
+    }
+
+    #[test]
+    fn calver_eq() {
+        assert_eq!(version("24.1.5"), version("24.01.05"));
+        assert_ne!(version("24.1.5"), version("24.1.6"));
+        assert!(CalVer::from_str("1.2.3-rc.1").is_err());
+        assert!(CalVer::from_str("2024..1").is_err());
+    }
+}
@@ -2,36 +2,255 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::version::Version;
+use crate::error::{self, VersioError};
+use crate::io;
+use crate::version::{SchemeVersion, Version, VersionBumper};
 use crate::Result;
+use failure::{bail, format_err};
+use log::debug;
 use std::fmt;
+use std::path::{Path, PathBuf};
 use std::result;
 use std::str::FromStr;
-use toml_edit::{Document, TomlError};
+use toml_edit::{Document, Item, Table, TableLike, Value};
+
+/// The file name of a Cargo manifest.
+pub const MANIFEST_FILE: &str = "Cargo.toml";
 
 #[derive(Clone, Debug)]
 pub struct Manifest(Document);
 
 impl Manifest {
     pub fn version(&self) -> Result<Version> {
-        let version_str = match self.0["package"]["version"].as_str() {
-            Some(version_str) => version_str,
-            None => panic!("TODO: cannot find version in Cargo manifest"),
-        };
+        if self.inherits_version() {
+            return Err(VersioError::InheritedVersion { path: None }.into());
+        }
+
+        Ok(parse_version(
+            self.package_item("version"),
+            "package.version",
+        )?)
+    }
+
+    pub fn set_version<V: fmt::Display>(&mut self, version: &V) {
+        set_string(&mut self.0["package"]["version"], version.to_string());
+    }
+
+    /// Determines whether the package inherits its version from the workspace, that is, whether
+    /// it declares `version.workspace = true`.
+    pub fn inherits_version(&self) -> bool {
+        self.package_item("version")
+            .and_then(|version| version.get("workspace"))
+            .and_then(Item::as_bool)
+            .unwrap_or(false)
+    }
+
+    /// Returns the version under `[workspace.package]`, which members may inherit.
+    pub fn workspace_version(&self) -> Result<Version> {
+        let item = self
+            .workspace_item("package")
+            .and_then(|package| package.get("version"));
+
+        Ok(parse_version(item, "workspace.package.version")?)
+    }
+
+    pub fn set_workspace_version<V: fmt::Display>(&mut self, version: &V) {
+        set_string(
+            &mut self.0["workspace"]["package"]["version"],
+            version.to_string(),
+        );
+    }
 
-        Ok(Version::from_str(version_str)?)
+    pub fn name(&self) -> Option<&str> {
+        self.package_item("name").and_then(Item::as_str)
     }
 
-    pub fn set_version(&mut self, version: &Version) {
-        self.0["package"]["version"] = toml_edit::value(version.to_string());
+    /// Returns the explicit path to the workspace root, as given by `package.workspace`.
+    pub fn workspace_path(&self) -> Option<&str> {
+        self.package_item("workspace").and_then(Item::as_str)
+    }
+
+    pub fn is_package(&self) -> bool {
+        self.0.contains_table("package")
+    }
+
+    pub fn is_workspace(&self) -> bool {
+        self.0.contains_table("workspace")
+    }
+
+    pub fn workspace_members(&self) -> Vec<&str> {
+        self.workspace_paths("members")
+    }
+
+    pub fn workspace_exclude(&self) -> Vec<&str> {
+        self.workspace_paths("exclude")
+    }
+
+    /// Updates the version requirement of every path dependency on the crate `name` to match
+    /// `version`, returning the number of requirements changed.
+    ///
+    /// All dependency tables are searched, including `[target.*]` and `[workspace]`
+    /// dependencies, and a renamed dependency matches on its `package` key. The requirement's
+    /// operator (`=`, `^`, `~`, or none) is kept. Requirements using any other form, such as a
+    /// range, are left alone.
+    pub fn set_dependency_version(&mut self, name: &str, version: &Version) -> usize {
+        let version = VersionBumper::new(version.clone()).clear_build().no_bump();
+        let mut changed = 0;
+
+        for path in self.dependency_tables() {
+            let table = match table_at_mut(self.0.as_table_mut(), &path) {
+                Some(table) => table,
+                None => continue,
+            };
+            for (key, item) in table.iter_mut() {
+                let dep = match item.as_table_like_mut() {
+                    Some(dep) => dep,
+                    None => continue,
+                };
+                if !is_path_dependency_on(dep, key.get(), name) {
+                    continue;
+                }
+                let req = match dep.get_mut("version") {
+                    Some(req) => req,
+                    None => continue,
+                };
+                let updated = match req.as_str() {
+                    Some(current) => rewrite_requirement(current, &version),
+                    None => None,
+                };
+                if let Some(updated) = updated {
+                    debug!(
+                        "updating dependency requirement; dependency={}, req={}",
+                        key.get(),
+                        updated
+                    );
+                    set_string(req, updated);
+                    changed += 1;
+                }
+            }
+        }
+
+        changed
+    }
+
+    fn package_item(&self, key: &str) -> Option<&Item> {
+        self.0.get("package").and_then(|package| package.get(key))
+    }
+
+    fn workspace_item(&self, key: &str) -> Option<&Item> {
+        self.0
+            .get("workspace")
+            .and_then(|workspace| workspace.get(key))
+    }
+
+    /// Returns the key paths of every dependency table which could be present in the manifest.
+    fn dependency_tables(&self) -> Vec<Vec<String>> {
+        let mut paths: Vec<Vec<String>> = DEPENDENCY_KINDS
+            .iter()
+            .map(|kind| vec![kind.to_string()])
+            .collect();
+        paths.push(vec!["workspace".to_string(), "dependencies".to_string()]);
+        if let Some(targets) = self.0.get("target").and_then(Item::as_table) {
+            for (target, _) in targets.iter() {
+                for kind in DEPENDENCY_KINDS {
+                    paths.push(vec![
+                        "target".to_string(),
+                        target.to_string(),
+                        kind.to_string(),
+                    ]);
+                }
+            }
+        }
+        paths
+    }
+
+    fn workspace_paths(&self, key: &str) -> Vec<&str> {
+        match self.workspace_item(key).and_then(Item::as_array) {
+            Some(array) => array.iter().filter_map(|value| value.as_str()).collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// The kinds of dependency tables in a Cargo manifest.
+const DEPENDENCY_KINDS: &[&str] = &["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// Parses the version held by `item`, found under `key`.
+pub(crate) fn parse_version<V: SchemeVersion>(
+    item: Option<&Item>,
+    key: &str,
+) -> result::Result<V, VersioError> {
+    let text = match item {
+        Some(item) => item.as_str().ok_or_else(|| VersioError::VersionNotString {
+            path: None,
+            key: key.to_string(),
+        })?,
+        None => {
+            return Err(VersioError::MissingVersion {
+                path: None,
+                key: key.to_string(),
+            })
+        }
+    };
+
+    V::parse(text)
+}
+
+/// Replaces the value of `item` with a string, keeping any surrounding whitespace and comments.
+pub(crate) fn set_string(item: &mut Item, value: String) {
+    match item.as_value_mut() {
+        Some(current) => {
+            let decor = current.decor().clone();
+            *current = Value::from(value);
+            *current.decor_mut() = decor;
+        }
+        None => *item = toml_edit::value(value),
+    }
+}
+
+fn table_at_mut<'a>(table: &'a mut Table, path: &[String]) -> Option<&'a mut Table> {
+    path.iter().try_fold(table, |table, key| {
+        table.get_mut(key).and_then(Item::as_table_mut)
+    })
+}
+
+/// Determines whether a dependency entry under `key` is a path dependency on the crate `name`.
+fn is_path_dependency_on(dep: &dyn TableLike, key: &str, name: &str) -> bool {
+    let package = dep.get("package").and_then(Item::as_str).unwrap_or(key);
+
+    package == name && dep.get("path").is_some()
+}
+
+/// Rewrites a version requirement to require `version`, keeping its operator.
+///
+/// Returns `None` if the requirement is not a single `=`, `^`, `~`, or bare version.
+fn rewrite_requirement(req: &str, version: &Version) -> Option<String> {
+    let req = req.trim();
+    let op = match req.chars().next() {
+        Some(c @ '=') | Some(c @ '^') | Some(c @ '~') => c.len_utf8(),
+        _ => 0,
+    };
+    let rest = &req[op..];
+    let spaces = rest.len() - rest.trim_start().len();
+    let current = rest.trim_start();
+
+    if current.is_empty()
+        || !current.starts_with(|c: char| c.is_ascii_digit())
+        || current.contains(|c: char| c == ',' || c == '*' || c.is_whitespace())
+    {
+        return None;
     }
+
+    Some(format!("{}{}", &req[..op + spaces], version))
 }
 
 impl FromStr for Manifest {
-    type Err = TomlError;
+    type Err = VersioError;
 
     fn from_str(s: &str) -> result::Result<Self, Self::Err> {
-        Ok(Manifest(Document::from_str(s)?))
+        Document::from_str(s)
+            .map(Manifest)
+            .map_err(|err| VersioError::toml(s, &err))
     }
 }
 
@@ -41,9 +260,292 @@ impl fmt::Display for Manifest {
     }
 }
 
+/// Finds the root manifest of the workspace which the manifest at `path` belongs to.
+///
+/// As with Cargo, an explicit `package.workspace` path is used if present, otherwise the parent
+/// directories are searched for a manifest with a `[workspace]` table. A manifest which is itself
+/// a workspace root is its own root.
+pub fn find_workspace_root(path: &Path, manifest: &Manifest) -> Result<PathBuf> {
+    if manifest.is_workspace() {
+        return Ok(path.to_path_buf());
+    }
+    let path = path.canonicalize()?;
+    let dir = path.parent().unwrap_or_else(|| Path::new("/"));
+
+    if let Some(root) = manifest.workspace_path() {
+        return Ok(dir.join(root).join(MANIFEST_FILE));
+    }
+
+    for ancestor in dir.ancestors().skip(1) {
+        let candidate = ancestor.join(MANIFEST_FILE);
+        if candidate.is_file() && io::read_manifest_file(&candidate)?.is_workspace() {
+            return Ok(candidate);
+        }
+    }
+
+    bail!(
+        "cannot find a workspace root above {}, but its version is inherited from a workspace",
+        path.display()
+    )
+}
+
+/// Reads the version of the manifest at `path`, following inheritance from `[workspace.package]`.
+pub fn read_version(path: &Path) -> Result<Version> {
+    let manifest = io::read_manifest_file(path)?;
+    if !manifest.inherits_version() {
+        return manifest
+            .version()
+            .map_err(|err| error::with_path(err, path));
+    }
+
+    let root_path = find_workspace_root(path, &manifest)?;
+    let root = if root_path == path {
+        manifest
+    } else {
+        io::read_manifest_file(&root_path)?
+    };
+
+    root.workspace_version()
+        .map_err(|err| error::with_path(err, &root_path))
+}
+
+/// A Cargo manifest which is a member of a workspace, along with its path on disk.
+#[derive(Clone, Debug)]
+pub struct Member {
+    pub path: PathBuf,
+    pub manifest: Manifest,
+}
+
+/// A Cargo workspace: its root manifest and all of its discovered member manifests.
+#[derive(Clone, Debug)]
+pub struct Workspace {
+    // The root manifest is always first, followed by the members in discovery order. A root
+    // manifest which also declares a `[package]` is itself a member.
+    manifests: Vec<Member>,
+}
+
+impl Workspace {
+    /// Loads the workspace whose root manifest is at `path`, discovering every member manifest.
+    ///
+    /// Member paths may contain glob patterns, as with Cargo. Any paths under `exclude` are
+    /// skipped and, if the root manifest also declares a `[package]`, it is included as a member.
+    pub fn load(path: &Path) -> Result<Self> {
+        let root_manifest = io::read_manifest_file(path)?;
+        if !root_manifest.is_workspace() {
+            bail!(
+                "manifest is not a workspace root (no [workspace] table): {}",
+                path.display()
+            );
+        }
+        let root = path.parent().unwrap_or_else(|| Path::new("")).to_path_buf();
+
+        let exclude: Vec<PathBuf> = root_manifest
+            .workspace_exclude()
+            .into_iter()
+            .map(|exclude| root.join(exclude))
+            .collect();
+
+        let mut paths = Vec::new();
+        for pattern in root_manifest.workspace_members() {
+            for dir in expand_member(&root, pattern)? {
+                if exclude.iter().any(|exclude| dir.starts_with(exclude)) {
+                    continue;
+                }
+                let manifest_path = dir.join(MANIFEST_FILE);
+                if !manifest_path.is_file() {
+                    bail!(
+                        "workspace member {} has no {}",
+                        dir.display(),
+                        MANIFEST_FILE
+                    );
+                }
+                if manifest_path != path && !paths.contains(&manifest_path) {
+                    paths.push(manifest_path);
+                }
+            }
+        }
+
+        let mut manifests = Vec::with_capacity(paths.len() + 1);
+        manifests.push(Member {
+            path: path.to_path_buf(),
+            manifest: root_manifest,
+        });
+        for path in paths {
+            let manifest = io::read_manifest_file(&path)?;
+            manifests.push(Member { path, manifest });
+        }
+
+        Ok(Workspace { manifests })
+    }
+
+    /// Returns every member package of the workspace, in workspace order.
+    pub fn members(&self) -> Vec<&Member> {
+        self.manifests
+            .iter()
+            .filter(|m| m.manifest.is_package())
+            .collect()
+    }
+
+    /// Returns only the members whose package names are in `names`, in workspace order.
+    ///
+    /// It is an error if any name does not match a workspace member.
+    pub fn select<S: AsRef<str>>(&self, names: &[S]) -> Result<Vec<&Member>> {
+        let members = self.members();
+        for name in names {
+            let name = name.as_ref();
+            if !members.iter().any(|m| m.manifest.name() == Some(name)) {
+                bail!("package '{}' is not a member of the workspace", name);
+            }
+        }
+
+        Ok(members
+            .into_iter()
+            .filter(|m| match m.manifest.name() {
+                Some(member) => names.iter().any(|name| name.as_ref() == member),
+                None => false,
+            })
+            .collect())
+    }
+
+    /// Returns the root manifest of the workspace.
+    pub fn root(&self) -> &Member {
+        &self.manifests[0]
+    }
+
+    /// Returns the version of `member`, following inheritance from `[workspace.package]`.
+    pub fn version_of(&self, member: &Member) -> Result<Version> {
+        if member.manifest.inherits_version() {
+            let root = self.root();
+            root.manifest
+                .workspace_version()
+                .map_err(|err| error::with_path(err, &root.path))
+        } else {
+            member
+                .manifest
+                .version()
+                .map_err(|err| error::with_path(err, &member.path))
+        }
+    }
+
+    /// Sets the version of every member named in `names` and updates any path dependency
+    /// requirements on those members throughout the workspace.
+    ///
+    /// A member which inherits its version has `[workspace.package]` updated in the root manifest
+    /// instead, which changes the version of every member inheriting it, so the requirements on
+    /// all of those are updated as well. Returns the manifests which were changed, in workspace
+    /// order.
+    pub fn set_version<S: AsRef<str>>(&mut self, names: &[S], version: &Version) -> Vec<&Member> {
+        let is_selected = |member: &Member| {
+            member.manifest.is_package()
+                && match member.manifest.name() {
+                    Some(name) => names.iter().any(|n| n.as_ref() == name),
+                    None => false,
+                }
+        };
+        let inherited = self
+            .manifests
+            .iter()
+            .any(|m| is_selected(m) && m.manifest.inherits_version());
+        let mut renamed: Vec<String> = names.iter().map(|n| n.as_ref().to_string()).collect();
+        if inherited {
+            for member in &self.manifests {
+                if let Some(name) = member.manifest.name() {
+                    if member.manifest.inherits_version() && !renamed.iter().any(|n| n == name) {
+                        renamed.push(name.to_string());
+                    }
+                }
+            }
+        }
+
+        let mut changed = Vec::new();
+        for (i, member) in self.manifests.iter_mut().enumerate() {
+            let mut updated = false;
+            if i == 0 && inherited {
+                debug!(
+                    "setting workspace version; version={}, manifest={}",
+                    version,
+                    member.path.display()
+                );
+                member.manifest.set_workspace_version(version);
+                updated = true;
+            }
+            if is_selected(member) && !member.manifest.inherits_version() {
+                debug!(
+                    "setting version; version={}, manifest={}",
+                    version,
+                    member.path.display()
+                );
+                member.manifest.set_version(version);
+                updated = true;
+            }
+            for name in &renamed {
+                if member.manifest.set_dependency_version(name, version) > 0 {
+                    updated = true;
+                }
+            }
+
+            if updated {
+                changed.push(i);
+            }
+        }
+
+        let manifests = &self.manifests;
+        changed.into_iter().map(|i| &manifests[i]).collect()
+    }
+}
+
+fn expand_member(root: &Path, pattern: &str) -> Result<Vec<PathBuf>> {
+    let full = root.join(pattern);
+    let full = full
+        .to_str()
+        .ok_or_else(|| format_err!("workspace member path is not valid UTF-8: {}", pattern))?;
+
+    let mut dirs = Vec::new();
+    for entry in glob::glob(full)? {
+        let entry = entry?;
+        if entry.is_dir() {
+            dirs.push(entry);
+        }
+    }
+    if dirs.is_empty() {
+        bail!(
+            "workspace member '{}' did not match any directories",
+            pattern
+        );
+    }
+
+    Ok(dirs)
+}
+
+#[cfg(test)]
+pub(crate) mod test_helpers {
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    /// Writes `content` to `path` under `dir`, creating its parent directories.
+    pub(crate) fn write(dir: &Path, path: &str, content: &str) {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).expect("dirs should be created");
+        fs::write(path, content).expect("file should be written");
+    }
+
+    /// Creates a workspace whose root manifest is `root`, along with each of the `members` as a
+    /// path and the contents of its manifest.
+    pub(crate) fn fixture(root: &str, members: &[(&str, &str)]) -> TempDir {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        write(dir.path(), "Cargo.toml", root);
+        for (path, content) in members {
+            write(dir.path(), path, content);
+        }
+        dir
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
     fn from_str() {
@@ -52,13 +554,21 @@ mod tests {
         assert_eq!("[package]\nversion = \"1.2.3\"\n", manifest.to_string());
     }
 
-    // #[test]
-    // fn from_str_err() {
-    //     match Manifest::from_str("not a toml file") {
-    //         Err(TomlError) => (),
-    //         Ok(m) => panic!("should not parse: {:?}", m),
-    //     }
-    // }
+    #[test]
+    fn from_str_err() {
+        match Manifest::from_str("[package]\nversion = \"1.2.3\"\nnot a toml file\n") {
+            Err(err) => {
+                assert_eq!(err.exit_code(), 4);
+                match err {
+                    VersioError::Syntax { line, column, .. } => {
+                        assert_eq!((line, column), (3, 5))
+                    }
+                    err => panic!("should be a syntax error: {:?}", err),
+                }
+            }
+            Ok(m) => panic!("should not parse: {:?}", m),
+        }
+    }
 
     #[test]
     fn version() {
@@ -67,16 +577,464 @@ mod tests {
         assert_eq!(manifest.version().unwrap().to_string(), "1.2.3");
     }
 
-    // #[test]
-    // fn version_err() {
-    //     let manifest = Manifest::from_str("[package]\nversion = \"nope.nope\"\n").unwrap();
+    fn version_error(input: &str) -> VersioError {
+        match Manifest::from_str(input).unwrap().version() {
+            Err(err) => err
+                .downcast::<VersioError>()
+                .expect("should be a versio error"),
+            Ok(v) => panic!("should not parse version: {:?}", v),
+        }
+    }
+
+    #[test]
+    fn version_err() {
+        let err = version_error("[package]\nversion = \"nope.nope\"\n");
+
+        assert_eq!(err.exit_code(), 7);
+        assert_eq!(err.to_string(), "invalid version 'nope.nope'");
+    }
+
+    #[test]
+    fn version_missing() {
+        let err = version_error("[package]\nname = \"versio\"\n");
+
+        assert_eq!(err.exit_code(), 5);
+        assert_eq!(err.to_string(), "cannot find version key 'package.version'");
+    }
+
+    #[test]
+    fn version_not_string() {
+        let err = version_error("[package]\nversion = 1.2\n");
+
+        assert_eq!(err.exit_code(), 6);
+        assert_eq!(
+            err.to_string(),
+            "version key 'package.version' is not a string"
+        );
+    }
+
+    #[test]
+    fn set_version_keeps_comment() {
+        let mut manifest = Manifest::from_str("[package]\nversion = \"1.2.3\" # here\n").unwrap();
+        manifest.set_version(&Version::from_str("1.2.4").unwrap());
+
+        assert_eq!(
+            manifest.to_string(),
+            "[package]\nversion = \"1.2.4\" # here\n"
+        );
+    }
+
+    #[test]
+    fn inherits_version() {
+        for input in &[
+            "[package]\nversion.workspace = true\n",
+            "[package]\nversion = { workspace = true }\n",
+        ] {
+            let manifest = Manifest::from_str(input).unwrap();
+
+            assert!(manifest.inherits_version());
+            match manifest.version() {
+                Err(err) => {
+                    let err = err
+                        .downcast::<VersioError>()
+                        .expect("should be a versio error");
+                    assert_eq!(err.exit_code(), 11);
+                    assert!(err.to_string().contains("inherited from the workspace"));
+                }
+                Ok(v) => panic!("should not read inherited version: {:?}", v),
+            }
+        }
+    }
+
+    #[test]
+    fn inherits_version_not() {
+        let manifest = Manifest::from_str("[package]\nversion = \"1.2.3\"\n").unwrap();
+
+        assert!(!manifest.inherits_version());
+    }
+
+    #[test]
+    fn workspace_version() {
+        let mut manifest = Manifest::from_str(
+            "[workspace]\nmembers = []\n\n[workspace.package]\nversion = \"0.4.0\" # shared\n",
+        )
+        .unwrap();
+
+        assert_eq!(manifest.workspace_version().unwrap().to_string(), "0.4.0");
+
+        manifest.set_workspace_version(&Version::from_str("0.5.0").unwrap());
+
+        assert_eq!(
+            manifest.to_string(),
+            "[workspace]\nmembers = []\n\n[workspace.package]\nversion = \"0.5.0\" # shared\n"
+        );
+    }
+
+    #[test]
+    fn workspace_version_missing() {
+        let manifest = Manifest::from_str("[workspace]\nmembers = []\n").unwrap();
+
+        match manifest.workspace_version() {
+            Err(err) => assert!(err.to_string().contains("workspace.package.version")),
+            Ok(v) => panic!("should not read version: {:?}", v),
+        }
+    }
+
+    mod set_dependency_version {
+        use super::*;
+
+        fn set(input: &str, name: &str, version: &str) -> (usize, String) {
+            let mut manifest = Manifest::from_str(input).unwrap();
+            let changed =
+                manifest.set_dependency_version(name, &Version::from_str(version).unwrap());
+
+            (changed, manifest.to_string())
+        }
+
+        #[test]
+        fn inline_table() {
+            assert_eq!(
+                set(
+                    "[dependencies]\nfoo = { path = \"../foo\", version = \"1.2.3\" }\n",
+                    "foo",
+                    "1.3.0"
+                ),
+                (
+                    1,
+                    "[dependencies]\nfoo = { path = \"../foo\", version = \"1.3.0\" }\n"
+                        .to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn table() {
+            assert_eq!(
+                set(
+                    "[dev-dependencies.foo]\npath = \"../foo\"\nversion = \"1.2.3\" # pinned\n",
+                    "foo",
+                    "2.0.0"
+                ),
+                (
+                    1,
+                    "[dev-dependencies.foo]\npath = \"../foo\"\nversion = \"2.0.0\" # pinned\n"
+                        .to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn operators() {
+            let input = concat!(
+                "[dependencies]\n",
+                "a = { path = \"a\", version = \"=1.2.3\" }\n",
+                "[build-dependencies]\n",
+                "a = { path = \"a\", version = \"^ 1.2\" }\n",
+                "[dev-dependencies]\n",
+                "a = { path = \"a\", version = \"~1\" }\n",
+            );
+            let expected = concat!(
+                "[dependencies]\n",
+                "a = { path = \"a\", version = \"=1.3.0\" }\n",
+                "[build-dependencies]\n",
+                "a = { path = \"a\", version = \"^ 1.3.0\" }\n",
+                "[dev-dependencies]\n",
+                "a = { path = \"a\", version = \"~1.3.0\" }\n",
+            );
+
+            assert_eq!(set(input, "a", "1.3.0"), (3, expected.to_string()));
+        }
+
+        #[test]
+        fn renamed() {
+            assert_eq!(
+                set(
+                    "[dependencies]\nbar = { package = \"foo\", path = \"foo\", version = \"1.0.0\" }\n",
+                    "foo",
+                    "1.1.0"
+                ),
+                (
+                    1,
+                    "[dependencies]\nbar = { package = \"foo\", path = \"foo\", version = \"1.1.0\" }\n"
+                        .to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn renamed_not_by_key() {
+            let input =
+                "[dependencies]\nfoo = { package = \"bar\", path = \"bar\", version = \"1.0.0\" }\n";
+
+            assert_eq!(set(input, "foo", "1.1.0"), (0, input.to_string()));
+        }
+
+        #[test]
+        fn target() {
+            assert_eq!(
+                set(
+                    "[target.'cfg(unix)'.dependencies]\nfoo = { path = \"foo\", version = \"0.1.0\" }\n",
+                    "foo",
+                    "0.2.0"
+                ),
+                (
+                    1,
+                    "[target.'cfg(unix)'.dependencies]\nfoo = { path = \"foo\", version = \"0.2.0\" }\n"
+                        .to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn workspace_dependencies() {
+            assert_eq!(
+                set(
+                    "[workspace.dependencies]\nfoo = { path = \"foo\", version = \"0.1.0\" }\n",
+                    "foo",
+                    "0.2.0"
+                ),
+                (
+                    1,
+                    "[workspace.dependencies]\nfoo = { path = \"foo\", version = \"0.2.0\" }\n"
+                        .to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn strips_build_metadata() {
+            assert_eq!(
+                set(
+                    "[dependencies]\nfoo = { path = \"foo\", version = \"0.1.0\" }\n",
+                    "foo",
+                    "0.2.0-rc.1+abc"
+                ),
+                (
+                    1,
+                    "[dependencies]\nfoo = { path = \"foo\", version = \"0.2.0-rc.1\" }\n"
+                        .to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn ignored() {
+            let input = concat!(
+                "[dependencies]\n",
+                "foo = \"1.0.0\"\n",
+                "bar = { version = \"1.0.0\" }\n",
+                "baz = { path = \"baz\", version = \">=1.0, <2\" }\n",
+                "qux = { path = \"qux\" }\n",
+                "[dependencies.quux]\n",
+                "path = \"quux\"\n",
+            );
+
+            for name in &["foo", "bar", "baz", "qux", "quux", "nope"] {
+                assert_eq!(set(input, name, "2.0.0"), (0, input.to_string()));
+            }
+        }
+    }
+
+    #[test]
+    fn name() {
+        let manifest = Manifest::from_str("[package]\nname = \"versio\"\n").unwrap();
+
+        assert_eq!(manifest.name(), Some("versio"));
+    }
+
+    #[test]
+    fn workspace_members() {
+        let manifest = Manifest::from_str(
+            "[workspace]\nmembers = [\"a\", \"crates/*\"]\nexclude = [\"crates/x\"]\n",
+        )
+        .unwrap();
+
+        assert!(manifest.is_workspace());
+        assert!(!manifest.is_package());
+        assert_eq!(manifest.workspace_members(), vec!["a", "crates/*"]);
+        assert_eq!(manifest.workspace_exclude(), vec!["crates/x"]);
+    }
+
+    #[test]
+    fn workspace_members_none() {
+        let manifest = Manifest::from_str("[package]\nversion = \"1.2.3\"\n").unwrap();
+
+        assert!(!manifest.is_workspace());
+        assert!(manifest.workspace_members().is_empty());
+    }
+
+    mod workspace {
+        use super::*;
+        use crate::cargo::test_helpers::write;
+        use tempfile::TempDir;
 
-    //     match manifest.version() {
-    //         Err(e) => {
-    //             dbg!(&e);
-    //             panic!("err: {:?}", e);
-    //         }
-    //         Ok(v) => panic!("should not parse version: {:?}", v),
-    //     }
-    // }
+        fn package(name: &str) -> String {
+            format!("[package]\nname = \"{}\"\nversion = \"1.2.3\"\n", name)
+        }
+
+        fn names<'a>(members: &[&'a Member]) -> Vec<&'a str> {
+            members.iter().filter_map(|m| m.manifest.name()).collect()
+        }
+
+        fn fixture() -> TempDir {
+            crate::cargo::test_helpers::fixture(
+                "[workspace]\nmembers = [\"core\", \"crates/*\"]\nexclude = [\"crates/skip\"]\n",
+                &[
+                    ("core/Cargo.toml", &package("core")),
+                    ("crates/one/Cargo.toml", &package("one")),
+                    ("crates/two/Cargo.toml", &package("two")),
+                    ("crates/skip/Cargo.toml", &package("skip")),
+                ],
+            )
+        }
+
+        #[test]
+        fn load() {
+            let dir = fixture();
+            let workspace = Workspace::load(&dir.path().join("Cargo.toml")).unwrap();
+            let members = workspace.members();
+
+            assert_eq!(names(&members), vec!["core", "one", "two"]);
+            assert_eq!(members[1].path, dir.path().join("crates/one/Cargo.toml"));
+        }
+
+        #[test]
+        fn load_root_package() {
+            let dir = fixture();
+            write(
+                dir.path(),
+                "Cargo.toml",
+                &format!("{}\n[workspace]\nmembers = [\"core\"]\n", package("root")),
+            );
+            let workspace = Workspace::load(&dir.path().join("Cargo.toml")).unwrap();
+
+            assert_eq!(names(&workspace.members()), vec!["root", "core"]);
+        }
+
+        #[test]
+        fn load_virtual_root() {
+            let dir = fixture();
+            let workspace = Workspace::load(&dir.path().join("Cargo.toml")).unwrap();
+
+            assert_eq!(workspace.root().path, dir.path().join("Cargo.toml"));
+            assert!(!workspace.root().manifest.is_package());
+        }
+
+        #[test]
+        fn load_not_workspace() {
+            let dir = fixture();
+            match Workspace::load(&dir.path().join("core/Cargo.toml")) {
+                Err(err) => assert!(err.to_string().contains("not a workspace root")),
+                Ok(w) => panic!("should not load: {:?}", w),
+            }
+        }
+
+        #[test]
+        fn load_member_without_manifest() {
+            let dir = fixture();
+            fs::create_dir_all(dir.path().join("crates/empty")).unwrap();
+            match Workspace::load(&dir.path().join("Cargo.toml")) {
+                Err(err) => assert!(err.to_string().contains("has no Cargo.toml")),
+                Ok(w) => panic!("should not load: {:?}", w),
+            }
+        }
+
+        #[test]
+        fn set_version_inherited() {
+            let dir = fixture();
+            write(
+                dir.path(),
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"core\", \"crates/*\"]\nexclude = [\"crates/skip\"]\n\n[workspace.package]\nversion = \"1.2.3\"\n",
+            );
+            write(
+                dir.path(),
+                "crates/one/Cargo.toml",
+                "[package]\nname = \"one\"\nversion.workspace = true\n",
+            );
+            let mut workspace = Workspace::load(&dir.path().join("Cargo.toml")).unwrap();
+            let one = workspace.select(&["one"]).unwrap()[0].clone();
+
+            assert_eq!(workspace.version_of(&one).unwrap().to_string(), "1.2.3");
+
+            let version = Version::from_str("1.3.0").unwrap();
+            let changed: Vec<PathBuf> = workspace
+                .set_version(&["one", "two"], &version)
+                .into_iter()
+                .map(|m| m.path.clone())
+                .collect();
+
+            assert_eq!(
+                changed,
+                vec![
+                    dir.path().join("Cargo.toml"),
+                    dir.path().join("crates/two/Cargo.toml")
+                ]
+            );
+            assert_eq!(
+                workspace.root().manifest.workspace_version().unwrap(),
+                version
+            );
+        }
+
+        #[test]
+        fn find_root() {
+            let dir = fixture();
+            let path = dir.path().join("crates/one/Cargo.toml");
+            let manifest = io::read_manifest_file(&path).unwrap();
+
+            assert_eq!(
+                find_workspace_root(&path, &manifest).unwrap(),
+                dir.path().canonicalize().unwrap().join("Cargo.toml")
+            );
+        }
+
+        #[test]
+        fn find_root_explicit() {
+            let dir = fixture();
+            write(
+                dir.path(),
+                "other/Cargo.toml",
+                "[package]\nname = \"other\"\nworkspace = \"../core\"\n",
+            );
+            let path = dir.path().join("other/Cargo.toml");
+            let manifest = io::read_manifest_file(&path).unwrap();
+
+            assert_eq!(
+                find_workspace_root(&path, &manifest).unwrap(),
+                dir.path()
+                    .canonicalize()
+                    .unwrap()
+                    .join("other/../core/Cargo.toml")
+            );
+        }
+
+        #[test]
+        fn find_root_is_root() {
+            let dir = fixture();
+            let path = dir.path().join("Cargo.toml");
+            let manifest = io::read_manifest_file(&path).unwrap();
+
+            assert_eq!(find_workspace_root(&path, &manifest).unwrap(), path);
+        }
+
+        #[test]
+        fn select() {
+            let dir = fixture();
+            let workspace = Workspace::load(&dir.path().join("Cargo.toml")).unwrap();
+            let members = workspace.select(&["two", "core"]).unwrap();
+
+            assert_eq!(names(&members), vec!["core", "two"]);
+        }
+
+        #[test]
+        fn select_unknown() {
+            let dir = fixture();
+            let workspace = Workspace::load(&dir.path().join("Cargo.toml")).unwrap();
+            match workspace.select(&["nope"]) {
+                Err(err) => assert!(err.to_string().contains("'nope' is not a member")),
+                Ok(m) => panic!("should not select: {:?}", m),
+            }
+        }
+    }
 }
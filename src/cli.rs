@@ -2,14 +2,31 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::version::{BuildMetadata, PreRelease, Version};
+use crate::calver;
+use crate::config::Config;
+use crate::helm::AppVersion;
+use crate::pattern::Pattern;
+use crate::pep440::Pep440Version;
+use crate::source::Source;
+use crate::version::{BuildMetadata, PreRelease, Scheme, Version, VersionReq};
+use chrono::{Local, NaiveDate};
+use failure::bail;
+use log::{debug, info};
 use std::io::{Read, Write};
 use std::path::PathBuf;
-use structopt::clap::AppSettings::{InferSubcommands, UnifiedHelpMessage, VersionlessSubcommands};
+use std::str::FromStr;
+use structopt::clap::{
+    self,
+    AppSettings::{InferSubcommands, UnifiedHelpMessage, VersionlessSubcommands},
+};
 use structopt::StructOpt;
 
 pub(crate) mod bump;
+pub(crate) mod check;
+pub(crate) mod get;
+pub(crate) mod report;
 pub(crate) mod util;
+pub(crate) mod verify;
 
 const AUTHOR: &str = concat!(env!("CARGO_PKG_AUTHORS"), "\n\n");
 
@@ -62,11 +79,87 @@ pub(super) struct Args {
     #[structopt(short = "v", long = "verbose", parse(from_occurrences), global = true)]
     verbose: usize,
 
+    /// The format of the output, either text or json.
+    ///
+    /// With json, a command prints a single JSON object describing its outcome instead of its
+    /// usual output, such as the previous and next version of a bump along with the files it
+    /// wrote.
+    #[structopt(
+        long = "format",
+        default_value = "text",
+        global = true,
+        raw(value_name = r#""FORMAT""#, possible_values = r#"&["text", "json"]"#)
+    )]
+    output_format: Format,
+
+    /// Prints the version as shell variables.
+    ///
+    /// Instead of its usual output, a command prints `export` lines which set VERSIO_VERSION,
+    /// VERSIO_MAJOR, VERSIO_MINOR, VERSIO_PATCH and VERSIO_PRE, and VERSIO_PREVIOUS after a bump,
+    /// quoted for a POSIX shell, so that they can be read with `eval "$(versio ...)"`.
+    #[structopt(long = "export", global = true)]
+    export: bool,
+
+    /// Appends the version variables to FILE as KEY=VALUE lines, as with $GITHUB_OUTPUT.
+    ///
+    /// The file is created if it does not exist. Implies --export.
+    #[structopt(
+        long = "export-file",
+        global = true,
+        raw(value_name = r#""FILE""#),
+        parse(from_os_str)
+    )]
+    export_file: Option<PathBuf>,
+
+    /// Prints the old and new version and a unified diff of each file which would change, without
+    /// writing anything.
+    #[structopt(short = "n", long = "dry-run", global = true)]
+    dry_run: bool,
+
     #[structopt(subcommand)]
     subcmd: SubCommand,
 }
 
 impl Args {
+    /// Parses the command line, exiting with a usage error if it is invalid.
+    ///
+    /// As `--dry-run` is global, it cannot be declared to conflict with the options of each
+    /// subcommand, so those it cannot be used with are rejected here instead, along with the
+    /// commands which never write files.
+    pub(crate) fn parse() -> Self {
+        let matches = Args::clap().get_matches();
+        let mut leaf = &matches;
+        let mut names = Vec::new();
+        while let (name, Some(subcmd)) = leaf.subcommand() {
+            names.push(name);
+            leaf = subcmd;
+        }
+        if leaf.is_present("dry_run") {
+            // Only the bumps which write files have anything to preview
+            if names.first() != Some(&"bump") || names.get(1) == Some(&"stdin") {
+                clap::Error::with_description(
+                    &format!(
+                        "The argument '--dry-run' cannot be used with 'versio {}'",
+                        names.join(" ")
+                    ),
+                    clap::ErrorKind::ArgumentConflict,
+                )
+                .exit();
+            }
+            for other in &["stdout", "commit", "tag"] {
+                if leaf.is_present(other) {
+                    clap::Error::with_description(
+                        &format!("The argument '--dry-run' cannot be used with '--{}'", other),
+                        clap::ErrorKind::ArgumentConflict,
+                    )
+                    .exit();
+                }
+            }
+        }
+
+        Args::from_clap(&matches)
+    }
+
     pub(crate) fn subcmd(self) -> SubCommand {
         self.subcmd
     }
@@ -78,6 +171,23 @@ impl Args {
     pub(crate) fn verbosity(&self) -> usize {
         self.verbose
     }
+
+    /// Returns whether the files which would change are previewed instead of being written.
+    pub(crate) fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Returns the format of the output, failing if both JSON and shell variables are asked for.
+    pub(crate) fn format(&self) -> crate::Result<Format> {
+        if !self.export && self.export_file.is_none() {
+            return Ok(self.output_format.clone());
+        }
+        if self.output_format == Format::Json {
+            bail!("--export cannot be used with --format json");
+        }
+
+        Ok(Format::Export(self.export_file.clone()))
+    }
 }
 
 #[derive(Debug, StructOpt)]
@@ -89,6 +199,24 @@ pub(super) enum SubCommand {
         #[structopt(subcommand)]
         subcmd: BumpSubCommand,
     },
+
+    /// Prints the current version, or a single part of it.
+    #[structopt(raw(author = "AUTHOR"))]
+    Get {
+        #[structopt(subcommand)]
+        subcmd: GetSubCommand,
+    },
+
+    /// Fails unless the current version matches an expected version or requirement.
+    #[structopt(raw(author = "AUTHOR"))]
+    Check {
+        #[structopt(subcommand)]
+        subcmd: CheckSubCommand,
+    },
+
+    /// Fails unless every given file holds the same version, printing where each was found.
+    #[structopt(raw(author = "AUTHOR"))]
+    Verify(CliVerifyArgs),
 }
 
 #[derive(Debug, StructOpt)]
@@ -108,12 +236,113 @@ pub(super) enum BumpSubCommand {
         subcmd: BumpFileSubCommand,
     },
 
+    /// Bumps a version found by a regular expression within an arbitrary text file.
+    #[structopt(raw(author = "AUTHOR"))]
+    Pattern {
+        #[structopt(subcommand)]
+        subcmd: BumpPatternSubCommand,
+    },
+
+    /// Bumps the version of an npm package.json and its package-lock.json.
+    #[structopt(raw(author = "AUTHOR"))]
+    Npm {
+        #[structopt(subcommand)]
+        subcmd: BumpNpmSubCommand,
+    },
+
+    /// Bumps the version of a Helm Chart.yaml, and optionally its appVersion.
+    #[structopt(raw(author = "AUTHOR"))]
+    Helm {
+        #[structopt(subcommand)]
+        subcmd: BumpHelmSubCommand,
+    },
+
+    /// Bumps the project version of a Maven pom.xml.
+    #[structopt(raw(author = "AUTHOR"))]
+    Maven {
+        #[structopt(subcommand)]
+        subcmd: BumpMavenSubCommand,
+    },
+
+    /// Bumps the version of a Gradle gradle.properties.
+    #[structopt(raw(author = "AUTHOR"))]
+    Gradle {
+        #[structopt(subcommand)]
+        subcmd: BumpGradleSubCommand,
+    },
+
+    /// Bumps the version labels and build arguments of a Dockerfile.
+    #[structopt(raw(author = "AUTHOR"))]
+    Dockerfile {
+        #[structopt(subcommand)]
+        subcmd: BumpDockerfileSubCommand,
+    },
+
+    /// Bumps the version of a Python pyproject.toml, under [project] or [tool.poetry].
+    #[structopt(raw(author = "AUTHOR"))]
+    Pyproject {
+        #[structopt(subcommand)]
+        subcmd: BumpPyProjectSubCommand,
+    },
+
     /// TODO: description.
     #[structopt(raw(author = "AUTHOR"))]
     Stdin {
         #[structopt(subcommand)]
         subcmd: BumpStdinSubCommand,
     },
+
+    /// Bumps the major version of every file listed in .versio.toml.
+    #[structopt(raw(author = "AUTHOR"))]
+    Major(CliBumpConfigArgs),
+
+    /// Bumps the minor version of every file listed in .versio.toml.
+    #[structopt(raw(author = "AUTHOR"))]
+    Minor(CliBumpConfigArgs),
+
+    /// Bumps the patch version of every file listed in .versio.toml.
+    #[structopt(raw(author = "AUTHOR"))]
+    Patch(CliBumpConfigArgs),
+
+    /// Bumps every file listed in .versio.toml for a breaking change.
+    #[structopt(raw(author = "AUTHOR"))]
+    Breaking(CliBumpConfigArgs),
+
+    /// Bumps every file listed in .versio.toml for a backwards compatible feature.
+    #[structopt(raw(author = "AUTHOR"))]
+    Feature(CliBumpConfigArgs),
+
+    /// Bumps every file listed in .versio.toml for a backwards compatible fix.
+    #[structopt(raw(author = "AUTHOR"))]
+    Fix(CliBumpConfigArgs),
+
+    /// Bumps the pre-release counter of every file listed in .versio.toml.
+    #[structopt(raw(author = "AUTHOR"))]
+    Pre(CliBumpConfigPreArgs),
+
+    /// Bumps the PEP 440 post-release of every file listed in .versio.toml.
+    #[structopt(raw(author = "AUTHOR"))]
+    Post(CliBumpConfigArgs),
+
+    /// Bumps the PEP 440 development release of every file listed in .versio.toml.
+    #[structopt(raw(author = "AUTHOR"))]
+    Dev(CliBumpConfigArgs),
+
+    /// Rolls every file listed in .versio.toml to today's calendar version.
+    #[structopt(raw(author = "AUTHOR"))]
+    Calver(CliBumpConfigCalverArgs),
+
+    /// Promotes the pre-release of every file listed in .versio.toml to its final version.
+    #[structopt(raw(author = "AUTHOR"))]
+    Release(CliBumpConfigReleaseArgs),
+
+    /// Bumps every file listed in .versio.toml for the Conventional Commits since the last tag.
+    #[structopt(raw(author = "AUTHOR"))]
+    Auto(CliBumpConfigAutoArgs),
+
+    /// Sets the version, or parts of it, in every file listed in .versio.toml.
+    #[structopt(raw(author = "AUTHOR"))]
+    Set(CliBumpConfigSetArgs),
 }
 
 #[derive(Debug, StructOpt)]
@@ -131,6 +360,30 @@ pub(super) enum BumpCargoSubCommand {
     #[structopt(raw(author = "AUTHOR"))]
     Patch(CliBumpCargoArgs),
 
+    /// Bumps for a breaking change: major from 1.0.0 on, minor of 0.y.z, or patch of 0.0.z.
+    #[structopt(raw(author = "AUTHOR"))]
+    Breaking(CliBumpCargoArgs),
+
+    /// Bumps for a backwards compatible feature: minor from 1.0.0 on, otherwise patch.
+    #[structopt(raw(author = "AUTHOR"))]
+    Feature(CliBumpCargoArgs),
+
+    /// Bumps for a backwards compatible fix, which is always the patch version.
+    #[structopt(raw(author = "AUTHOR"))]
+    Fix(CliBumpCargoArgs),
+
+    /// Bumps the pre-release counter, for example from 1.2.0-rc.1 to 1.2.0-rc.2.
+    #[structopt(raw(author = "AUTHOR"))]
+    Pre(CliBumpCargoPreArgs),
+
+    /// Promotes a pre-release to its final version, for example from 1.2.0-rc.2 to 1.2.0.
+    #[structopt(raw(author = "AUTHOR"))]
+    Release(CliBumpCargoReleaseArgs),
+
+    /// Bumps for the most significant Conventional Commit since the last version tag in git.
+    #[structopt(raw(author = "AUTHOR"))]
+    Auto(CliBumpCargoAutoArgs),
+
     /// TODO: description.
     #[structopt(raw(author = "AUTHOR"))]
     Set(CliBumpCargoSetArgs),
@@ -151,6 +404,30 @@ pub(super) enum BumpFileSubCommand {
     #[structopt(raw(author = "AUTHOR"))]
     Patch(CliBumpFileArgs),
 
+    /// Bumps for a breaking change: major from 1.0.0 on, minor of 0.y.z, or patch of 0.0.z.
+    #[structopt(raw(author = "AUTHOR"))]
+    Breaking(CliBumpFileArgs),
+
+    /// Bumps for a backwards compatible feature: minor from 1.0.0 on, otherwise patch.
+    #[structopt(raw(author = "AUTHOR"))]
+    Feature(CliBumpFileArgs),
+
+    /// Bumps for a backwards compatible fix, which is always the patch version.
+    #[structopt(raw(author = "AUTHOR"))]
+    Fix(CliBumpFileArgs),
+
+    /// Bumps the pre-release counter, for example from 1.2.0-rc.1 to 1.2.0-rc.2.
+    #[structopt(raw(author = "AUTHOR"))]
+    Pre(CliBumpFilePreArgs),
+
+    /// Promotes a pre-release to its final version, for example from 1.2.0-rc.2 to 1.2.0.
+    #[structopt(raw(author = "AUTHOR"))]
+    Release(CliBumpFileReleaseArgs),
+
+    /// Bumps for the most significant Conventional Commit since the last version tag in git.
+    #[structopt(raw(author = "AUTHOR"))]
+    Auto(CliBumpFileAutoArgs),
+
     /// TODO: description.
     #[structopt(raw(author = "AUTHOR"))]
     Set(CliBumpFileSetArgs),
@@ -158,282 +435,3231 @@ pub(super) enum BumpFileSubCommand {
 
 #[derive(Debug, StructOpt)]
 #[structopt(rename_all = "kebab-case")]
-pub(super) enum BumpStdinSubCommand {
-    /// TODO: description.
+pub(super) enum BumpPatternSubCommand {
+    /// Bumps the major version matched in a file.
     #[structopt(raw(author = "AUTHOR"))]
-    Major(CliBumpStdinArgs),
+    Major(CliBumpPatternArgs),
 
-    /// TODO: description.
+    /// Bumps the minor version matched in a file.
     #[structopt(raw(author = "AUTHOR"))]
-    Minor(CliBumpStdinArgs),
+    Minor(CliBumpPatternArgs),
 
-    /// TODO: description.
+    /// Bumps the patch version matched in a file.
     #[structopt(raw(author = "AUTHOR"))]
-    Patch(CliBumpStdinArgs),
+    Patch(CliBumpPatternArgs),
 
-    /// TODO: description.
+    /// Bumps the version matched in a file for a breaking change.
     #[structopt(raw(author = "AUTHOR"))]
-    Set(CliBumpStdinSetArgs),
-}
+    Breaking(CliBumpPatternArgs),
 
-#[derive(Debug, StructOpt)]
-pub(super) struct BumpCommonArgs {
-    /// TODO: description.
-    #[structopt(long = "pre-release", short = "P", rename_all = "screaming_snake_case")]
-    pre_release: Option<PreRelease>,
+    /// Bumps the version matched in a file for a backwards compatible feature.
+    #[structopt(raw(author = "AUTHOR"))]
+    Feature(CliBumpPatternArgs),
 
-    /// TODO: description.
-    #[structopt(
-        long = "build-metadata",
-        short = "b",
-        rename_all = "screaming_snake_case"
-    )]
-    build_metadata: Option<BuildMetadata>,
-}
+    /// Bumps the version matched in a file for a backwards compatible fix.
+    #[structopt(raw(author = "AUTHOR"))]
+    Fix(CliBumpPatternArgs),
 
-#[derive(Debug, StructOpt)]
-pub(super) struct BumpSetArgs {
-    /// TODO: description.
-    #[structopt(short = "M", long = "major", rename_all = "screaming_snake_case")]
-    major: Option<u64>,
+    /// Bumps the pre-release counter of the version matched in a file.
+    #[structopt(raw(author = "AUTHOR"))]
+    Pre(CliBumpPatternPreArgs),
 
-    /// TODO: description.
-    #[structopt(short = "m", long = "minor", rename_all = "screaming_snake_case")]
-    minor: Option<u64>,
+    /// Bumps the PEP 440 post-release of the version matched in a file.
+    #[structopt(raw(author = "AUTHOR"))]
+    Post(CliBumpPatternArgs),
 
-    /// TODO: description.
-    #[structopt(short = "p", long = "patch", rename_all = "screaming_snake_case")]
-    patch: Option<u64>,
+    /// Bumps the PEP 440 development release of the version matched in a file.
+    #[structopt(raw(author = "AUTHOR"))]
+    Dev(CliBumpPatternArgs),
 
-    /// TODO: description.
-    #[structopt(
-        short = "V",
-        long = "version",
-        rename_all = "screaming_snake_case",
-        raw(
-            conflicts_with_all = r#"&["MAJOR", "MINOR", "PATCH", "PRE_RELEASE", "BUILD_METADATA"]"#
-        )
-    )]
-    version: Option<Version>,
+    /// Rolls the version matched in a file to today's calendar version.
+    #[structopt(raw(author = "AUTHOR"))]
+    Calver(CliBumpPatternCalverArgs),
 
-    /// TODO: description.
-    #[structopt(
-        long = "no-pre-release",
-        raw(conflicts_with_all = r#"&["PRE_RELEASE"]"#)
-    )]
-    no_pre_release: bool,
+    /// Promotes the pre-release matched in a file to its final version.
+    #[structopt(raw(author = "AUTHOR"))]
+    Release(CliBumpPatternReleaseArgs),
 
-    /// TODO: description.
-    #[structopt(
-        long = "no-build-metadata",
-        raw(conflicts_with_all = r#"&["BUILD_METADATA"]"#)
-    )]
-    no_build_metadata: bool,
+    /// Bumps the version matched in a file for the Conventional Commits since the last tag.
+    #[structopt(raw(author = "AUTHOR"))]
+    Auto(CliBumpPatternAutoArgs),
+
+    /// Sets the version, or parts of it, matched in a file.
+    #[structopt(raw(author = "AUTHOR"))]
+    Set(CliBumpPatternSetArgs),
 }
 
 #[derive(Debug, StructOpt)]
-pub(super) struct CliBumpCargoArgs {
-    #[structopt(flatten)]
-    common: BumpCommonArgs,
+#[structopt(rename_all = "kebab-case")]
+pub(super) enum BumpNpmSubCommand {
+    /// Bumps the major version of a package.
+    #[structopt(raw(author = "AUTHOR"))]
+    Major(CliBumpNpmArgs),
 
-    /// TODO: description.
-    #[structopt(
-        rename_all = "screaming_snake_case",
-        raw(default_value = "\"Cargo.toml\"")
-    )]
-    manifest: PathBuf,
+    /// Bumps the minor version of a package.
+    #[structopt(raw(author = "AUTHOR"))]
+    Minor(CliBumpNpmArgs),
 
-    /// TODO: description.
-    #[structopt(short = "s", long)]
-    stdout: bool,
-}
+    /// Bumps the patch version of a package.
+    #[structopt(raw(author = "AUTHOR"))]
+    Patch(CliBumpNpmArgs),
 
-#[derive(Debug, StructOpt)]
-pub(super) struct CliBumpCargoSetArgs {
-    #[structopt(flatten)]
-    pub common: CliBumpCargoArgs,
+    /// Bumps the version of a package for a breaking change.
+    #[structopt(raw(author = "AUTHOR"))]
+    Breaking(CliBumpNpmArgs),
 
-    #[structopt(flatten)]
-    pub set: BumpSetArgs,
-}
+    /// Bumps the version of a package for a backwards compatible feature.
+    #[structopt(raw(author = "AUTHOR"))]
+    Feature(CliBumpNpmArgs),
 
-#[derive(Debug, StructOpt)]
-pub(super) struct CliBumpFileArgs {
-    #[structopt(flatten)]
-    common: BumpCommonArgs,
+    /// Bumps the version of a package for a backwards compatible fix.
+    #[structopt(raw(author = "AUTHOR"))]
+    Fix(CliBumpNpmArgs),
 
-    /// TODO: description.
-    #[structopt(
-        rename_all = "screaming_snake_case",
-        raw(default_value = "\"VERSION.txt\"")
-    )]
-    file: PathBuf,
+    /// Bumps the pre-release counter of a package.
+    #[structopt(raw(author = "AUTHOR"))]
+    Pre(CliBumpNpmPreArgs),
 
-    /// TODO: description.
-    #[structopt(short = "s", long)]
-    stdout: bool,
-}
+    /// Promotes the pre-release of a package to its final version.
+    #[structopt(raw(author = "AUTHOR"))]
+    Release(CliBumpNpmReleaseArgs),
 
-#[derive(Debug, StructOpt)]
-pub(super) struct CliBumpFileSetArgs {
-    #[structopt(flatten)]
-    common: CliBumpFileArgs,
+    /// Bumps the version of a package for the Conventional Commits since the last tag.
+    #[structopt(raw(author = "AUTHOR"))]
+    Auto(CliBumpNpmAutoArgs),
 
-    #[structopt(flatten)]
-    set: BumpSetArgs,
+    /// Sets the version, or parts of it, of a package.
+    #[structopt(raw(author = "AUTHOR"))]
+    Set(CliBumpNpmSetArgs),
 }
 
 #[derive(Debug, StructOpt)]
-pub(super) struct CliBumpStdinArgs {
-    #[structopt(flatten)]
-    common: BumpCommonArgs,
-}
+#[structopt(rename_all = "kebab-case")]
+pub(super) enum BumpHelmSubCommand {
+    /// Bumps the major version of a chart.
+    #[structopt(raw(author = "AUTHOR"))]
+    Major(CliBumpHelmArgs),
 
-#[derive(Debug, StructOpt)]
-pub(super) struct CliBumpStdinSetArgs {
-    #[structopt(flatten)]
-    common: CliBumpStdinArgs,
+    /// Bumps the minor version of a chart.
+    #[structopt(raw(author = "AUTHOR"))]
+    Minor(CliBumpHelmArgs),
 
-    #[structopt(flatten)]
-    set: BumpSetArgs,
-}
+    /// Bumps the patch version of a chart.
+    #[structopt(raw(author = "AUTHOR"))]
+    Patch(CliBumpHelmArgs),
 
-/// Build time metadata
-struct BuildInfo;
+    /// Bumps the version of a chart for a breaking change.
+    #[structopt(raw(author = "AUTHOR"))]
+    Breaking(CliBumpHelmArgs),
 
-impl BuildInfo {
-    fn version_short() -> &'static str {
-        include_str!(concat!(env!("OUT_DIR"), "/version_short.txt"))
-    }
+    /// Bumps the version of a chart for a backwards compatible feature.
+    #[structopt(raw(author = "AUTHOR"))]
+    Feature(CliBumpHelmArgs),
 
-    fn version_long() -> &'static str {
-        include_str!(concat!(env!("OUT_DIR"), "/version_long.txt"))
-    }
-}
+    /// Bumps the version of a chart for a backwards compatible fix.
+    #[structopt(raw(author = "AUTHOR"))]
+    Fix(CliBumpHelmArgs),
 
-pub(crate) enum Output {
+    /// Bumps the pre-release counter of a chart.
+    #[structopt(raw(author = "AUTHOR"))]
+    Pre(CliBumpHelmPreArgs),
+
+    /// Promotes the pre-release of a chart to its final version.
+    #[structopt(raw(author = "AUTHOR"))]
+    Release(CliBumpHelmReleaseArgs),
+
+    /// Bumps the version of a chart for the Conventional Commits since the last tag.
+    #[structopt(raw(author = "AUTHOR"))]
+    Auto(CliBumpHelmAutoArgs),
+
+    /// Sets the version, or parts of it, of a chart.
+    #[structopt(raw(author = "AUTHOR"))]
+    Set(CliBumpHelmSetArgs),
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub(super) enum BumpMavenSubCommand {
+    /// Bumps the major version of a project.
+    #[structopt(raw(author = "AUTHOR"))]
+    Major(CliBumpMavenArgs),
+
+    /// Bumps the minor version of a project.
+    #[structopt(raw(author = "AUTHOR"))]
+    Minor(CliBumpMavenArgs),
+
+    /// Bumps the patch version of a project.
+    #[structopt(raw(author = "AUTHOR"))]
+    Patch(CliBumpMavenArgs),
+
+    /// Bumps the version of a project for a breaking change.
+    #[structopt(raw(author = "AUTHOR"))]
+    Breaking(CliBumpMavenArgs),
+
+    /// Bumps the version of a project for a backwards compatible feature.
+    #[structopt(raw(author = "AUTHOR"))]
+    Feature(CliBumpMavenArgs),
+
+    /// Bumps the version of a project for a backwards compatible fix.
+    #[structopt(raw(author = "AUTHOR"))]
+    Fix(CliBumpMavenArgs),
+
+    /// Bumps the pre-release counter of a project.
+    #[structopt(raw(author = "AUTHOR"))]
+    Pre(CliBumpMavenPreArgs),
+
+    /// Promotes the pre-release of a project to its final version.
+    #[structopt(raw(author = "AUTHOR"))]
+    Release(CliBumpMavenReleaseArgs),
+
+    /// Bumps the version of a project for the Conventional Commits since the last tag.
+    #[structopt(raw(author = "AUTHOR"))]
+    Auto(CliBumpMavenAutoArgs),
+
+    /// Sets the version, or parts of it, of a project.
+    #[structopt(raw(author = "AUTHOR"))]
+    Set(CliBumpMavenSetArgs),
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub(super) enum BumpGradleSubCommand {
+    /// Bumps the major version of a project.
+    #[structopt(raw(author = "AUTHOR"))]
+    Major(CliBumpGradleArgs),
+
+    /// Bumps the minor version of a project.
+    #[structopt(raw(author = "AUTHOR"))]
+    Minor(CliBumpGradleArgs),
+
+    /// Bumps the patch version of a project.
+    #[structopt(raw(author = "AUTHOR"))]
+    Patch(CliBumpGradleArgs),
+
+    /// Bumps the version of a project for a breaking change.
+    #[structopt(raw(author = "AUTHOR"))]
+    Breaking(CliBumpGradleArgs),
+
+    /// Bumps the version of a project for a backwards compatible feature.
+    #[structopt(raw(author = "AUTHOR"))]
+    Feature(CliBumpGradleArgs),
+
+    /// Bumps the version of a project for a backwards compatible fix.
+    #[structopt(raw(author = "AUTHOR"))]
+    Fix(CliBumpGradleArgs),
+
+    /// Bumps the pre-release counter of a project.
+    #[structopt(raw(author = "AUTHOR"))]
+    Pre(CliBumpGradlePreArgs),
+
+    /// Promotes the pre-release of a project to its final version.
+    #[structopt(raw(author = "AUTHOR"))]
+    Release(CliBumpGradleReleaseArgs),
+
+    /// Bumps the version of a project for the Conventional Commits since the last tag.
+    #[structopt(raw(author = "AUTHOR"))]
+    Auto(CliBumpGradleAutoArgs),
+
+    /// Sets the version, or parts of it, of a project.
+    #[structopt(raw(author = "AUTHOR"))]
+    Set(CliBumpGradleSetArgs),
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub(super) enum BumpDockerfileSubCommand {
+    /// Bumps the major version of a Dockerfile.
+    #[structopt(raw(author = "AUTHOR"))]
+    Major(CliBumpDockerfileArgs),
+
+    /// Bumps the minor version of a Dockerfile.
+    #[structopt(raw(author = "AUTHOR"))]
+    Minor(CliBumpDockerfileArgs),
+
+    /// Bumps the patch version of a Dockerfile.
+    #[structopt(raw(author = "AUTHOR"))]
+    Patch(CliBumpDockerfileArgs),
+
+    /// Bumps the version of a Dockerfile for a breaking change.
+    #[structopt(raw(author = "AUTHOR"))]
+    Breaking(CliBumpDockerfileArgs),
+
+    /// Bumps the version of a Dockerfile for a backwards compatible feature.
+    #[structopt(raw(author = "AUTHOR"))]
+    Feature(CliBumpDockerfileArgs),
+
+    /// Bumps the version of a Dockerfile for a backwards compatible fix.
+    #[structopt(raw(author = "AUTHOR"))]
+    Fix(CliBumpDockerfileArgs),
+
+    /// Bumps the pre-release counter of a Dockerfile.
+    #[structopt(raw(author = "AUTHOR"))]
+    Pre(CliBumpDockerfilePreArgs),
+
+    /// Promotes the pre-release of a Dockerfile to its final version.
+    #[structopt(raw(author = "AUTHOR"))]
+    Release(CliBumpDockerfileReleaseArgs),
+
+    /// Bumps the version of a Dockerfile for the Conventional Commits since the last tag.
+    #[structopt(raw(author = "AUTHOR"))]
+    Auto(CliBumpDockerfileAutoArgs),
+
+    /// Sets the version, or parts of it, of a Dockerfile.
+    #[structopt(raw(author = "AUTHOR"))]
+    Set(CliBumpDockerfileSetArgs),
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub(super) enum BumpPyProjectSubCommand {
+    /// Bumps the major version of a Python project.
+    #[structopt(raw(author = "AUTHOR"))]
+    Major(CliBumpPyProjectArgs),
+
+    /// Bumps the minor version of a Python project.
+    #[structopt(raw(author = "AUTHOR"))]
+    Minor(CliBumpPyProjectArgs),
+
+    /// Bumps the patch version of a Python project.
+    #[structopt(raw(author = "AUTHOR"))]
+    Patch(CliBumpPyProjectArgs),
+
+    /// Bumps the version of a Python project for a breaking change.
+    #[structopt(raw(author = "AUTHOR"))]
+    Breaking(CliBumpPyProjectArgs),
+
+    /// Bumps the version of a Python project for a backwards compatible feature.
+    #[structopt(raw(author = "AUTHOR"))]
+    Feature(CliBumpPyProjectArgs),
+
+    /// Bumps the version of a Python project for a backwards compatible fix.
+    #[structopt(raw(author = "AUTHOR"))]
+    Fix(CliBumpPyProjectArgs),
+
+    /// Bumps the pre-release counter of a Python project.
+    #[structopt(raw(author = "AUTHOR"))]
+    Pre(CliBumpPyProjectPreArgs),
+
+    /// Bumps the post-release of a Python project, such as 1.2.0.post1.
+    #[structopt(raw(author = "AUTHOR"))]
+    Post(CliBumpPyProjectArgs),
+
+    /// Bumps the development release of a Python project, such as 1.2.1.dev1.
+    #[structopt(raw(author = "AUTHOR"))]
+    Dev(CliBumpPyProjectArgs),
+
+    /// Promotes the pre-release of a Python project to its final version.
+    #[structopt(raw(author = "AUTHOR"))]
+    Release(CliBumpPyProjectReleaseArgs),
+
+    /// Bumps the version of a Python project for the Conventional Commits since the last tag.
+    #[structopt(raw(author = "AUTHOR"))]
+    Auto(CliBumpPyProjectAutoArgs),
+
+    /// Sets the version, or parts of it, of a Python project.
+    #[structopt(raw(author = "AUTHOR"))]
+    Set(CliBumpPyProjectSetArgs),
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub(super) enum BumpStdinSubCommand {
+    /// TODO: description.
+    #[structopt(raw(author = "AUTHOR"))]
+    Major(CliBumpStdinArgs),
+
+    /// TODO: description.
+    #[structopt(raw(author = "AUTHOR"))]
+    Minor(CliBumpStdinArgs),
+
+    /// TODO: description.
+    #[structopt(raw(author = "AUTHOR"))]
+    Patch(CliBumpStdinArgs),
+
+    /// Bumps for a breaking change: major from 1.0.0 on, minor of 0.y.z, or patch of 0.0.z.
+    #[structopt(raw(author = "AUTHOR"))]
+    Breaking(CliBumpStdinArgs),
+
+    /// Bumps for a backwards compatible feature: minor from 1.0.0 on, otherwise patch.
+    #[structopt(raw(author = "AUTHOR"))]
+    Feature(CliBumpStdinArgs),
+
+    /// Bumps for a backwards compatible fix, which is always the patch version.
+    #[structopt(raw(author = "AUTHOR"))]
+    Fix(CliBumpStdinArgs),
+
+    /// Bumps the pre-release counter, for example from 1.2.0-rc.1 to 1.2.0-rc.2.
+    #[structopt(raw(author = "AUTHOR"))]
+    Pre(CliBumpStdinPreArgs),
+
+    /// Promotes a pre-release to its final version, for example from 1.2.0-rc.2 to 1.2.0.
+    #[structopt(raw(author = "AUTHOR"))]
+    Release(CliBumpStdinReleaseArgs),
+
+    /// Bumps for the most significant Conventional Commit since the last version tag in git.
+    #[structopt(raw(author = "AUTHOR"))]
+    Auto(CliBumpStdinAutoArgs),
+
+    /// TODO: description.
+    #[structopt(raw(author = "AUTHOR"))]
+    Set(CliBumpStdinSetArgs),
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub(super) enum GetSubCommand {
+    /// Prints the version of a Cargo manifest.
+    #[structopt(raw(author = "AUTHOR"))]
+    Cargo(CliGetCargoArgs),
+
+    /// Prints the version held in a file.
+    #[structopt(raw(author = "AUTHOR"))]
+    File(CliGetFileArgs),
+
+    /// Prints the version read from standard input.
+    #[structopt(raw(author = "AUTHOR"))]
+    Stdin(CliGetStdinArgs),
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub(super) enum CheckSubCommand {
+    /// Checks the version of a Cargo manifest.
+    #[structopt(raw(author = "AUTHOR"))]
+    Cargo(CliCheckCargoArgs),
+
+    /// Checks the version held in a file.
+    #[structopt(raw(author = "AUTHOR"))]
+    File(CliCheckFileArgs),
+
+    /// Checks the version read from standard input.
+    #[structopt(raw(author = "AUTHOR"))]
+    Stdin(CliCheckStdinArgs),
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct BumpCommonArgs {
+    /// TODO: description.
+    #[structopt(long = "pre-release", short = "P", rename_all = "screaming_snake_case")]
+    pre_release: Option<PreRelease>,
+
+    /// TODO: description.
+    #[structopt(
+        long = "build-metadata",
+        short = "b",
+        rename_all = "screaming_snake_case"
+    )]
+    build_metadata: Option<BuildMetadata>,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct BumpSetArgs {
+    /// TODO: description.
+    #[structopt(short = "M", long = "major", rename_all = "screaming_snake_case")]
+    major: Option<u64>,
+
+    /// TODO: description.
+    #[structopt(short = "m", long = "minor", rename_all = "screaming_snake_case")]
+    minor: Option<u64>,
+
+    /// TODO: description.
+    #[structopt(short = "p", long = "patch", rename_all = "screaming_snake_case")]
+    patch: Option<u64>,
+
+    /// TODO: description.
+    #[structopt(
+        short = "V",
+        long = "version",
+        rename_all = "screaming_snake_case",
+        raw(
+            conflicts_with_all = r#"&["MAJOR", "MINOR", "PATCH", "PRE_RELEASE", "BUILD_METADATA"]"#,
+            validator = "any_version"
+        )
+    )]
+    version: Option<String>,
+
+    /// TODO: description.
+    #[structopt(
+        long = "no-pre-release",
+        raw(conflicts_with_all = r#"&["PRE_RELEASE"]"#)
+    )]
+    no_pre_release: bool,
+
+    /// TODO: description.
+    #[structopt(
+        long = "no-build-metadata",
+        raw(conflicts_with_all = r#"&["BUILD_METADATA"]"#)
+    )]
+    no_build_metadata: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct BumpGitArgs {
+    /// Commits the changed files to git, refusing if the working tree has other changes.
+    #[structopt(long, raw(conflicts_with = r#""stdout""#))]
+    commit: bool,
+
+    /// Creates an annotated git tag for the new version, implying --commit.
+    #[structopt(long, raw(conflicts_with = r#""stdout""#))]
+    tag: bool,
+
+    /// The commit and tag message, where {version} is replaced with the new version.
+    #[structopt(
+        long = "message",
+        rename_all = "screaming_snake_case",
+        raw(default_value = r#""Release {version}""#)
+    )]
+    message: String,
+
+    /// The tag name, where {version} is replaced with the new version.
+    #[structopt(
+        long = "tag-name",
+        rename_all = "screaming_snake_case",
+        raw(default_value = r#""v{version}""#)
+    )]
+    tag_name: String,
+}
+
+impl BumpGitArgs {
+    fn record(&self) -> GitRecord {
+        if self.tag {
+            GitRecord::Tag {
+                message: self.message.clone(),
+                tag: self.tag_name.clone(),
+            }
+        } else if self.commit {
+            GitRecord::Commit {
+                message: self.message.clone(),
+            }
+        } else {
+            GitRecord::None
+        }
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct BumpPreArgs {
+    /// Switches the pre-release to a new channel, such as `beta`, restarting its counter at 1.
+    ///
+    /// A version without a pre-release has its patch bumped to start the channel. The
+    /// --pre-release option may be given instead.
+    #[structopt(
+        short = "c",
+        long = "channel",
+        rename_all = "screaming_snake_case",
+        raw(conflicts_with = r#""PRE_RELEASE""#)
+    )]
+    channel: Option<PreRelease>,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct BumpReleaseArgs {
+    /// Succeeds without changes when the version is already a release.
+    #[structopt(long = "allow-released")]
+    allow_released: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct BumpCalverArgs {
+    /// The format of the calendar version, made of YYYY, YY, 0Y, MM, 0M, WW, 0W, DD or 0D date
+    /// parts, optionally followed by a MICRO counter, separated by `.`, `-` or `_`.
+    ///
+    /// The date parts roll to today, resetting the counter to 0, or the counter is incremented
+    /// when the version is already today's.
+    #[structopt(
+        long = "calver-format",
+        rename_all = "screaming_snake_case",
+        raw(default_value = "\"YYYY.MM.MICRO\"")
+    )]
+    format: calver::Format,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct BumpAutoArgs {
+    /// The git repository whose history is read.
+    ///
+    /// Commits since the tag naming the highest version, such as v1.2.3 or 1.2.3, are classified
+    /// as Conventional Commits. A breaking change bumps as `bump breaking` does, a `feat:` commit
+    /// as `bump feature`, and a `fix:` commit as `bump fix`.
+    #[structopt(
+        long = "repo",
+        rename_all = "screaming_snake_case",
+        raw(default_value = "\".\"")
+    )]
+    repo: PathBuf,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpCargoArgs {
+    #[structopt(flatten)]
+    common: BumpCommonArgs,
+
+    /// TODO: description.
+    #[structopt(
+        rename_all = "screaming_snake_case",
+        raw(default_value = "\"Cargo.toml\"")
+    )]
+    manifest: PathBuf,
+
+    /// TODO: description.
+    #[structopt(short = "s", long)]
+    stdout: bool,
+
+    #[structopt(flatten)]
+    git: BumpGitArgs,
+
+    /// Bumps every member of the workspace rooted at MANIFEST in lock-step.
+    #[structopt(short = "w", long, raw(conflicts_with = r#""stdout""#))]
+    workspace: bool,
+
+    /// Bumps only the named workspace member, implying --workspace.
+    ///
+    /// This option may be given more than once to select several members.
+    #[structopt(
+        long,
+        rename_all = "screaming_snake_case",
+        raw(number_of_values = "1", conflicts_with = r#""stdout""#)
+    )]
+    package: Vec<String>,
+}
+
+impl CliBumpCargoArgs {
+    fn members(&mut self) -> SelectMembers {
+        if !self.package.is_empty() {
+            SelectMembers::Some(self.package.drain(..).collect())
+        } else if self.workspace {
+            SelectMembers::All
+        } else {
+            SelectMembers::None
+        }
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpCargoSetArgs {
+    #[structopt(flatten)]
+    pub common: CliBumpCargoArgs,
+
+    #[structopt(flatten)]
+    pub set: BumpSetArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpCargoPreArgs {
+    #[structopt(flatten)]
+    common: CliBumpCargoArgs,
+
+    #[structopt(flatten)]
+    pre: BumpPreArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpCargoReleaseArgs {
+    #[structopt(flatten)]
+    common: CliBumpCargoArgs,
+
+    #[structopt(flatten)]
+    release: BumpReleaseArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpCargoAutoArgs {
+    #[structopt(flatten)]
+    common: CliBumpCargoArgs,
+
+    #[structopt(flatten)]
+    auto: BumpAutoArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpFileArgs {
+    #[structopt(flatten)]
+    common: BumpCommonArgs,
+
+    /// TODO: description.
+    #[structopt(
+        rename_all = "screaming_snake_case",
+        raw(default_value = "\"VERSION.txt\"")
+    )]
+    file: PathBuf,
+
+    /// TODO: description.
+    #[structopt(short = "s", long)]
+    stdout: bool,
+
+    /// The scheme the version follows, either semver or pep440.
+    ///
+    /// By default, the version follows Semantic Versioning.
+    #[structopt(
+        long = "scheme",
+        rename_all = "screaming_snake_case",
+        raw(possible_values = r#"&["semver", "pep440"]"#)
+    )]
+    scheme: Option<Scheme>,
+
+    #[structopt(flatten)]
+    git: BumpGitArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpFileSetArgs {
+    #[structopt(flatten)]
+    common: CliBumpFileArgs,
+
+    #[structopt(flatten)]
+    set: BumpSetArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpFilePreArgs {
+    #[structopt(flatten)]
+    common: CliBumpFileArgs,
+
+    #[structopt(flatten)]
+    pre: BumpPreArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpFileReleaseArgs {
+    #[structopt(flatten)]
+    common: CliBumpFileArgs,
+
+    #[structopt(flatten)]
+    release: BumpReleaseArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpFileAutoArgs {
+    #[structopt(flatten)]
+    common: CliBumpFileArgs,
+
+    #[structopt(flatten)]
+    auto: BumpAutoArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpPatternArgs {
+    #[structopt(flatten)]
+    common: BumpCommonArgs,
+
+    /// The regular expression which finds the version, captured by a group named `version`, such
+    /// as 'VERSION=(?P<version>\S+)'.
+    #[structopt(short = "e", long = "regex", rename_all = "screaming_snake_case")]
+    regex: Pattern,
+
+    /// The number of times the regular expression may match, each of which must hold the same
+    /// version.
+    #[structopt(
+        long = "max-matches",
+        rename_all = "screaming_snake_case",
+        raw(default_value = "\"1\"", validator = "positive")
+    )]
+    max_matches: usize,
+
+    /// The scheme the version follows, either semver or pep440.
+    ///
+    /// By default, the version follows Semantic Versioning.
+    #[structopt(
+        long = "scheme",
+        rename_all = "screaming_snake_case",
+        raw(possible_values = r#"&["semver", "pep440"]"#)
+    )]
+    scheme: Option<Scheme>,
+
+    /// The file holding the version.
+    #[structopt(rename_all = "screaming_snake_case")]
+    file: PathBuf,
+
+    /// Prints the new contents of the file instead of writing it.
+    #[structopt(short = "s", long)]
+    stdout: bool,
+
+    #[structopt(flatten)]
+    git: BumpGitArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpPatternSetArgs {
+    #[structopt(flatten)]
+    common: CliBumpPatternArgs,
+
+    #[structopt(flatten)]
+    set: BumpSetArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpPatternPreArgs {
+    #[structopt(flatten)]
+    common: CliBumpPatternArgs,
+
+    #[structopt(flatten)]
+    pre: BumpPreArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpPatternReleaseArgs {
+    #[structopt(flatten)]
+    common: CliBumpPatternArgs,
+
+    #[structopt(flatten)]
+    release: BumpReleaseArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpPatternCalverArgs {
+    #[structopt(flatten)]
+    common: CliBumpPatternArgs,
+
+    #[structopt(flatten)]
+    calver: BumpCalverArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpPatternAutoArgs {
+    #[structopt(flatten)]
+    common: CliBumpPatternArgs,
+
+    #[structopt(flatten)]
+    auto: BumpAutoArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpNpmArgs {
+    #[structopt(flatten)]
+    common: BumpCommonArgs,
+
+    /// The package.json, whose package-lock.json is updated along with it.
+    #[structopt(
+        rename_all = "screaming_snake_case",
+        raw(default_value = "\"package.json\"")
+    )]
+    package: PathBuf,
+
+    /// Prints the new contents of the package.json instead of writing it.
+    #[structopt(short = "s", long)]
+    stdout: bool,
+
+    #[structopt(flatten)]
+    git: BumpGitArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpNpmSetArgs {
+    #[structopt(flatten)]
+    common: CliBumpNpmArgs,
+
+    #[structopt(flatten)]
+    set: BumpSetArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpNpmPreArgs {
+    #[structopt(flatten)]
+    common: CliBumpNpmArgs,
+
+    #[structopt(flatten)]
+    pre: BumpPreArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpNpmReleaseArgs {
+    #[structopt(flatten)]
+    common: CliBumpNpmArgs,
+
+    #[structopt(flatten)]
+    release: BumpReleaseArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpNpmAutoArgs {
+    #[structopt(flatten)]
+    common: CliBumpNpmArgs,
+
+    #[structopt(flatten)]
+    auto: BumpAutoArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpHelmArgs {
+    #[structopt(flatten)]
+    common: BumpCommonArgs,
+
+    /// The Chart.yaml holding the version.
+    #[structopt(
+        rename_all = "screaming_snake_case",
+        raw(default_value = "\"Chart.yaml\"")
+    )]
+    chart: PathBuf,
+
+    /// Sets the appVersion of the chart to the version of the crate whose Cargo manifest is given.
+    ///
+    /// An appVersion is added after the version if the chart has none.
+    #[structopt(long = "app-version-from", rename_all = "screaming_snake_case")]
+    app_version_from: Option<PathBuf>,
+
+    /// Prints the new contents of the Chart.yaml instead of writing it.
+    #[structopt(short = "s", long)]
+    stdout: bool,
+
+    #[structopt(flatten)]
+    git: BumpGitArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpHelmSetArgs {
+    #[structopt(flatten)]
+    common: CliBumpHelmArgs,
+
+    #[structopt(flatten)]
+    set: BumpSetArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpHelmPreArgs {
+    #[structopt(flatten)]
+    common: CliBumpHelmArgs,
+
+    #[structopt(flatten)]
+    pre: BumpPreArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpHelmReleaseArgs {
+    #[structopt(flatten)]
+    common: CliBumpHelmArgs,
+
+    #[structopt(flatten)]
+    release: BumpReleaseArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpHelmAutoArgs {
+    #[structopt(flatten)]
+    common: CliBumpHelmArgs,
+
+    #[structopt(flatten)]
+    auto: BumpAutoArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpMavenArgs {
+    #[structopt(flatten)]
+    common: BumpCommonArgs,
+
+    /// The pom.xml holding the project version.
+    #[structopt(
+        rename_all = "screaming_snake_case",
+        raw(default_value = "\"pom.xml\"")
+    )]
+    pom: PathBuf,
+
+    /// Prints the new contents of the pom.xml instead of writing it.
+    #[structopt(short = "s", long)]
+    stdout: bool,
+
+    #[structopt(flatten)]
+    git: BumpGitArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpMavenSetArgs {
+    #[structopt(flatten)]
+    common: CliBumpMavenArgs,
+
+    #[structopt(flatten)]
+    set: BumpSetArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpMavenPreArgs {
+    #[structopt(flatten)]
+    common: CliBumpMavenArgs,
+
+    #[structopt(flatten)]
+    pre: BumpPreArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpMavenReleaseArgs {
+    #[structopt(flatten)]
+    common: CliBumpMavenArgs,
+
+    #[structopt(flatten)]
+    release: BumpReleaseArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpMavenAutoArgs {
+    #[structopt(flatten)]
+    common: CliBumpMavenArgs,
+
+    #[structopt(flatten)]
+    auto: BumpAutoArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpGradleArgs {
+    #[structopt(flatten)]
+    common: BumpCommonArgs,
+
+    /// The gradle.properties holding the version.
+    #[structopt(
+        rename_all = "screaming_snake_case",
+        raw(default_value = "\"gradle.properties\"")
+    )]
+    properties: PathBuf,
+
+    /// Prints the new contents of the gradle.properties instead of writing it.
+    #[structopt(short = "s", long)]
+    stdout: bool,
+
+    #[structopt(flatten)]
+    git: BumpGitArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpGradleSetArgs {
+    #[structopt(flatten)]
+    common: CliBumpGradleArgs,
+
+    #[structopt(flatten)]
+    set: BumpSetArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpGradlePreArgs {
+    #[structopt(flatten)]
+    common: CliBumpGradleArgs,
+
+    #[structopt(flatten)]
+    pre: BumpPreArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpGradleReleaseArgs {
+    #[structopt(flatten)]
+    common: CliBumpGradleArgs,
+
+    #[structopt(flatten)]
+    release: BumpReleaseArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpGradleAutoArgs {
+    #[structopt(flatten)]
+    common: CliBumpGradleArgs,
+
+    #[structopt(flatten)]
+    auto: BumpAutoArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpDockerfileArgs {
+    #[structopt(flatten)]
+    common: BumpCommonArgs,
+
+    /// The Dockerfile holding the version, in `LABEL org.opencontainers.image.version=...` or
+    /// `ARG VERSION=...` instructions.
+    ///
+    /// Every such instruction is updated, including those continued over several lines. Each
+    /// changed line is reported at -v.
+    #[structopt(
+        rename_all = "screaming_snake_case",
+        raw(default_value = "\"Dockerfile\"")
+    )]
+    dockerfile: PathBuf,
+
+    /// Prints the new contents of the Dockerfile instead of writing it.
+    #[structopt(short = "s", long)]
+    stdout: bool,
+
+    #[structopt(flatten)]
+    git: BumpGitArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpDockerfileSetArgs {
+    #[structopt(flatten)]
+    common: CliBumpDockerfileArgs,
+
+    #[structopt(flatten)]
+    set: BumpSetArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpDockerfilePreArgs {
+    #[structopt(flatten)]
+    common: CliBumpDockerfileArgs,
+
+    #[structopt(flatten)]
+    pre: BumpPreArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpDockerfileReleaseArgs {
+    #[structopt(flatten)]
+    common: CliBumpDockerfileArgs,
+
+    #[structopt(flatten)]
+    release: BumpReleaseArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpDockerfileAutoArgs {
+    #[structopt(flatten)]
+    common: CliBumpDockerfileArgs,
+
+    #[structopt(flatten)]
+    auto: BumpAutoArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpPyProjectArgs {
+    #[structopt(flatten)]
+    common: BumpCommonArgs,
+
+    /// The pyproject.toml holding the version.
+    #[structopt(
+        rename_all = "screaming_snake_case",
+        raw(default_value = "\"pyproject.toml\"")
+    )]
+    pyproject: PathBuf,
+
+    /// The scheme the version follows, either semver or pep440.
+    ///
+    /// By default, the version follows PEP 440.
+    #[structopt(
+        long = "scheme",
+        rename_all = "screaming_snake_case",
+        raw(possible_values = r#"&["semver", "pep440"]"#)
+    )]
+    scheme: Option<Scheme>,
+
+    /// Prints the new contents of the pyproject.toml instead of writing it.
+    #[structopt(short = "s", long)]
+    stdout: bool,
+
+    #[structopt(flatten)]
+    git: BumpGitArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpPyProjectSetArgs {
+    #[structopt(flatten)]
+    common: CliBumpPyProjectArgs,
+
+    #[structopt(flatten)]
+    set: BumpSetArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpPyProjectPreArgs {
+    #[structopt(flatten)]
+    common: CliBumpPyProjectArgs,
+
+    #[structopt(flatten)]
+    pre: BumpPreArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpPyProjectReleaseArgs {
+    #[structopt(flatten)]
+    common: CliBumpPyProjectArgs,
+
+    #[structopt(flatten)]
+    release: BumpReleaseArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpPyProjectAutoArgs {
+    #[structopt(flatten)]
+    common: CliBumpPyProjectArgs,
+
+    #[structopt(flatten)]
+    auto: BumpAutoArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpStdinArgs {
+    #[structopt(flatten)]
+    common: BumpCommonArgs,
+
+    /// The scheme the version follows, either semver or pep440.
+    ///
+    /// By default, the version follows Semantic Versioning.
+    #[structopt(
+        long = "scheme",
+        rename_all = "screaming_snake_case",
+        raw(possible_values = r#"&["semver", "pep440"]"#)
+    )]
+    scheme: Option<Scheme>,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpStdinSetArgs {
+    #[structopt(flatten)]
+    common: CliBumpStdinArgs,
+
+    #[structopt(flatten)]
+    set: BumpSetArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpStdinPreArgs {
+    #[structopt(flatten)]
+    common: CliBumpStdinArgs,
+
+    #[structopt(flatten)]
+    pre: BumpPreArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpStdinReleaseArgs {
+    #[structopt(flatten)]
+    common: CliBumpStdinArgs,
+
+    #[structopt(flatten)]
+    release: BumpReleaseArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpStdinAutoArgs {
+    #[structopt(flatten)]
+    common: CliBumpStdinArgs,
+
+    #[structopt(flatten)]
+    auto: BumpAutoArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpConfigArgs {
+    #[structopt(flatten)]
+    common: BumpCommonArgs,
+
+    /// The configuration file listing the files which hold the version.
+    ///
+    /// By default, .versio.toml is searched for in the current directory and then in each of its
+    /// parents.
+    #[structopt(short = "C", long = "config", rename_all = "screaming_snake_case")]
+    config: Option<PathBuf>,
+
+    /// The scheme the version follows, either semver or pep440.
+    ///
+    /// By default, the scheme is read from the configuration file, and otherwise the version
+    /// follows PEP 440 if every file is a pyproject.toml, or Semantic Versioning.
+    #[structopt(
+        long = "scheme",
+        rename_all = "screaming_snake_case",
+        raw(possible_values = r#"&["semver", "pep440"]"#)
+    )]
+    scheme: Option<Scheme>,
+
+    #[structopt(flatten)]
+    git: BumpGitArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpConfigSetArgs {
+    #[structopt(flatten)]
+    common: CliBumpConfigArgs,
+
+    #[structopt(flatten)]
+    set: BumpSetArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpConfigPreArgs {
+    #[structopt(flatten)]
+    common: CliBumpConfigArgs,
+
+    #[structopt(flatten)]
+    pre: BumpPreArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpConfigReleaseArgs {
+    #[structopt(flatten)]
+    common: CliBumpConfigArgs,
+
+    #[structopt(flatten)]
+    release: BumpReleaseArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpConfigCalverArgs {
+    #[structopt(flatten)]
+    common: CliBumpConfigArgs,
+
+    #[structopt(flatten)]
+    calver: BumpCalverArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliBumpConfigAutoArgs {
+    #[structopt(flatten)]
+    common: CliBumpConfigArgs,
+
+    #[structopt(flatten)]
+    auto: BumpAutoArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct GetPartArgs {
+    /// Prints only the major version.
+    #[structopt(
+        short = "M",
+        long = "major",
+        raw(
+            conflicts_with_all = r#"&["minor", "patch", "pre-release", "build-metadata", "major-minor"]"#
+        )
+    )]
+    major: bool,
+
+    /// Prints only the minor version.
+    #[structopt(
+        short = "m",
+        long = "minor",
+        raw(conflicts_with_all = r#"&["patch", "pre-release", "build-metadata", "major-minor"]"#)
+    )]
+    minor: bool,
+
+    /// Prints only the patch version.
+    #[structopt(
+        short = "p",
+        long = "patch",
+        raw(conflicts_with_all = r#"&["pre-release", "build-metadata", "major-minor"]"#)
+    )]
+    patch: bool,
+
+    /// Prints only the pre-release, or an empty line if there is none.
+    #[structopt(
+        short = "P",
+        long = "pre-release",
+        raw(conflicts_with_all = r#"&["build-metadata", "major-minor"]"#)
+    )]
+    pre_release: bool,
+
+    /// Prints only the build metadata, or an empty line if there is none.
+    #[structopt(
+        short = "b",
+        long = "build-metadata",
+        raw(conflicts_with_all = r#"&["major-minor"]"#)
+    )]
+    build_metadata: bool,
+
+    /// Prints only the major and minor versions, such as 1.2.
+    #[structopt(long = "major-minor")]
+    major_minor: bool,
+}
+
+impl GetPartArgs {
+    fn part(&self) -> VersionPart {
+        if self.major {
+            VersionPart::Major
+        } else if self.minor {
+            VersionPart::Minor
+        } else if self.patch {
+            VersionPart::Patch
+        } else if self.pre_release {
+            VersionPart::PreRelease
+        } else if self.build_metadata {
+            VersionPart::BuildMetadata
+        } else if self.major_minor {
+            VersionPart::MajorMinor
+        } else {
+            VersionPart::All
+        }
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliGetCargoArgs {
+    #[structopt(flatten)]
+    part: GetPartArgs,
+
+    /// The Cargo manifest to read.
+    #[structopt(
+        rename_all = "screaming_snake_case",
+        raw(default_value = "\"Cargo.toml\"")
+    )]
+    manifest: PathBuf,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliGetFileArgs {
+    #[structopt(flatten)]
+    part: GetPartArgs,
+
+    /// The file holding the version.
+    #[structopt(
+        rename_all = "screaming_snake_case",
+        raw(default_value = "\"VERSION.txt\"")
+    )]
+    file: PathBuf,
+
+    /// The scheme the version follows, either semver or pep440.
+    ///
+    /// By default, the version follows Semantic Versioning.
+    #[structopt(
+        long = "scheme",
+        rename_all = "screaming_snake_case",
+        raw(possible_values = r#"&["semver", "pep440"]"#)
+    )]
+    scheme: Option<Scheme>,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliGetStdinArgs {
+    #[structopt(flatten)]
+    part: GetPartArgs,
+
+    /// The scheme the version follows, either semver or pep440.
+    ///
+    /// By default, the version follows Semantic Versioning.
+    #[structopt(
+        long = "scheme",
+        rename_all = "screaming_snake_case",
+        raw(possible_values = r#"&["semver", "pep440"]"#)
+    )]
+    scheme: Option<Scheme>,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CheckExpectArgs {
+    /// The exact version expected, ignoring any build metadata.
+    #[structopt(
+        short = "V",
+        long = "version",
+        rename_all = "screaming_snake_case",
+        raw(
+            required_unless = r#""REQ""#,
+            conflicts_with = r#""REQ""#,
+            validator = "any_version"
+        )
+    )]
+    version: Option<String>,
+
+    /// A requirement which the version must satisfy, such as ">=1.4, <2".
+    ///
+    /// Requirements only apply to semantic versions.
+    #[structopt(short = "r", long = "req", rename_all = "screaming_snake_case")]
+    req: Option<VersionReq>,
+}
+
+impl CheckExpectArgs {
+    fn expected(self) -> Expected {
+        match (self.version, self.req) {
+            (Some(version), _) => Expected::Version(version),
+            (None, Some(req)) => Expected::Requirement(req),
+            (None, None) => unreachable!("clap requires either a version or a requirement"),
+        }
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliCheckCargoArgs {
+    #[structopt(flatten)]
+    expect: CheckExpectArgs,
+
+    /// The Cargo manifest to read.
+    #[structopt(
+        rename_all = "screaming_snake_case",
+        raw(default_value = "\"Cargo.toml\"")
+    )]
+    manifest: PathBuf,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliCheckFileArgs {
+    #[structopt(flatten)]
+    expect: CheckExpectArgs,
+
+    /// The file holding the version.
+    #[structopt(
+        rename_all = "screaming_snake_case",
+        raw(default_value = "\"VERSION.txt\"")
+    )]
+    file: PathBuf,
+
+    /// The scheme the version follows, either semver or pep440.
+    ///
+    /// By default, the version follows Semantic Versioning.
+    #[structopt(
+        long = "scheme",
+        rename_all = "screaming_snake_case",
+        raw(possible_values = r#"&["semver", "pep440"]"#)
+    )]
+    scheme: Option<Scheme>,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliCheckStdinArgs {
+    #[structopt(flatten)]
+    expect: CheckExpectArgs,
+
+    /// The scheme the version follows, either semver or pep440.
+    ///
+    /// By default, the version follows Semantic Versioning.
+    #[structopt(
+        long = "scheme",
+        rename_all = "screaming_snake_case",
+        raw(possible_values = r#"&["semver", "pep440"]"#)
+    )]
+    scheme: Option<Scheme>,
+}
+
+#[derive(Debug, StructOpt)]
+pub(super) struct CliVerifyArgs {
+    /// A Cargo manifest holding the version.
+    ///
+    /// This option may be given more than once.
+    #[structopt(
+        long = "cargo",
+        rename_all = "screaming_snake_case",
+        raw(number_of_values = "1")
+    )]
+    cargo: Vec<PathBuf>,
+
+    /// A file holding nothing but the version.
+    ///
+    /// This option may be given more than once.
+    #[structopt(
+        long = "file",
+        rename_all = "screaming_snake_case",
+        raw(number_of_values = "1")
+    )]
+    file: Vec<PathBuf>,
+
+    /// A file holding the version within its text, given as FILE=REGEX.
+    ///
+    /// The version is captured by a group named `version` in the regular expression, such as
+    /// `install.sh=VERSION=(?P<version>\S+)`. This option may be given more than once.
+    #[structopt(
+        long = "pattern",
+        rename_all = "screaming_snake_case",
+        raw(number_of_values = "1")
+    )]
+    pattern: Vec<PatternArg>,
+
+    /// An npm package.json holding the version.
+    ///
+    /// This option may be given more than once.
+    #[structopt(
+        long = "npm",
+        rename_all = "screaming_snake_case",
+        raw(number_of_values = "1")
+    )]
+    npm: Vec<PathBuf>,
+
+    /// A Python pyproject.toml holding the version.
+    ///
+    /// This option may be given more than once.
+    #[structopt(
+        long = "pyproject",
+        rename_all = "screaming_snake_case",
+        raw(number_of_values = "1")
+    )]
+    pyproject: Vec<PathBuf>,
+
+    /// A Helm Chart.yaml holding the version.
+    ///
+    /// This option may be given more than once.
+    #[structopt(
+        long = "helm",
+        rename_all = "screaming_snake_case",
+        raw(number_of_values = "1")
+    )]
+    helm: Vec<PathBuf>,
+
+    /// A Maven pom.xml holding the project version.
+    ///
+    /// This option may be given more than once.
+    #[structopt(
+        long = "maven",
+        rename_all = "screaming_snake_case",
+        raw(number_of_values = "1")
+    )]
+    maven: Vec<PathBuf>,
+
+    /// A Gradle gradle.properties holding the version.
+    ///
+    /// This option may be given more than once.
+    #[structopt(
+        long = "gradle",
+        rename_all = "screaming_snake_case",
+        raw(number_of_values = "1")
+    )]
+    gradle: Vec<PathBuf>,
+
+    /// A Dockerfile holding the version in its labels and build arguments.
+    ///
+    /// This option may be given more than once.
+    #[structopt(
+        long = "dockerfile",
+        rename_all = "screaming_snake_case",
+        raw(number_of_values = "1")
+    )]
+    dockerfile: Vec<PathBuf>,
+
+    /// The configuration file listing the files which hold the version, used when no files are
+    /// given.
+    ///
+    /// By default, .versio.toml is searched for in the current directory and then in each of its
+    /// parents.
+    #[structopt(
+        short = "C",
+        long = "config",
+        rename_all = "screaming_snake_case",
+        raw(conflicts_with_all = r#"&[
+            "cargo", "file", "pattern", "npm", "pyproject", "helm", "maven", "gradle", "dockerfile"
+        ]"#)
+    )]
+    config: Option<PathBuf>,
+
+    /// The scheme the versions follow, either semver or pep440.
+    ///
+    /// By default, the scheme is read from the configuration file, and otherwise the versions
+    /// follow PEP 440 if every file is a pyproject.toml, or Semantic Versioning.
+    #[structopt(
+        long = "scheme",
+        rename_all = "screaming_snake_case",
+        raw(possible_values = r#"&["semver", "pep440"]"#)
+    )]
+    scheme: Option<Scheme>,
+}
+
+fn positive(value: String) -> Result<(), String> {
+    match value.parse::<usize>() {
+        Ok(count) if count > 0 => Ok(()),
+        _ => Err(format!("expected a positive number, found '{}'", value)),
+    }
+}
+
+/// Accepts a semantic or PEP 440 version, which is parsed by the scheme of the version it
+/// replaces.
+fn any_version(value: String) -> Result<(), String> {
+    if Version::from_str(&value).is_ok() || Pep440Version::from_str(&value).is_ok() {
+        Ok(())
+    } else {
+        Err(format!(
+            "expected a semantic or PEP 440 version, found '{}'",
+            value
+        ))
+    }
+}
+
+/// A file and the pattern which finds the version within it, parsed from `FILE=REGEX`.
+#[derive(Debug)]
+pub(super) struct PatternArg(PathBuf, Pattern);
+
+impl FromStr for PatternArg {
+    type Err = failure::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, '=');
+        match (parts.next(), parts.next()) {
+            (Some(file), Some(pattern)) if !file.is_empty() => {
+                Ok(PatternArg(PathBuf::from(file), Pattern::from_str(pattern)?))
+            }
+            _ => bail!("expected FILE=REGEX, found '{}'", s),
+        }
+    }
+}
+
+/// Build time metadata
+struct BuildInfo;
+
+impl BuildInfo {
+    fn version_short() -> &'static str {
+        include_str!(concat!(env!("OUT_DIR"), "/version_short.txt"))
+    }
+
+    fn version_long() -> &'static str {
+        include_str!(concat!(env!("OUT_DIR"), "/version_long.txt"))
+    }
+}
+
+pub(crate) enum Output {
     File(PathBuf),
     Stdout(Box<dyn Write>),
+    /// Previews the changes to the file, and any others which would be written, without writing.
+    DryRun(PathBuf, Box<dyn Write>),
+}
+
+impl Output {
+    fn new(path: PathBuf, stdout: bool, dry_run: bool) -> Self {
+        if stdout {
+            Output::Stdout(Box::new(std::io::stdout()))
+        } else if dry_run {
+            Output::DryRun(path, Box::new(std::io::stdout()))
+        } else {
+            Output::File(path)
+        }
+    }
+}
+
+pub(crate) struct BumpCargoArgs {
+    pub pre: Option<PreRelease>,
+    pub build: Option<BuildMetadata>,
+    pub input: PathBuf,
+    pub output: Output,
+    pub git: GitRecord,
+    pub members: SelectMembers,
+}
+
+impl From<(CliBumpCargoArgs, bool)> for BumpCargoArgs {
+    fn from((mut args, dry_run): (CliBumpCargoArgs, bool)) -> Self {
+        BumpCargoArgs {
+            members: args.members(),
+            pre: args.common.pre_release,
+            build: args.common.build_metadata,
+            git: args.git.record(),
+            input: args.manifest.clone(),
+            output: Output::new(args.manifest, args.stdout, dry_run),
+        }
+    }
+}
+
+pub(crate) struct BumpCargoSetArgs {
+    pub input: PathBuf,
+    pub output: Output,
+    pub git: GitRecord,
+    pub members: SelectMembers,
+    pub set: SetVersion,
+}
+
+impl From<(CliBumpCargoSetArgs, bool)> for BumpCargoSetArgs {
+    fn from((mut args, dry_run): (CliBumpCargoSetArgs, bool)) -> Self {
+        BumpCargoSetArgs {
+            members: args.common.members(),
+            git: args.common.git.record(),
+            input: args.common.manifest.clone(),
+            output: Output::new(args.common.manifest.clone(), args.common.stdout, dry_run),
+            set: args.into(),
+        }
+    }
+}
+
+pub(crate) struct BumpCargoPreArgs {
+    pub channel: Option<PreRelease>,
+    pub build: Option<BuildMetadata>,
+    pub input: PathBuf,
+    pub output: Output,
+    pub git: GitRecord,
+    pub members: SelectMembers,
+}
+
+impl From<(CliBumpCargoPreArgs, bool)> for BumpCargoPreArgs {
+    fn from((args, dry_run): (CliBumpCargoPreArgs, bool)) -> Self {
+        let BumpCargoArgs {
+            pre,
+            build,
+            input,
+            output,
+            git,
+            members,
+        } = (args.common, dry_run).into();
+
+        BumpCargoPreArgs {
+            channel: args.pre.channel.or(pre),
+            build,
+            input,
+            output,
+            git,
+            members,
+        }
+    }
+}
+
+pub(crate) struct BumpCargoReleaseArgs {
+    pub pre: Option<PreRelease>,
+    pub build: Option<BuildMetadata>,
+    pub input: PathBuf,
+    pub output: Output,
+    pub git: GitRecord,
+    pub members: SelectMembers,
+    pub allow_released: bool,
+}
+
+impl From<(CliBumpCargoReleaseArgs, bool)> for BumpCargoReleaseArgs {
+    fn from((args, dry_run): (CliBumpCargoReleaseArgs, bool)) -> Self {
+        let BumpCargoArgs {
+            pre,
+            build,
+            input,
+            output,
+            git,
+            members,
+        } = (args.common, dry_run).into();
+
+        BumpCargoReleaseArgs {
+            pre,
+            build,
+            input,
+            output,
+            git,
+            members,
+            allow_released: args.release.allow_released,
+        }
+    }
+}
+
+pub(crate) struct BumpCargoAutoArgs {
+    pub pre: Option<PreRelease>,
+    pub build: Option<BuildMetadata>,
+    pub input: PathBuf,
+    pub output: Output,
+    pub git: GitRecord,
+    pub members: SelectMembers,
+    pub repo: PathBuf,
+}
+
+impl From<(CliBumpCargoAutoArgs, bool)> for BumpCargoAutoArgs {
+    fn from((args, dry_run): (CliBumpCargoAutoArgs, bool)) -> Self {
+        let BumpCargoArgs {
+            pre,
+            build,
+            input,
+            output,
+            git,
+            members,
+        } = (args.common, dry_run).into();
+
+        BumpCargoAutoArgs {
+            pre,
+            build,
+            input,
+            output,
+            git,
+            members,
+            repo: args.auto.repo,
+        }
+    }
+}
+
+pub(crate) struct BumpFileArgs {
+    pub scheme: Scheme,
+    pub pre: Option<PreRelease>,
+    pub build: Option<BuildMetadata>,
+    pub input: PathBuf,
+    pub output: Output,
+    pub git: GitRecord,
+}
+
+impl From<(CliBumpFileArgs, bool)> for BumpFileArgs {
+    fn from((args, dry_run): (CliBumpFileArgs, bool)) -> Self {
+        BumpFileArgs {
+            scheme: args.scheme.unwrap_or(Scheme::Semver),
+            pre: args.common.pre_release,
+            build: args.common.build_metadata,
+            git: args.git.record(),
+            input: args.file.clone(),
+            output: Output::new(args.file, args.stdout, dry_run),
+        }
+    }
+}
+
+pub(crate) struct BumpFileSetArgs {
+    pub scheme: Scheme,
+    pub input: PathBuf,
+    pub output: Output,
+    pub git: GitRecord,
+    pub set: SetVersion,
+}
+
+impl From<(CliBumpFileSetArgs, bool)> for BumpFileSetArgs {
+    fn from((args, dry_run): (CliBumpFileSetArgs, bool)) -> Self {
+        BumpFileSetArgs {
+            scheme: args.common.scheme.unwrap_or(Scheme::Semver),
+            git: args.common.git.record(),
+            input: args.common.file.clone(),
+            output: Output::new(args.common.file.clone(), args.common.stdout, dry_run),
+            set: args.into(),
+        }
+    }
+}
+
+pub(crate) struct BumpFilePreArgs {
+    pub scheme: Scheme,
+    pub channel: Option<PreRelease>,
+    pub build: Option<BuildMetadata>,
+    pub input: PathBuf,
+    pub output: Output,
+    pub git: GitRecord,
+}
+
+impl From<(CliBumpFilePreArgs, bool)> for BumpFilePreArgs {
+    fn from((args, dry_run): (CliBumpFilePreArgs, bool)) -> Self {
+        let BumpFileArgs {
+            scheme,
+            pre,
+            build,
+            input,
+            output,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpFilePreArgs {
+            scheme,
+            channel: args.pre.channel.or(pre),
+            build,
+            input,
+            output,
+            git,
+        }
+    }
+}
+
+pub(crate) struct BumpFileReleaseArgs {
+    pub scheme: Scheme,
+    pub pre: Option<PreRelease>,
+    pub build: Option<BuildMetadata>,
+    pub input: PathBuf,
+    pub output: Output,
+    pub git: GitRecord,
+    pub allow_released: bool,
+}
+
+impl From<(CliBumpFileReleaseArgs, bool)> for BumpFileReleaseArgs {
+    fn from((args, dry_run): (CliBumpFileReleaseArgs, bool)) -> Self {
+        let BumpFileArgs {
+            scheme,
+            pre,
+            build,
+            input,
+            output,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpFileReleaseArgs {
+            scheme,
+            pre,
+            build,
+            input,
+            output,
+            git,
+            allow_released: args.release.allow_released,
+        }
+    }
+}
+
+pub(crate) struct BumpFileAutoArgs {
+    pub scheme: Scheme,
+    pub pre: Option<PreRelease>,
+    pub build: Option<BuildMetadata>,
+    pub input: PathBuf,
+    pub output: Output,
+    pub git: GitRecord,
+    pub repo: PathBuf,
+}
+
+impl From<(CliBumpFileAutoArgs, bool)> for BumpFileAutoArgs {
+    fn from((args, dry_run): (CliBumpFileAutoArgs, bool)) -> Self {
+        let BumpFileArgs {
+            scheme,
+            pre,
+            build,
+            input,
+            output,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpFileAutoArgs {
+            scheme,
+            pre,
+            build,
+            input,
+            output,
+            git,
+            repo: args.auto.repo,
+        }
+    }
+}
+
+pub(crate) struct BumpSourcesArgs {
+    pub pre: Option<PreRelease>,
+    pub build: Option<BuildMetadata>,
+    pub sources: SelectSources,
+    pub scheme: Option<Scheme>,
+    pub dest: Destination,
+    pub git: GitRecord,
+}
+
+impl From<(CliBumpConfigArgs, bool)> for BumpSourcesArgs {
+    fn from((args, dry_run): (CliBumpConfigArgs, bool)) -> Self {
+        BumpSourcesArgs {
+            pre: args.common.pre_release,
+            build: args.common.build_metadata,
+            sources: SelectSources::Config(args.config),
+            scheme: args.scheme,
+            dest: Destination::new(false, dry_run),
+            git: args.git.record(),
+        }
+    }
+}
+
+impl From<(CliBumpPatternArgs, bool)> for BumpSourcesArgs {
+    fn from((args, dry_run): (CliBumpPatternArgs, bool)) -> Self {
+        let pattern = args.regex.with_max_matches(args.max_matches);
+
+        BumpSourcesArgs {
+            pre: args.common.pre_release,
+            build: args.common.build_metadata,
+            sources: SelectSources::Given(vec![Source::Pattern(args.file, pattern)]),
+            scheme: args.scheme,
+            dest: Destination::new(args.stdout, dry_run),
+            git: args.git.record(),
+        }
+    }
+}
+
+pub(crate) struct BumpSourcesSetArgs {
+    pub sources: SelectSources,
+    pub scheme: Option<Scheme>,
+    pub dest: Destination,
+    pub git: GitRecord,
+    pub set: SetVersion,
+}
+
+impl From<(CliBumpConfigSetArgs, bool)> for BumpSourcesSetArgs {
+    fn from((args, dry_run): (CliBumpConfigSetArgs, bool)) -> Self {
+        BumpSourcesSetArgs {
+            sources: SelectSources::Config(args.common.config.clone()),
+            scheme: args.common.scheme,
+            dest: Destination::new(false, dry_run),
+            git: args.common.git.record(),
+            set: args.into(),
+        }
+    }
+}
+
+impl From<(CliBumpNpmArgs, bool)> for BumpSourcesArgs {
+    fn from((args, dry_run): (CliBumpNpmArgs, bool)) -> Self {
+        BumpSourcesArgs {
+            pre: args.common.pre_release,
+            build: args.common.build_metadata,
+            sources: SelectSources::Given(vec![Source::Npm(args.package)]),
+            scheme: None,
+            dest: Destination::new(args.stdout, dry_run),
+            git: args.git.record(),
+        }
+    }
+}
+
+impl From<(CliBumpDockerfileArgs, bool)> for BumpSourcesArgs {
+    fn from((args, dry_run): (CliBumpDockerfileArgs, bool)) -> Self {
+        BumpSourcesArgs {
+            pre: args.common.pre_release,
+            build: args.common.build_metadata,
+            sources: SelectSources::Given(vec![Source::Dockerfile(args.dockerfile)]),
+            scheme: None,
+            dest: Destination::new(args.stdout, dry_run),
+            git: args.git.record(),
+        }
+    }
+}
+
+impl From<(CliBumpGradleArgs, bool)> for BumpSourcesArgs {
+    fn from((args, dry_run): (CliBumpGradleArgs, bool)) -> Self {
+        BumpSourcesArgs {
+            pre: args.common.pre_release,
+            build: args.common.build_metadata,
+            sources: SelectSources::Given(vec![Source::Gradle(args.properties)]),
+            scheme: None,
+            dest: Destination::new(args.stdout, dry_run),
+            git: args.git.record(),
+        }
+    }
+}
+
+impl From<(CliBumpMavenArgs, bool)> for BumpSourcesArgs {
+    fn from((args, dry_run): (CliBumpMavenArgs, bool)) -> Self {
+        BumpSourcesArgs {
+            pre: args.common.pre_release,
+            build: args.common.build_metadata,
+            sources: SelectSources::Given(vec![Source::Maven(args.pom)]),
+            scheme: None,
+            dest: Destination::new(args.stdout, dry_run),
+            git: args.git.record(),
+        }
+    }
+}
+
+impl From<(CliBumpHelmArgs, bool)> for BumpSourcesArgs {
+    fn from((args, dry_run): (CliBumpHelmArgs, bool)) -> Self {
+        let app_version = match args.app_version_from {
+            Some(manifest) => AppVersion::Crate(manifest),
+            None => AppVersion::Keep,
+        };
+
+        BumpSourcesArgs {
+            pre: args.common.pre_release,
+            build: args.common.build_metadata,
+            sources: SelectSources::Given(vec![Source::Helm(args.chart, app_version)]),
+            scheme: None,
+            dest: Destination::new(args.stdout, dry_run),
+            git: args.git.record(),
+        }
+    }
+}
+
+impl From<(CliBumpPyProjectArgs, bool)> for BumpSourcesArgs {
+    fn from((args, dry_run): (CliBumpPyProjectArgs, bool)) -> Self {
+        BumpSourcesArgs {
+            pre: args.common.pre_release,
+            build: args.common.build_metadata,
+            sources: SelectSources::Given(vec![Source::PyProject(args.pyproject)]),
+            scheme: args.scheme,
+            dest: Destination::new(args.stdout, dry_run),
+            git: args.git.record(),
+        }
+    }
+}
+
+impl From<(CliBumpPatternSetArgs, bool)> for BumpSourcesSetArgs {
+    fn from((args, dry_run): (CliBumpPatternSetArgs, bool)) -> Self {
+        let git = args.common.git.record();
+        let dest = Destination::new(args.common.stdout, dry_run);
+        let path = args.common.file.clone();
+        let pattern = args
+            .common
+            .regex
+            .clone()
+            .with_max_matches(args.common.max_matches);
+
+        BumpSourcesSetArgs {
+            sources: SelectSources::Given(vec![Source::Pattern(path, pattern)]),
+            scheme: args.common.scheme,
+            dest,
+            git,
+            set: args.into(),
+        }
+    }
+}
+
+impl From<(CliBumpNpmSetArgs, bool)> for BumpSourcesSetArgs {
+    fn from((args, dry_run): (CliBumpNpmSetArgs, bool)) -> Self {
+        let git = args.common.git.record();
+        let dest = Destination::new(args.common.stdout, dry_run);
+        let path = args.common.package.clone();
+
+        BumpSourcesSetArgs {
+            sources: SelectSources::Given(vec![Source::Npm(path)]),
+            scheme: None,
+            dest,
+            git,
+            set: args.into(),
+        }
+    }
+}
+
+impl From<(CliBumpDockerfileSetArgs, bool)> for BumpSourcesSetArgs {
+    fn from((args, dry_run): (CliBumpDockerfileSetArgs, bool)) -> Self {
+        let git = args.common.git.record();
+        let dest = Destination::new(args.common.stdout, dry_run);
+        let path = args.common.dockerfile.clone();
+
+        BumpSourcesSetArgs {
+            sources: SelectSources::Given(vec![Source::Dockerfile(path)]),
+            scheme: None,
+            dest,
+            git,
+            set: args.into(),
+        }
+    }
+}
+
+impl From<(CliBumpGradleSetArgs, bool)> for BumpSourcesSetArgs {
+    fn from((args, dry_run): (CliBumpGradleSetArgs, bool)) -> Self {
+        let git = args.common.git.record();
+        let dest = Destination::new(args.common.stdout, dry_run);
+        let path = args.common.properties.clone();
+
+        BumpSourcesSetArgs {
+            sources: SelectSources::Given(vec![Source::Gradle(path)]),
+            scheme: None,
+            dest,
+            git,
+            set: args.into(),
+        }
+    }
+}
+
+impl From<(CliBumpMavenSetArgs, bool)> for BumpSourcesSetArgs {
+    fn from((args, dry_run): (CliBumpMavenSetArgs, bool)) -> Self {
+        let git = args.common.git.record();
+        let dest = Destination::new(args.common.stdout, dry_run);
+        let path = args.common.pom.clone();
+
+        BumpSourcesSetArgs {
+            sources: SelectSources::Given(vec![Source::Maven(path)]),
+            scheme: None,
+            dest,
+            git,
+            set: args.into(),
+        }
+    }
 }
 
-pub(crate) struct BumpCargoArgs {
+impl From<(CliBumpHelmSetArgs, bool)> for BumpSourcesSetArgs {
+    fn from((args, dry_run): (CliBumpHelmSetArgs, bool)) -> Self {
+        let git = args.common.git.record();
+        let dest = Destination::new(args.common.stdout, dry_run);
+        let app_version = match args.common.app_version_from.clone() {
+            Some(manifest) => AppVersion::Crate(manifest),
+            None => AppVersion::Keep,
+        };
+        let path = args.common.chart.clone();
+
+        BumpSourcesSetArgs {
+            sources: SelectSources::Given(vec![Source::Helm(path, app_version)]),
+            scheme: None,
+            dest,
+            git,
+            set: args.into(),
+        }
+    }
+}
+
+impl From<(CliBumpPyProjectSetArgs, bool)> for BumpSourcesSetArgs {
+    fn from((args, dry_run): (CliBumpPyProjectSetArgs, bool)) -> Self {
+        let git = args.common.git.record();
+        let dest = Destination::new(args.common.stdout, dry_run);
+        let path = args.common.pyproject.clone();
+
+        BumpSourcesSetArgs {
+            sources: SelectSources::Given(vec![Source::PyProject(path)]),
+            scheme: args.common.scheme,
+            dest,
+            git,
+            set: args.into(),
+        }
+    }
+}
+
+pub(crate) struct BumpSourcesPreArgs {
+    pub channel: Option<PreRelease>,
+    pub build: Option<BuildMetadata>,
+    pub sources: SelectSources,
+    pub scheme: Option<Scheme>,
+    pub dest: Destination,
+    pub git: GitRecord,
+}
+
+impl From<(CliBumpConfigPreArgs, bool)> for BumpSourcesPreArgs {
+    fn from((args, dry_run): (CliBumpConfigPreArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesPreArgs {
+            channel: args.pre.channel.or(pre),
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        }
+    }
+}
+
+impl From<(CliBumpPatternPreArgs, bool)> for BumpSourcesPreArgs {
+    fn from((args, dry_run): (CliBumpPatternPreArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesPreArgs {
+            channel: args.pre.channel.or(pre),
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        }
+    }
+}
+
+impl From<(CliBumpNpmPreArgs, bool)> for BumpSourcesPreArgs {
+    fn from((args, dry_run): (CliBumpNpmPreArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesPreArgs {
+            channel: args.pre.channel.or(pre),
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        }
+    }
+}
+
+impl From<(CliBumpDockerfilePreArgs, bool)> for BumpSourcesPreArgs {
+    fn from((args, dry_run): (CliBumpDockerfilePreArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesPreArgs {
+            channel: args.pre.channel.or(pre),
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        }
+    }
+}
+
+impl From<(CliBumpGradlePreArgs, bool)> for BumpSourcesPreArgs {
+    fn from((args, dry_run): (CliBumpGradlePreArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesPreArgs {
+            channel: args.pre.channel.or(pre),
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        }
+    }
+}
+
+impl From<(CliBumpMavenPreArgs, bool)> for BumpSourcesPreArgs {
+    fn from((args, dry_run): (CliBumpMavenPreArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesPreArgs {
+            channel: args.pre.channel.or(pre),
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        }
+    }
+}
+
+impl From<(CliBumpHelmPreArgs, bool)> for BumpSourcesPreArgs {
+    fn from((args, dry_run): (CliBumpHelmPreArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesPreArgs {
+            channel: args.pre.channel.or(pre),
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        }
+    }
+}
+
+impl From<(CliBumpPyProjectPreArgs, bool)> for BumpSourcesPreArgs {
+    fn from((args, dry_run): (CliBumpPyProjectPreArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesPreArgs {
+            channel: args.pre.channel.or(pre),
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        }
+    }
+}
+
+pub(crate) struct BumpSourcesReleaseArgs {
     pub pre: Option<PreRelease>,
     pub build: Option<BuildMetadata>,
-    pub input: PathBuf,
-    pub output: Output,
+    pub sources: SelectSources,
+    pub scheme: Option<Scheme>,
+    pub dest: Destination,
+    pub git: GitRecord,
+    pub allow_released: bool,
 }
 
-impl From<CliBumpCargoArgs> for BumpCargoArgs {
-    fn from(args: CliBumpCargoArgs) -> Self {
-        BumpCargoArgs {
+impl From<(CliBumpConfigReleaseArgs, bool)> for BumpSourcesReleaseArgs {
+    fn from((args, dry_run): (CliBumpConfigReleaseArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesReleaseArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+            allow_released: args.release.allow_released,
+        }
+    }
+}
+
+impl From<(CliBumpPatternReleaseArgs, bool)> for BumpSourcesReleaseArgs {
+    fn from((args, dry_run): (CliBumpPatternReleaseArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesReleaseArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+            allow_released: args.release.allow_released,
+        }
+    }
+}
+
+impl From<(CliBumpNpmReleaseArgs, bool)> for BumpSourcesReleaseArgs {
+    fn from((args, dry_run): (CliBumpNpmReleaseArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesReleaseArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+            allow_released: args.release.allow_released,
+        }
+    }
+}
+
+impl From<(CliBumpDockerfileReleaseArgs, bool)> for BumpSourcesReleaseArgs {
+    fn from((args, dry_run): (CliBumpDockerfileReleaseArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesReleaseArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+            allow_released: args.release.allow_released,
+        }
+    }
+}
+
+impl From<(CliBumpGradleReleaseArgs, bool)> for BumpSourcesReleaseArgs {
+    fn from((args, dry_run): (CliBumpGradleReleaseArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesReleaseArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+            allow_released: args.release.allow_released,
+        }
+    }
+}
+
+impl From<(CliBumpMavenReleaseArgs, bool)> for BumpSourcesReleaseArgs {
+    fn from((args, dry_run): (CliBumpMavenReleaseArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesReleaseArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+            allow_released: args.release.allow_released,
+        }
+    }
+}
+
+impl From<(CliBumpHelmReleaseArgs, bool)> for BumpSourcesReleaseArgs {
+    fn from((args, dry_run): (CliBumpHelmReleaseArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesReleaseArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+            allow_released: args.release.allow_released,
+        }
+    }
+}
+
+impl From<(CliBumpPyProjectReleaseArgs, bool)> for BumpSourcesReleaseArgs {
+    fn from((args, dry_run): (CliBumpPyProjectReleaseArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesReleaseArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+            allow_released: args.release.allow_released,
+        }
+    }
+}
+
+pub(crate) struct BumpSourcesCalverArgs {
+    pub pre: Option<PreRelease>,
+    pub build: Option<BuildMetadata>,
+    pub sources: SelectSources,
+    pub scheme: Option<Scheme>,
+    pub dest: Destination,
+    pub git: GitRecord,
+    pub format: calver::Format,
+    /// The date the calendar version rolls to.
+    pub today: NaiveDate,
+}
+
+impl From<(CliBumpConfigCalverArgs, bool)> for BumpSourcesCalverArgs {
+    fn from((args, dry_run): (CliBumpConfigCalverArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesCalverArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+            format: args.calver.format,
+            today: Local::today().naive_local(),
+        }
+    }
+}
+
+impl From<(CliBumpPatternCalverArgs, bool)> for BumpSourcesCalverArgs {
+    fn from((args, dry_run): (CliBumpPatternCalverArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesCalverArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+            format: args.calver.format,
+            today: Local::today().naive_local(),
+        }
+    }
+}
+
+pub(crate) struct BumpSourcesAutoArgs {
+    pub pre: Option<PreRelease>,
+    pub build: Option<BuildMetadata>,
+    pub sources: SelectSources,
+    pub scheme: Option<Scheme>,
+    pub dest: Destination,
+    pub git: GitRecord,
+    pub repo: PathBuf,
+}
+
+impl From<(CliBumpConfigAutoArgs, bool)> for BumpSourcesAutoArgs {
+    fn from((args, dry_run): (CliBumpConfigAutoArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesAutoArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+            repo: args.auto.repo,
+        }
+    }
+}
+
+impl From<(CliBumpPatternAutoArgs, bool)> for BumpSourcesAutoArgs {
+    fn from((args, dry_run): (CliBumpPatternAutoArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesAutoArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+            repo: args.auto.repo,
+        }
+    }
+}
+
+impl From<(CliBumpNpmAutoArgs, bool)> for BumpSourcesAutoArgs {
+    fn from((args, dry_run): (CliBumpNpmAutoArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesAutoArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+            repo: args.auto.repo,
+        }
+    }
+}
+
+impl From<(CliBumpDockerfileAutoArgs, bool)> for BumpSourcesAutoArgs {
+    fn from((args, dry_run): (CliBumpDockerfileAutoArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesAutoArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+            repo: args.auto.repo,
+        }
+    }
+}
+
+impl From<(CliBumpGradleAutoArgs, bool)> for BumpSourcesAutoArgs {
+    fn from((args, dry_run): (CliBumpGradleAutoArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesAutoArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+            repo: args.auto.repo,
+        }
+    }
+}
+
+impl From<(CliBumpMavenAutoArgs, bool)> for BumpSourcesAutoArgs {
+    fn from((args, dry_run): (CliBumpMavenAutoArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesAutoArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+            repo: args.auto.repo,
+        }
+    }
+}
+
+impl From<(CliBumpHelmAutoArgs, bool)> for BumpSourcesAutoArgs {
+    fn from((args, dry_run): (CliBumpHelmAutoArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesAutoArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+            repo: args.auto.repo,
+        }
+    }
+}
+
+impl From<(CliBumpPyProjectAutoArgs, bool)> for BumpSourcesAutoArgs {
+    fn from((args, dry_run): (CliBumpPyProjectAutoArgs, bool)) -> Self {
+        let BumpSourcesArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+        } = (args.common, dry_run).into();
+
+        BumpSourcesAutoArgs {
+            pre,
+            build,
+            sources,
+            scheme,
+            dest,
+            git,
+            repo: args.auto.repo,
+        }
+    }
+}
+
+pub(crate) struct BumpStdinArgs {
+    pub scheme: Scheme,
+    pub pre: Option<PreRelease>,
+    pub build: Option<BuildMetadata>,
+    pub input: Box<dyn Read>,
+    pub output: Box<dyn Write>,
+}
+
+impl From<CliBumpStdinArgs> for BumpStdinArgs {
+    fn from(args: CliBumpStdinArgs) -> Self {
+        BumpStdinArgs {
+            scheme: args.scheme.unwrap_or(Scheme::Semver),
             pre: args.common.pre_release,
             build: args.common.build_metadata,
-            input: args.manifest.clone(),
-            output: if args.stdout {
-                Output::Stdout(Box::new(std::io::stdout()))
-            } else {
-                Output::File(args.manifest)
-            },
+            input: Box::new(std::io::stdin()),
+            output: Box::new(std::io::stdout()),
         }
     }
 }
 
-pub(crate) struct BumpCargoSetArgs {
-    pub input: PathBuf,
-    pub output: Output,
+pub(crate) struct BumpStdinSetArgs {
+    pub scheme: Scheme,
+    pub input: Box<dyn Read>,
+    pub output: Box<dyn Write>,
     pub set: SetVersion,
 }
 
-impl From<CliBumpCargoSetArgs> for BumpCargoSetArgs {
-    fn from(args: CliBumpCargoSetArgs) -> Self {
-        BumpCargoSetArgs {
-            input: args.common.manifest.clone(),
-            output: if args.common.stdout {
-                Output::Stdout(Box::new(std::io::stdout()))
-            } else {
-                Output::File(args.common.manifest.clone())
-            },
+impl From<CliBumpStdinSetArgs> for BumpStdinSetArgs {
+    fn from(args: CliBumpStdinSetArgs) -> Self {
+        BumpStdinSetArgs {
+            scheme: args.common.scheme.unwrap_or(Scheme::Semver),
+            input: Box::new(std::io::stdin()),
+            output: Box::new(std::io::stdout()),
             set: args.into(),
         }
     }
 }
 
-pub(crate) struct BumpFileArgs {
+pub(crate) struct BumpStdinPreArgs {
+    pub scheme: Scheme,
+    pub channel: Option<PreRelease>,
+    pub build: Option<BuildMetadata>,
+    pub input: Box<dyn Read>,
+    pub output: Box<dyn Write>,
+}
+
+impl From<CliBumpStdinPreArgs> for BumpStdinPreArgs {
+    fn from(args: CliBumpStdinPreArgs) -> Self {
+        let BumpStdinArgs {
+            scheme,
+            pre,
+            build,
+            input,
+            output,
+        } = args.common.into();
+
+        BumpStdinPreArgs {
+            scheme,
+            channel: args.pre.channel.or(pre),
+            build,
+            input,
+            output,
+        }
+    }
+}
+
+pub(crate) struct BumpStdinReleaseArgs {
+    pub scheme: Scheme,
+    pub pre: Option<PreRelease>,
+    pub build: Option<BuildMetadata>,
+    pub input: Box<dyn Read>,
+    pub output: Box<dyn Write>,
+    pub allow_released: bool,
+}
+
+impl From<CliBumpStdinReleaseArgs> for BumpStdinReleaseArgs {
+    fn from(args: CliBumpStdinReleaseArgs) -> Self {
+        let BumpStdinArgs {
+            scheme,
+            pre,
+            build,
+            input,
+            output,
+        } = args.common.into();
+
+        BumpStdinReleaseArgs {
+            scheme,
+            pre,
+            build,
+            input,
+            output,
+            allow_released: args.release.allow_released,
+        }
+    }
+}
+
+pub(crate) struct BumpStdinAutoArgs {
+    pub scheme: Scheme,
     pub pre: Option<PreRelease>,
     pub build: Option<BuildMetadata>,
+    pub input: Box<dyn Read>,
+    pub output: Box<dyn Write>,
+    pub repo: PathBuf,
+}
+
+impl From<CliBumpStdinAutoArgs> for BumpStdinAutoArgs {
+    fn from(args: CliBumpStdinAutoArgs) -> Self {
+        let BumpStdinArgs {
+            scheme,
+            pre,
+            build,
+            input,
+            output,
+        } = args.common.into();
+
+        BumpStdinAutoArgs {
+            scheme,
+            pre,
+            build,
+            input,
+            output,
+            repo: args.auto.repo,
+        }
+    }
+}
+
+pub(crate) struct GetCargoArgs {
+    pub part: VersionPart,
     pub input: PathBuf,
-    pub output: Output,
+    pub output: Box<dyn Write>,
 }
 
-impl From<CliBumpFileArgs> for BumpFileArgs {
-    fn from(args: CliBumpFileArgs) -> Self {
-        BumpFileArgs {
-            pre: args.common.pre_release,
-            build: args.common.build_metadata,
-            input: args.file.clone(),
-            output: if args.stdout {
-                Output::Stdout(Box::new(std::io::stdout()))
+impl From<CliGetCargoArgs> for GetCargoArgs {
+    fn from(args: CliGetCargoArgs) -> Self {
+        GetCargoArgs {
+            part: args.part.part(),
+            input: args.manifest,
+            output: Box::new(std::io::stdout()),
+        }
+    }
+}
+
+pub(crate) struct GetFileArgs {
+    pub part: VersionPart,
+    pub scheme: Scheme,
+    pub input: PathBuf,
+    pub output: Box<dyn Write>,
+}
+
+impl From<CliGetFileArgs> for GetFileArgs {
+    fn from(args: CliGetFileArgs) -> Self {
+        GetFileArgs {
+            part: args.part.part(),
+            scheme: args.scheme.unwrap_or(Scheme::Semver),
+            input: args.file,
+            output: Box::new(std::io::stdout()),
+        }
+    }
+}
+
+pub(crate) struct GetStdinArgs {
+    pub part: VersionPart,
+    pub scheme: Scheme,
+    pub input: Box<dyn Read>,
+    pub output: Box<dyn Write>,
+}
+
+impl From<CliGetStdinArgs> for GetStdinArgs {
+    fn from(args: CliGetStdinArgs) -> Self {
+        GetStdinArgs {
+            part: args.part.part(),
+            scheme: args.scheme.unwrap_or(Scheme::Semver),
+            input: Box::new(std::io::stdin()),
+            output: Box::new(std::io::stdout()),
+        }
+    }
+}
+
+pub(crate) struct CheckCargoArgs {
+    pub expected: Expected,
+    pub input: PathBuf,
+}
+
+impl From<CliCheckCargoArgs> for CheckCargoArgs {
+    fn from(args: CliCheckCargoArgs) -> Self {
+        CheckCargoArgs {
+            expected: args.expect.expected(),
+            input: args.manifest,
+        }
+    }
+}
+
+pub(crate) struct CheckFileArgs {
+    pub expected: Expected,
+    pub scheme: Scheme,
+    pub input: PathBuf,
+}
+
+impl From<CliCheckFileArgs> for CheckFileArgs {
+    fn from(args: CliCheckFileArgs) -> Self {
+        CheckFileArgs {
+            expected: args.expect.expected(),
+            scheme: args.scheme.unwrap_or(Scheme::Semver),
+            input: args.file,
+        }
+    }
+}
+
+pub(crate) struct CheckStdinArgs {
+    pub expected: Expected,
+    pub scheme: Scheme,
+    pub input: Box<dyn Read>,
+}
+
+impl From<CliCheckStdinArgs> for CheckStdinArgs {
+    fn from(args: CliCheckStdinArgs) -> Self {
+        CheckStdinArgs {
+            expected: args.expect.expected(),
+            scheme: args.scheme.unwrap_or(Scheme::Semver),
+            input: Box::new(std::io::stdin()),
+        }
+    }
+}
+
+pub(crate) struct VerifyArgs {
+    pub sources: SelectSources,
+    pub scheme: Option<Scheme>,
+    pub output: Box<dyn Write>,
+}
+
+impl From<CliVerifyArgs> for VerifyArgs {
+    fn from(args: CliVerifyArgs) -> Self {
+        let cargo = args.cargo.into_iter().map(Source::Cargo);
+        let file = args.file.into_iter().map(Source::File);
+        let pattern = args
+            .pattern
+            .into_iter()
+            .map(|PatternArg(path, pattern)| Source::Pattern(path, pattern));
+        let npm = args.npm.into_iter().map(Source::Npm);
+        let pyproject = args.pyproject.into_iter().map(Source::PyProject);
+        let helm = args
+            .helm
+            .into_iter()
+            .map(|path| Source::Helm(path, AppVersion::Keep));
+        let maven = args.maven.into_iter().map(Source::Maven);
+        let gradle = args.gradle.into_iter().map(Source::Gradle);
+        let dockerfile = args.dockerfile.into_iter().map(Source::Dockerfile);
+
+        let sources: Vec<_> = cargo
+            .chain(file)
+            .chain(pattern)
+            .chain(npm)
+            .chain(pyproject)
+            .chain(helm)
+            .chain(maven)
+            .chain(gradle)
+            .chain(dockerfile)
+            .collect();
+
+        VerifyArgs {
+            sources: if sources.is_empty() {
+                SelectSources::Config(args.config)
             } else {
-                Output::File(args.file)
+                SelectSources::Given(sources)
             },
+            scheme: args.scheme,
+            output: Box::new(std::io::stdout()),
         }
     }
 }
 
-pub(crate) struct BumpFileSetArgs {
-    pub input: PathBuf,
-    pub output: Output,
-    pub set: SetVersion,
+/// The version, or requirement on the version, which a check expects.
+#[derive(Debug)]
+pub(crate) enum Expected {
+    /// The text of the exact version, which is parsed by the scheme of the version checked.
+    Version(String),
+    Requirement(VersionReq),
+}
+
+/// Whether to record a new version in git, with the templates for its message and tag name.
+#[derive(Debug)]
+pub(crate) enum GitRecord {
+    None,
+    Commit { message: String },
+    Tag { message: String, tag: String },
+}
+
+/// The part of a version to print.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum VersionPart {
+    All,
+    Major,
+    Minor,
+    Patch,
+    PreRelease,
+    BuildMetadata,
+    MajorMinor,
 }
 
-impl From<CliBumpFileSetArgs> for BumpFileSetArgs {
-    fn from(args: CliBumpFileSetArgs) -> Self {
-        BumpFileSetArgs {
-            input: args.common.file.clone(),
-            output: if args.common.stdout {
-                Output::Stdout(Box::new(std::io::stdout()))
-            } else {
-                Output::File(args.common.file.clone())
-            },
-            set: args.into(),
+/// The format in which a command reports its outcome on standard output.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Format {
+    /// The version, or the files, as each command usually prints them.
+    Text,
+    /// A single JSON object on one line.
+    Json,
+    /// Shell `export` lines, which are also appended as `KEY=VALUE` lines to the file, if any.
+    Export(Option<PathBuf>),
+}
+
+impl FromStr for Format {
+    type Err = failure::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => bail!("unknown output format '{}', expected text or json", s),
         }
     }
 }
 
-pub(crate) struct BumpStdinArgs {
-    pub pre: Option<PreRelease>,
-    pub build: Option<BuildMetadata>,
-    pub input: Box<dyn Read>,
-    pub output: Box<dyn Write>,
+/// The files holding the version, either given on the command line or listed in a configuration
+/// file, which is searched for when no path is given.
+#[derive(Debug)]
+pub(crate) enum SelectSources {
+    Config(Option<PathBuf>),
+    Given(Vec<Source>),
 }
 
-impl From<CliBumpStdinArgs> for BumpStdinArgs {
-    fn from(args: CliBumpStdinArgs) -> Self {
-        BumpStdinArgs {
-            pre: args.common.pre_release,
-            build: args.common.build_metadata,
-            input: Box::new(std::io::stdin()),
-            output: Box::new(std::io::stdout()),
-        }
+impl SelectSources {
+    /// Loads the sources along with the scheme their versions follow: `scheme` when it is given,
+    /// then the scheme of the configuration, then PEP 440 if every source is a Python project,
+    /// and otherwise Semantic Versioning.
+    pub(crate) fn load(self, scheme: Option<Scheme>) -> crate::Result<(Vec<Source>, Scheme)> {
+        let (sources, configured) = match self {
+            SelectSources::Config(path) => {
+                let config = match path {
+                    Some(path) => Config::load(&path)?,
+                    None => Config::find()?,
+                };
+                info!("using configuration {}", config.path.display());
+
+                (config.sources, config.scheme)
+            }
+            SelectSources::Given(sources) => (sources, None),
+        };
+        let scheme = scheme.or(configured).unwrap_or_else(|| {
+            if sources
+                .iter()
+                .all(|source| matches!(source, Source::PyProject(_)))
+            {
+                Scheme::Pep440
+            } else {
+                Scheme::Semver
+            }
+        });
+        debug!("versions follow the {} scheme", scheme);
+
+        Ok((sources, scheme))
     }
 }
 
-pub(crate) struct BumpStdinSetArgs {
-    pub input: Box<dyn Read>,
-    pub output: Box<dyn Write>,
-    pub set: SetVersion,
+/// Where the new contents of the files holding a version are written.
+pub(crate) enum Destination {
+    Files,
+    Stdout(Box<dyn Write>),
+    /// Previews the changes to the files without writing.
+    DryRun(Box<dyn Write>),
 }
 
-impl From<CliBumpStdinSetArgs> for BumpStdinSetArgs {
-    fn from(args: CliBumpStdinSetArgs) -> Self {
-        BumpStdinSetArgs {
-            input: Box::new(std::io::stdin()),
-            output: Box::new(std::io::stdout()),
-            set: args.into(),
+impl Destination {
+    fn new(stdout: bool, dry_run: bool) -> Self {
+        if stdout {
+            Destination::Stdout(Box::new(std::io::stdout()))
+        } else if dry_run {
+            Destination::DryRun(Box::new(std::io::stdout()))
+        } else {
+            Destination::Files
         }
     }
 }
 
+#[derive(Debug)]
+pub(crate) enum SelectMembers {
+    All,
+    Some(Vec<String>),
+    None,
+}
+
 #[derive(Debug)]
 pub(crate) enum SetPreRelease {
     Some(PreRelease),
@@ -450,7 +3676,8 @@ pub(crate) enum SetBuildMetadata {
 
 #[derive(Debug)]
 pub(crate) enum SetVersion {
-    Version(Version),
+    /// The text of a whole version, parsed by the scheme of the version it replaces.
+    Version(String),
     Parts {
         major: Option<u64>,
         minor: Option<u64>,
@@ -518,6 +3745,238 @@ impl From<CliBumpFileSetArgs> for SetVersion {
     }
 }
 
+impl From<CliBumpConfigSetArgs> for SetVersion {
+    fn from(args: CliBumpConfigSetArgs) -> Self {
+        match args.set.version {
+            Some(version) => SetVersion::Version(version),
+            None => SetVersion::Parts {
+                major: args.set.major,
+                minor: args.set.minor,
+                patch: args.set.patch,
+                pre: if args.set.no_pre_release {
+                    SetPreRelease::Clear
+                } else {
+                    match args.common.common.pre_release {
+                        Some(pre) => SetPreRelease::Some(pre),
+                        None => SetPreRelease::None,
+                    }
+                },
+                build: if args.set.no_build_metadata {
+                    SetBuildMetadata::Clear
+                } else {
+                    match args.common.common.build_metadata {
+                        Some(build) => SetBuildMetadata::Some(build),
+                        None => SetBuildMetadata::None,
+                    }
+                },
+            },
+        }
+    }
+}
+
+impl From<CliBumpPatternSetArgs> for SetVersion {
+    fn from(args: CliBumpPatternSetArgs) -> Self {
+        match args.set.version {
+            Some(version) => SetVersion::Version(version),
+            None => SetVersion::Parts {
+                major: args.set.major,
+                minor: args.set.minor,
+                patch: args.set.patch,
+                pre: if args.set.no_pre_release {
+                    SetPreRelease::Clear
+                } else {
+                    match args.common.common.pre_release {
+                        Some(pre) => SetPreRelease::Some(pre),
+                        None => SetPreRelease::None,
+                    }
+                },
+                build: if args.set.no_build_metadata {
+                    SetBuildMetadata::Clear
+                } else {
+                    match args.common.common.build_metadata {
+                        Some(build) => SetBuildMetadata::Some(build),
+                        None => SetBuildMetadata::None,
+                    }
+                },
+            },
+        }
+    }
+}
+
+impl From<CliBumpNpmSetArgs> for SetVersion {
+    fn from(args: CliBumpNpmSetArgs) -> Self {
+        match args.set.version {
+            Some(version) => SetVersion::Version(version),
+            None => SetVersion::Parts {
+                major: args.set.major,
+                minor: args.set.minor,
+                patch: args.set.patch,
+                pre: if args.set.no_pre_release {
+                    SetPreRelease::Clear
+                } else {
+                    match args.common.common.pre_release {
+                        Some(pre) => SetPreRelease::Some(pre),
+                        None => SetPreRelease::None,
+                    }
+                },
+                build: if args.set.no_build_metadata {
+                    SetBuildMetadata::Clear
+                } else {
+                    match args.common.common.build_metadata {
+                        Some(build) => SetBuildMetadata::Some(build),
+                        None => SetBuildMetadata::None,
+                    }
+                },
+            },
+        }
+    }
+}
+
+impl From<CliBumpDockerfileSetArgs> for SetVersion {
+    fn from(args: CliBumpDockerfileSetArgs) -> Self {
+        match args.set.version {
+            Some(version) => SetVersion::Version(version),
+            None => SetVersion::Parts {
+                major: args.set.major,
+                minor: args.set.minor,
+                patch: args.set.patch,
+                pre: if args.set.no_pre_release {
+                    SetPreRelease::Clear
+                } else {
+                    match args.common.common.pre_release {
+                        Some(pre) => SetPreRelease::Some(pre),
+                        None => SetPreRelease::None,
+                    }
+                },
+                build: if args.set.no_build_metadata {
+                    SetBuildMetadata::Clear
+                } else {
+                    match args.common.common.build_metadata {
+                        Some(build) => SetBuildMetadata::Some(build),
+                        None => SetBuildMetadata::None,
+                    }
+                },
+            },
+        }
+    }
+}
+
+impl From<CliBumpGradleSetArgs> for SetVersion {
+    fn from(args: CliBumpGradleSetArgs) -> Self {
+        match args.set.version {
+            Some(version) => SetVersion::Version(version),
+            None => SetVersion::Parts {
+                major: args.set.major,
+                minor: args.set.minor,
+                patch: args.set.patch,
+                pre: if args.set.no_pre_release {
+                    SetPreRelease::Clear
+                } else {
+                    match args.common.common.pre_release {
+                        Some(pre) => SetPreRelease::Some(pre),
+                        None => SetPreRelease::None,
+                    }
+                },
+                build: if args.set.no_build_metadata {
+                    SetBuildMetadata::Clear
+                } else {
+                    match args.common.common.build_metadata {
+                        Some(build) => SetBuildMetadata::Some(build),
+                        None => SetBuildMetadata::None,
+                    }
+                },
+            },
+        }
+    }
+}
+
+impl From<CliBumpMavenSetArgs> for SetVersion {
+    fn from(args: CliBumpMavenSetArgs) -> Self {
+        match args.set.version {
+            Some(version) => SetVersion::Version(version),
+            None => SetVersion::Parts {
+                major: args.set.major,
+                minor: args.set.minor,
+                patch: args.set.patch,
+                pre: if args.set.no_pre_release {
+                    SetPreRelease::Clear
+                } else {
+                    match args.common.common.pre_release {
+                        Some(pre) => SetPreRelease::Some(pre),
+                        None => SetPreRelease::None,
+                    }
+                },
+                build: if args.set.no_build_metadata {
+                    SetBuildMetadata::Clear
+                } else {
+                    match args.common.common.build_metadata {
+                        Some(build) => SetBuildMetadata::Some(build),
+                        None => SetBuildMetadata::None,
+                    }
+                },
+            },
+        }
+    }
+}
+
+impl From<CliBumpHelmSetArgs> for SetVersion {
+    fn from(args: CliBumpHelmSetArgs) -> Self {
+        match args.set.version {
+            Some(version) => SetVersion::Version(version),
+            None => SetVersion::Parts {
+                major: args.set.major,
+                minor: args.set.minor,
+                patch: args.set.patch,
+                pre: if args.set.no_pre_release {
+                    SetPreRelease::Clear
+                } else {
+                    match args.common.common.pre_release {
+                        Some(pre) => SetPreRelease::Some(pre),
+                        None => SetPreRelease::None,
+                    }
+                },
+                build: if args.set.no_build_metadata {
+                    SetBuildMetadata::Clear
+                } else {
+                    match args.common.common.build_metadata {
+                        Some(build) => SetBuildMetadata::Some(build),
+                        None => SetBuildMetadata::None,
+                    }
+                },
+            },
+        }
+    }
+}
+
+impl From<CliBumpPyProjectSetArgs> for SetVersion {
+    fn from(args: CliBumpPyProjectSetArgs) -> Self {
+        match args.set.version {
+            Some(version) => SetVersion::Version(version),
+            None => SetVersion::Parts {
+                major: args.set.major,
+                minor: args.set.minor,
+                patch: args.set.patch,
+                pre: if args.set.no_pre_release {
+                    SetPreRelease::Clear
+                } else {
+                    match args.common.common.pre_release {
+                        Some(pre) => SetPreRelease::Some(pre),
+                        None => SetPreRelease::None,
+                    }
+                },
+                build: if args.set.no_build_metadata {
+                    SetBuildMetadata::Clear
+                } else {
+                    match args.common.common.build_metadata {
+                        Some(build) => SetBuildMetadata::Some(build),
+                        None => SetBuildMetadata::None,
+                    }
+                },
+            },
+        }
+    }
+}
+
 impl From<CliBumpStdinSetArgs> for SetVersion {
     fn from(args: CliBumpStdinSetArgs) -> Self {
         match args.set.version {
@@ -2,24 +2,26 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::cli::{SetBuildMetadata, SetPreRelease, SetVersion};
-use crate::io;
-use crate::version::{BuildMetadata, PreRelease, Version, VersionBumper};
+use crate::cli::{GitRecord, SetBuildMetadata, SetPreRelease, SetVersion};
+use crate::conventional::Change;
+use crate::diff;
+use crate::error::VersioError;
+use crate::git::Repository;
+use crate::pep440::{Pep440Version, Phase};
+use crate::version::{BuildMetadata, PreRelease, SchemeVersion, Version, VersionBumper};
 use crate::Result;
-use std::io::Read;
+use failure::bail;
+use log::info;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 pub(crate) mod cargo;
 pub(crate) mod file;
+pub(crate) mod sources;
 pub(crate) mod stdin;
 
-fn prepare_version_from_reader<R: Read>(
-    reader: &mut R,
-    pre: Option<PreRelease>,
-    build: Option<BuildMetadata>,
-) -> Result<VersionBumper> {
-    prepare_version(|| io::read_version(reader), pre, build)
-}
-
 fn prepare_version<F>(
     mut version: F,
     pre: Option<PreRelease>,
@@ -33,9 +35,199 @@ where
         .maybe_build(build))
 }
 
+fn bump_pre(
+    version: Version,
+    channel: Option<PreRelease>,
+    build: Option<BuildMetadata>,
+) -> Result<Version> {
+    let current = version.to_string();
+
+    match VersionBumper::new(version)
+        .maybe_build(build)
+        .bump_pre(channel)?
+    {
+        Some(version) => Ok(version),
+        None => bail!(
+            "version {} has no pre-release to bump, use --channel to start one",
+            current
+        ),
+    }
+}
+
+fn release_version(
+    version: Version,
+    pre: Option<PreRelease>,
+    build: Option<BuildMetadata>,
+    allow_released: bool,
+) -> Result<Version> {
+    if pre.is_some() {
+        bail!("a pre-release cannot be given when releasing a version");
+    }
+
+    match VersionBumper::new(version.clone())
+        .maybe_build(build)
+        .release()
+    {
+        Some(version) => Ok(version),
+        None if allow_released => Ok(version),
+        None => bail!("version {} is already a release", version),
+    }
+}
+
+/// Bumps `version` for the most significant change among the Conventional Commits made in `repo`
+/// since its last version tag.
+fn auto_version(
+    version: Version,
+    repo: &Path,
+    pre: Option<PreRelease>,
+    build: Option<BuildMetadata>,
+) -> Result<Version> {
+    auto_bump(version, repo, |version, change| {
+        let bumper = prepare_version(|| Ok(version.clone()), pre, build)?;
+        Ok(match change {
+            Change::Breaking => bumper.bump_breaking(),
+            Change::Feature => bumper.bump_feature(),
+            _ => bumper.bump_fix(),
+        })
+    })
+}
+
+/// Bumps a PEP 440 `version` for the most significant change among the Conventional Commits made
+/// in `repo` since its last version tag.
+fn pep440_auto_version(
+    version: Pep440Version,
+    repo: &Path,
+    pre: Option<PreRelease>,
+    build: Option<BuildMetadata>,
+) -> Result<Pep440Version> {
+    pep440_parts(pre, build)?;
+
+    auto_bump(version, repo, |version, change| {
+        Ok(match change {
+            Change::Breaking => version.bump_breaking(),
+            Change::Feature => version.bump_feature(),
+            _ => version.bump_fix(),
+        })
+    })
+}
+
+/// Finds the most significant change among the Conventional Commits made in `repo` since its last
+/// version tag, and bumps `version` for it with `bump`, which is only given a change warranting a
+/// new version.
+fn auto_bump<V, F>(version: V, repo: &Path, bump: F) -> Result<V>
+where
+    V: fmt::Display,
+    F: FnOnce(&V, Change) -> Result<V>,
+{
+    let repo = Repository::open(repo)?;
+    let tag = repo.last_version_tag()?;
+    let since = match &tag {
+        Some(tag) => format!("tag {}", tag.name),
+        None => "the first commit".to_string(),
+    };
+
+    let mut change = Change::None;
+    for commit in repo.commits_since(tag.as_ref().map(|tag| tag.name.as_str()))? {
+        let commit_change = Change::of(&commit.message);
+        info!(
+            "commit {} is a {}: {}",
+            commit.id,
+            commit_change,
+            commit.summary()
+        );
+        change = change.max(commit_change);
+    }
+
+    let next = match change {
+        Change::None => bail!("no commits since {} warrant a new version", since),
+        change => bump(&version, change)?,
+    };
+    info!(
+        "bumping {} to {} for a {} since {}",
+        version, next, change, since
+    );
+
+    Ok(next)
+}
+
+/// Checks that a new version can be recorded in git before any file is written, returning the
+/// repository containing `path` to record it in.
+///
+/// Recording is refused if any tracked file in the working tree has uncommitted changes, as they
+/// would be unrelated to the new version.
+fn prepare_record(record: &GitRecord, path: &Path) -> Result<Option<Repository>> {
+    if let GitRecord::None = record {
+        return Ok(None);
+    }
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let repo = Repository::open(dir)?;
+    let dirty = repo.dirty_paths()?;
+    if !dirty.is_empty() {
+        bail!(
+            "working tree has uncommitted changes, refusing to commit the new version: {}",
+            dirty.join(", ")
+        );
+    }
+
+    Ok(Some(repo))
+}
+
+/// Commits the files at `paths` and tags the commit, as requested by `record`.
+fn record_version<V: fmt::Display>(
+    repo: Option<Repository>,
+    record: &GitRecord,
+    paths: &[PathBuf],
+    version: &V,
+) -> Result<()> {
+    let repo = match repo {
+        Some(repo) => repo,
+        None => return Ok(()),
+    };
+    let render = |template: &str| template.replace("{version}", &version.to_string());
+
+    match record {
+        GitRecord::None => {}
+        GitRecord::Commit { message } => {
+            info!("committing new version; version={}", version);
+            repo.commit(paths, &render(message))?;
+        }
+        GitRecord::Tag { message, tag } => {
+            let message = render(message);
+            let tag = render(tag);
+            info!(
+                "committing and tagging new version; version={}, tag={}",
+                version, tag
+            );
+            repo.commit(paths, &message)?;
+            repo.tag(&tag, &message)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the change from the `previous` to the `next` version for a dry run.
+fn announce<V: fmt::Display>(writer: &mut dyn Write, previous: &V, next: &V) -> Result<()> {
+    writeln!(writer, "{} -> {}", previous, next)?;
+
+    Ok(())
+}
+
+/// Writes a unified diff of replacing the contents of the file at `path` with `contents` for a
+/// dry run.
+fn preview(writer: &mut dyn Write, path: &Path, contents: &str) -> Result<()> {
+    let current = fs::read_to_string(path).map_err(|cause| VersioError::io(path, cause))?;
+    write!(writer, "{}", diff::unified(path, &current, contents))?;
+
+    Ok(())
+}
+
 fn set_version(initial_version: Version, set: SetVersion) -> Result<Version> {
     let v = match set {
-        SetVersion::Version(version) => version,
+        SetVersion::Version(version) => Version::parse(&version)?,
         SetVersion::Parts {
             major,
             minor,
@@ -72,6 +264,90 @@ fn set_version(initial_version: Version, set: SetVersion) -> Result<Version> {
     Ok(v)
 }
 
+/// Fails if a pre-release or build metadata is given for a PEP 440 version, which has neither.
+fn pep440_parts(pre: Option<PreRelease>, build: Option<BuildMetadata>) -> Result<()> {
+    if pre.is_some() {
+        bail!("--pre-release does not apply to PEP 440 versions, use `pre --channel` instead");
+    }
+    if build.is_some() {
+        bail!("--build-metadata does not apply to PEP 440 versions");
+    }
+
+    Ok(())
+}
+
+fn pep440_bump_pre(
+    version: Pep440Version,
+    channel: Option<PreRelease>,
+    build: Option<BuildMetadata>,
+) -> Result<Pep440Version> {
+    pep440_parts(None, build)?;
+    let phase = match channel {
+        Some(channel) => Some(channel.to_string().parse::<Phase>()?),
+        None => None,
+    };
+
+    match version.bump_pre(phase) {
+        Some(version) => Ok(version),
+        None => bail!(
+            "version {} has no pre-release to bump, use --channel to start one",
+            version
+        ),
+    }
+}
+
+fn pep440_release(
+    version: Pep440Version,
+    pre: Option<PreRelease>,
+    build: Option<BuildMetadata>,
+    allow_released: bool,
+) -> Result<Pep440Version> {
+    if pre.is_some() {
+        bail!("a pre-release cannot be given when releasing a version");
+    }
+    pep440_parts(None, build)?;
+
+    match version.release() {
+        Some(version) => Ok(version),
+        None if allow_released => Ok(version),
+        None => bail!("version {} is already a release", version),
+    }
+}
+
+fn set_pep440_version(initial_version: Pep440Version, set: SetVersion) -> Result<Pep440Version> {
+    let (major, minor, patch, pre, build) = match set {
+        SetVersion::Version(version) => return Ok(Pep440Version::parse(&version)?),
+        SetVersion::Parts {
+            major,
+            minor,
+            patch,
+            pre,
+            build,
+        } => (major, minor, patch, pre, build),
+    };
+
+    let mut version = initial_version;
+    for (index, part) in [major, minor, patch].iter().enumerate() {
+        if let Some(part) = part {
+            version.set_part(index, *part);
+        }
+    }
+    match pre {
+        SetPreRelease::Some(_) => {
+            bail!("--pre-release does not apply to PEP 440 versions, use `pre --channel` instead")
+        }
+        SetPreRelease::Clear => version.clear_pre(),
+        SetPreRelease::None => {}
+    }
+    match build {
+        SetBuildMetadata::Some(_) => bail!("--build-metadata does not apply to PEP 440 versions"),
+        SetBuildMetadata::Clear => version.clear_local(),
+        SetBuildMetadata::None => {}
+    }
+
+    Ok(version)
+}
+
 #[cfg(test)]
 pub(crate) mod test_helpers {
     use std::io;
@@ -2,97 +2,470 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::cargo::Manifest;
-use crate::cli::{bump, BumpCargoArgs, BumpCargoSetArgs, Output};
+use crate::cargo::{self, Manifest, Member, Workspace};
+use crate::cli::{
+    bump, report, BumpCargoArgs, BumpCargoAutoArgs, BumpCargoPreArgs, BumpCargoReleaseArgs,
+    BumpCargoSetArgs, Format, GitRecord, Output, SelectMembers,
+};
+use crate::error;
 use crate::io;
+use crate::version::{Version, VersionBumper};
 use crate::Result;
-use std::fs::File;
+use failure::bail;
+use log::info;
+use std::path::{Path, PathBuf};
 
 pub(crate) mod major {
-    use crate::{cli::BumpCargoArgs, Result};
+    use crate::cli::{BumpCargoArgs, Format};
+    use crate::Result;
 
-    pub(crate) fn run(args: BumpCargoArgs) -> Result<()> {
-        super::run_major(args)
+    pub(crate) fn run(args: BumpCargoArgs, format: &Format) -> Result<()> {
+        super::run_major(args, format)
     }
 }
 
 pub(crate) mod minor {
-    use crate::{cli::BumpCargoArgs, Result};
+    use crate::cli::{BumpCargoArgs, Format};
+    use crate::Result;
 
-    pub(crate) fn run(args: BumpCargoArgs) -> Result<()> {
-        super::run_minor(args)
+    pub(crate) fn run(args: BumpCargoArgs, format: &Format) -> Result<()> {
+        super::run_minor(args, format)
     }
 }
 
 pub(crate) mod patch {
-    use crate::{cli::BumpCargoArgs, Result};
+    use crate::cli::{BumpCargoArgs, Format};
+    use crate::Result;
 
-    pub(crate) fn run(args: BumpCargoArgs) -> Result<()> {
-        super::run_patch(args)
+    pub(crate) fn run(args: BumpCargoArgs, format: &Format) -> Result<()> {
+        super::run_patch(args, format)
+    }
+}
+
+pub(crate) mod breaking {
+    use crate::cli::{BumpCargoArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpCargoArgs, format: &Format) -> Result<()> {
+        super::run_breaking(args, format)
+    }
+}
+
+pub(crate) mod feature {
+    use crate::cli::{BumpCargoArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpCargoArgs, format: &Format) -> Result<()> {
+        super::run_feature(args, format)
+    }
+}
+
+pub(crate) mod fix {
+    use crate::cli::{BumpCargoArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpCargoArgs, format: &Format) -> Result<()> {
+        super::run_fix(args, format)
+    }
+}
+
+pub(crate) mod pre {
+    use crate::cli::{BumpCargoPreArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpCargoPreArgs, format: &Format) -> Result<()> {
+        super::run_pre(args, format)
+    }
+}
+
+pub(crate) mod release {
+    use crate::cli::{BumpCargoReleaseArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpCargoReleaseArgs, format: &Format) -> Result<()> {
+        super::run_release(args, format)
+    }
+}
+
+pub(crate) mod auto {
+    use crate::cli::{BumpCargoAutoArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpCargoAutoArgs, format: &Format) -> Result<()> {
+        super::run_auto(args, format)
     }
 }
 
 pub(crate) mod set {
-    use crate::{cli::BumpCargoSetArgs, Result};
+    use crate::cli::{BumpCargoSetArgs, Format};
+    use crate::Result;
 
-    pub(crate) fn run(args: BumpCargoSetArgs) -> Result<()> {
-        super::run_set(args)
+    pub(crate) fn run(args: BumpCargoSetArgs, format: &Format) -> Result<()> {
+        super::run_set(args, format)
     }
 }
 
-fn run_major(mut args: BumpCargoArgs) -> Result<()> {
-    let mut manifest = io::read_manifest(&mut io::bufreader(&args.input)?)?;
-    let version = bump::prepare_version(|| manifest.version(), args.pre, args.build)?.bump_major();
-    manifest.set_version(&version);
+fn run_major(args: BumpCargoArgs, format: &Format) -> Result<()> {
+    run_bump(args, format, "major", VersionBumper::bump_major)
+}
+
+fn run_minor(args: BumpCargoArgs, format: &Format) -> Result<()> {
+    run_bump(args, format, "minor", VersionBumper::bump_minor)
+}
 
-    write_to_dest(&mut args.output, &manifest)
+fn run_patch(args: BumpCargoArgs, format: &Format) -> Result<()> {
+    run_bump(args, format, "patch", VersionBumper::bump_patch)
 }
 
-fn run_minor(mut args: BumpCargoArgs) -> Result<()> {
-    let mut manifest = io::read_manifest(&mut io::bufreader(&args.input)?)?;
-    let version = bump::prepare_version(|| manifest.version(), args.pre, args.build)?.bump_minor();
-    manifest.set_version(&version);
+fn run_breaking(args: BumpCargoArgs, format: &Format) -> Result<()> {
+    run_bump(args, format, "breaking", VersionBumper::bump_breaking)
+}
+
+fn run_feature(args: BumpCargoArgs, format: &Format) -> Result<()> {
+    run_bump(args, format, "feature", VersionBumper::bump_feature)
+}
 
-    write_to_dest(&mut args.output, &manifest)
+fn run_fix(args: BumpCargoArgs, format: &Format) -> Result<()> {
+    run_bump(args, format, "fix", VersionBumper::bump_fix)
 }
 
-fn run_patch(mut args: BumpCargoArgs) -> Result<()> {
-    let mut manifest = io::read_manifest(&mut io::bufreader(&args.input)?)?;
-    let version = bump::prepare_version(|| manifest.version(), args.pre, args.build)?.bump_patch();
-    manifest.set_version(&version);
+/// Bumps the manifests by `kind`, with `semver` bumping the version once any pre-release and build
+/// metadata are applied to it.
+fn run_bump<S>(args: BumpCargoArgs, format: &Format, kind: &str, semver: S) -> Result<()>
+where
+    S: FnOnce(VersionBumper) -> Version,
+{
+    let BumpCargoArgs {
+        pre,
+        build,
+        input,
+        mut output,
+        git,
+        members,
+    } = args;
 
-    write_to_dest(&mut args.output, &manifest)
+    update_manifests(
+        &input,
+        &mut output,
+        &members,
+        &git,
+        format,
+        kind,
+        |version| {
+            Ok(semver(bump::prepare_version(
+                || Ok(version.clone()),
+                pre,
+                build,
+            )?))
+        },
+    )
 }
 
-fn run_set(mut args: BumpCargoSetArgs) -> Result<()> {
-    let mut manifest = io::read_manifest(&mut io::bufreader(&args.input)?)?;
-    let version = bump::set_version(manifest.version()?, args.set)?;
-    manifest.set_version(&version);
+fn run_pre(args: BumpCargoPreArgs, format: &Format) -> Result<()> {
+    let BumpCargoPreArgs {
+        channel,
+        build,
+        input,
+        mut output,
+        git,
+        members,
+    } = args;
 
-    write_to_dest(&mut args.output, &manifest)
+    update_manifests(
+        &input,
+        &mut output,
+        &members,
+        &git,
+        format,
+        "pre",
+        |version| bump::bump_pre(version, channel, build),
+    )
+}
+
+fn run_release(args: BumpCargoReleaseArgs, format: &Format) -> Result<()> {
+    let BumpCargoReleaseArgs {
+        pre,
+        build,
+        input,
+        mut output,
+        git,
+        members,
+        allow_released,
+    } = args;
+
+    update_manifests(
+        &input,
+        &mut output,
+        &members,
+        &git,
+        format,
+        "release",
+        |version| bump::release_version(version, pre, build, allow_released),
+    )
+}
+
+fn run_auto(args: BumpCargoAutoArgs, format: &Format) -> Result<()> {
+    let BumpCargoAutoArgs {
+        pre,
+        build,
+        input,
+        mut output,
+        git,
+        members,
+        repo,
+    } = args;
+
+    update_manifests(
+        &input,
+        &mut output,
+        &members,
+        &git,
+        format,
+        "auto",
+        |version| bump::auto_version(version, &repo, pre, build),
+    )
+}
+
+fn run_set(args: BumpCargoSetArgs, format: &Format) -> Result<()> {
+    let BumpCargoSetArgs {
+        input,
+        mut output,
+        git,
+        members,
+        set,
+    } = args;
+
+    update_manifests(
+        &input,
+        &mut output,
+        &members,
+        &git,
+        format,
+        "set",
+        |version| bump::set_version(version, set),
+    )
+}
+
+/// Computes the next version with `next_version` and writes it into either the single manifest
+/// at `input` or, when members are selected, into every selected member of the workspace rooted
+/// at `input`.
+///
+/// In a workspace, any path dependency requirements on the selected members are also updated to
+/// the new version, wherever they appear in the workspace. A version inherited with
+/// `version.workspace = true` is updated under `[workspace.package]` in the workspace root.
+///
+/// With the JSON or export format, the bump is reported as a bump of `kind` instead of previewing
+/// the changes of a dry run.
+fn update_manifests<F>(
+    input: &Path,
+    output: &mut Output,
+    members: &SelectMembers,
+    git: &GitRecord,
+    format: &Format,
+    kind: &str,
+    next_version: F,
+) -> Result<()>
+where
+    F: FnOnce(Version) -> Result<Version>,
+{
+    let repo = bump::prepare_record(git, input)?;
+    match (format, output) {
+        (Format::Text, output) => {
+            let (_, version, paths) = write_manifests(input, output, members, next_version)?;
+
+            bump::record_version(repo, git, &paths, &version)
+        }
+        (_, Output::Stdout(_)) => Err(report::stdout_conflict()),
+        (_, Output::DryRun(path, writer)) => {
+            let mut preview = Output::DryRun(path.clone(), Box::new(std::io::sink()));
+            let (previous, version, paths) =
+                write_manifests(input, &mut preview, members, next_version)?;
+
+            report::bumped(writer, format, kind, &previous, &version, &paths, true)
+        }
+        (_, output) => {
+            let (previous, version, paths) = write_manifests(input, output, members, next_version)?;
+            bump::record_version(repo, git, &paths, &version)?;
+
+            report::bumped(
+                &mut std::io::stdout(),
+                format,
+                kind,
+                &previous,
+                &version,
+                &paths,
+                false,
+            )
+        }
+    }
+}
+
+/// Writes the new version as described by `update_manifests`, returning it along with the
+/// previous version and the paths of the manifests which were changed.
+fn write_manifests<F>(
+    input: &Path,
+    output: &mut Output,
+    members: &SelectMembers,
+    next_version: F,
+) -> Result<(Version, Version, Vec<PathBuf>)>
+where
+    F: FnOnce(Version) -> Result<Version>,
+{
+    let mut workspace = match members {
+        SelectMembers::None => {
+            let mut manifest = io::read_manifest_file(input)?;
+            if manifest.inherits_version() {
+                return update_inherited_manifest(input, &manifest, output, next_version);
+            }
+            let previous = manifest
+                .version()
+                .map_err(|err| error::with_path(err, input))?;
+            let version = next(output, previous.clone(), next_version)?;
+            manifest.set_version(&version);
+            write_to_dest(output, &manifest)?;
+
+            return Ok((previous, version, vec![input.to_path_buf()]));
+        }
+        _ => Workspace::load(input)?,
+    };
+
+    let (names, previous, version, inherited) = {
+        let selected = match members {
+            SelectMembers::Some(names) => workspace.select(names)?,
+            _ => workspace.members(),
+        };
+        let mut versions = Vec::with_capacity(selected.len());
+        for member in &selected {
+            versions.push((member.path.as_path(), workspace.version_of(member)?));
+        }
+        let previous = lock_step_version(&versions)?;
+        let version = next(output, previous.clone(), next_version)?;
+        let names: Vec<String> = selected
+            .iter()
+            .filter_map(|m| m.manifest.name())
+            .map(String::from)
+            .collect();
+        let inherited = selected.iter().any(|m| m.manifest.inherits_version());
+
+        (names, previous, version, inherited)
+    };
+
+    if inherited {
+        report_inherited(&workspace.root().path);
+    }
+    let paths = write_members(output, workspace.set_version(&names, &version))?;
+
+    Ok((previous, version, paths))
+}
+
+/// Writes each of the changed `members` as `output` says, returning their paths.
+fn write_members(output: &mut Output, members: Vec<&Member>) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::with_capacity(members.len());
+    for member in members {
+        match output {
+            Output::Stdout(writer) => io::write_manifest(writer, &member.manifest)?,
+            Output::DryRun(_, writer) => {
+                bump::preview(writer, &member.path, &member.manifest.to_string())?
+            }
+            Output::File(_) => write_to_path(&member.path, &member.manifest)?,
+        }
+        paths.push(member.path.clone());
+    }
+
+    Ok(paths)
+}
+
+/// Updates `[workspace.package]` in the workspace root for a manifest at `input` which inherits
+/// its version from the workspace, along with the requirements on every member inheriting it.
+fn update_inherited_manifest<F>(
+    input: &Path,
+    manifest: &Manifest,
+    output: &mut Output,
+    next_version: F,
+) -> Result<(Version, Version, Vec<PathBuf>)>
+where
+    F: FnOnce(Version) -> Result<Version>,
+{
+    let root_path = cargo::find_workspace_root(input, manifest)?;
+    let mut workspace = Workspace::load(&root_path)?;
+    let previous = workspace
+        .root()
+        .manifest
+        .workspace_version()
+        .map_err(|err| error::with_path(err, &root_path))?;
+    let version = next(output, previous.clone(), next_version)?;
+    let names: Vec<String> = manifest.name().map(String::from).into_iter().collect();
+    workspace.select(&names)?;
+
+    report_inherited(&root_path);
+    let paths = write_members(output, workspace.set_version(&names, &version))?;
+
+    Ok((previous, version, paths))
+}
+
+/// Reports that the version was changed in the workspace root rather than the given manifest.
+fn report_inherited(root: &Path) {
+    info!(
+        "version is inherited from the workspace, updating [workspace.package] in {}",
+        root.display()
+    );
+}
+
+/// Computes the next version from `previous`, announcing the change when previewing a dry run.
+fn next<F>(output: &mut Output, previous: Version, next_version: F) -> Result<Version>
+where
+    F: FnOnce(Version) -> Result<Version>,
+{
+    let version = next_version(previous.clone())?;
+    if let Output::DryRun(_, writer) = output {
+        bump::announce(writer, &previous, &version)?;
+    }
+
+    Ok(version)
+}
+
+/// Returns the version shared by all manifests, failing if they do not agree.
+fn lock_step_version(versions: &[(&Path, Version)]) -> Result<Version> {
+    match versions.first() {
+        Some((_, first)) if versions.iter().all(|(_, version)| version == first) => {
+            Ok(first.clone())
+        }
+        Some(_) => {
+            let found: Vec<_> = versions
+                .iter()
+                .map(|(path, version)| format!("{}={}", path.display(), version))
+                .collect();
+            bail!(
+                "workspace members have differing versions, cannot bump in lock-step: {}",
+                found.join(", ")
+            )
+        }
+        None => bail!("no workspace members were selected"),
+    }
 }
 
 fn write_to_dest(output: &mut Output, manifest: &Manifest) -> Result<()> {
     match output {
         Output::Stdout(writer) => io::write_manifest(writer, manifest),
-        Output::File(path) => {
-            // TODO: make more robust
-            let mut file = File::create(path)?;
-            io::write_manifest(&mut file, manifest)
-        }
+        Output::DryRun(path, writer) => bump::preview(writer, path, &manifest.to_string()),
+        Output::File(path) => write_to_path(path, manifest),
     }
 }
 
+fn write_to_path(path: &Path, manifest: &Manifest) -> Result<()> {
+    io::write_file(path, |file| io::write_manifest(file, manifest))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::cli::bump::test_helpers::{OutputReceiver, WriteableSender};
     use crate::cli::{
-        BuildMetadata, BumpCargoArgs, BumpCargoSetArgs, Output, PreRelease, SetBuildMetadata,
+        BuildMetadata, BumpCargoArgs, BumpCargoAutoArgs, BumpCargoPreArgs, BumpCargoReleaseArgs,
+        BumpCargoSetArgs, Format, GitRecord, Output, PreRelease, SelectMembers, SetBuildMetadata,
         SetPreRelease, SetVersion,
     };
-    use crate::version::Version;
     use std::fs;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
     use std::str::FromStr;
     use std::sync::mpsc;
     use tempfile::NamedTempFile;
@@ -104,7 +477,7 @@ mod tests {
             #[test]
             fn $name() {
                 let (args, tempfile) = new_args($input, None, None);
-                run(args).unwrap();
+                run(args, &Format::Text).unwrap();
                 let manifest = crate::cargo::Manifest::from_str(
                     &std::fs::read_to_string(tempfile.path()).expect("file should be openable"),
                 )
@@ -123,7 +496,7 @@ mod tests {
             #[test]
             fn $name() {
                 let (args, output, tempfile) = new_args_stdout($input, None, None);
-                run(args).unwrap();
+                run(args, &Format::Text).unwrap();
                 let manifest = crate::cargo::Manifest::from_str(&output.into_string())
                     .expect("manifest should be parseable");
                 let version = manifest.version().expect("version should be readable");
@@ -160,7 +533,7 @@ mod tests {
                     $no_pre_release,
                     $no_build_metadata,
                 );
-                run(args).unwrap();
+                run(args, &Format::Text).unwrap();
                 let manifest = crate::cargo::Manifest::from_str(
                     &std::fs::read_to_string(tempfile.path()).expect("file should be openable"),
                 )
@@ -191,7 +564,7 @@ mod tests {
                     $no_pre_release,
                     $no_build_metadata,
                 );
-                run(args).unwrap();
+                run(args, &Format::Text).unwrap();
                 let manifest = crate::cargo::Manifest::from_str(&output.into_string())
                     .expect("manifest should be parseable");
                 let version = manifest.version().expect("version should be readable");
@@ -209,6 +582,44 @@ mod tests {
         };
     }
 
+    macro_rules! test_pre {
+        (
+            $name:ident, $input:expr, $channel:expr, $build:expr, $output_version:expr
+        ) => {
+            #[test]
+            fn $name() {
+                let (args, tempfile) = new_pre_args($input, $channel, $build);
+                run(args, &Format::Text).unwrap();
+                let manifest = crate::cargo::Manifest::from_str(
+                    &std::fs::read_to_string(tempfile.path()).expect("file should be openable"),
+                )
+                .expect("manifest should be parseable");
+                let version = manifest.version().expect("version should be readable");
+
+                assert_eq!(version.to_string(), $output_version.to_string());
+            }
+        };
+    }
+
+    macro_rules! test_release {
+        (
+            $name:ident, $input:expr, $build:expr, $allow_released:expr, $output_version:expr
+        ) => {
+            #[test]
+            fn $name() {
+                let (args, tempfile) = new_release_args($input, $build, $allow_released);
+                run(args, &Format::Text).unwrap();
+                let manifest = crate::cargo::Manifest::from_str(
+                    &std::fs::read_to_string(tempfile.path()).expect("file should be openable"),
+                )
+                .expect("manifest should be parseable");
+                let version = manifest.version().expect("version should be readable");
+
+                assert_eq!(version.to_string(), $output_version.to_string());
+            }
+        };
+    }
+
     mod major {
         use super::super::major::run;
         use super::*;
@@ -236,6 +647,103 @@ mod tests {
         test_stdout!(stdout_round_trip, "0.0.1", "0.0.2");
     }
 
+    mod breaking {
+        use super::super::breaking::run;
+        use super::*;
+
+        test!(stable, "1.2.3", "2.0.0");
+        test!(pre_major, "0.2.3", "0.3.0");
+        test!(pre_minor, "0.0.3", "0.0.4");
+    }
+
+    mod feature {
+        use super::super::feature::run;
+        use super::*;
+
+        test!(stable, "1.2.3", "1.3.0");
+        test!(pre_major, "0.2.3", "0.2.4");
+        test!(pre_minor, "0.0.3", "0.0.4");
+    }
+
+    mod fix {
+        use super::super::fix::run;
+        use super::*;
+
+        test!(stable, "1.2.3", "1.2.4");
+        test!(pre_major, "0.2.3", "0.2.4");
+        test!(pre_minor, "0.0.3", "0.0.4");
+    }
+
+    mod pre {
+        use super::super::pre::run;
+        use super::*;
+
+        test_pre!(round_trip, "1.0.0-rc.1", None, None, "1.0.0-rc.2");
+        test_pre!(channel, "1.0.0-alpha.2", Some("beta"), None, "1.0.0-beta.1");
+        test_pre!(
+            build,
+            "1.0.0-rc.1",
+            None,
+            Some("build8"),
+            "1.0.0-rc.2+build8"
+        );
+
+        #[test]
+        fn no_pre_release() {
+            let (args, _tempfile) = new_pre_args("1.0.0", None, None);
+
+            match run(args, &Format::Text) {
+                Err(err) => assert!(err.to_string().contains("no pre-release to bump")),
+                Ok(_) => panic!("should not bump a release"),
+            }
+        }
+    }
+
+    mod release {
+        use super::super::release::run;
+        use super::*;
+
+        test_release!(pre_release, "1.2.0-rc.2", None, false, "1.2.0");
+        test_release!(pre_release_and_build, "1.2.0-rc.2+b7", None, false, "1.2.0");
+        test_release!(build, "1.2.0-rc.2", Some("b8"), false, "1.2.0+b8");
+        test_release!(allow_released, "1.2.0", None, true, "1.2.0");
+
+        #[test]
+        fn already_released() {
+            let (args, _tempfile) = new_release_args("1.2.0", None, false);
+
+            match run(args, &Format::Text) {
+                Err(err) => assert!(err.to_string().contains("already a release")),
+                Ok(_) => panic!("should not release a release"),
+            }
+        }
+    }
+
+    mod auto {
+        use super::super::auto::run;
+        use super::*;
+        use crate::git::test_helpers::{commit, git, init};
+
+        #[test]
+        fn breaking() {
+            let repo = tempfile::tempdir().expect("tempdir should be created");
+            init(repo.path());
+            commit(repo.path(), "a", "feat: first");
+            git(repo.path(), &["tag", "v0.2.1"]);
+            commit(repo.path(), "b", "fix: second");
+            commit(repo.path(), "c", "feat!: third");
+            let (args, tempfile) = new_auto_args("0.2.1", repo.path());
+            run(args, &Format::Text).unwrap();
+
+            let manifest = crate::cargo::Manifest::from_str(
+                &fs::read_to_string(tempfile.path()).expect("file should be openable"),
+            )
+            .expect("manifest should be parseable");
+
+            assert_eq!(manifest.version().unwrap().to_string(), "0.3.0");
+        }
+    }
+
     mod set {
         use super::super::set::run;
         use super::*;
@@ -452,6 +960,301 @@ mod tests {
         );
     }
 
+    mod workspace {
+        use super::*;
+        use crate::cargo::test_helpers::write;
+        use std::path::Path;
+        use tempfile::TempDir;
+
+        fn read_version(dir: &Path, path: &str) -> String {
+            crate::cargo::Manifest::from_str(
+                &fs::read_to_string(dir.join(path)).expect("file should be openable"),
+            )
+            .expect("manifest should be parseable")
+            .version()
+            .expect("version should be readable")
+            .to_string()
+        }
+
+        fn fixture(one: &str, two: &str) -> TempDir {
+            crate::cargo::test_helpers::fixture(
+                "[workspace]\nmembers = [\"crates/*\"]\n",
+                &[
+                    (
+                        "crates/one/Cargo.toml",
+                        &format!(
+                            "[package]\nname = \"one\" # first\nversion = \"{}\"\n\n\
+                             [dependencies]\n",
+                            one
+                        ),
+                    ),
+                    (
+                        "crates/two/Cargo.toml",
+                        &format!("[package]\nname = \"two\"\nversion = \"{}\"\n", two),
+                    ),
+                ],
+            )
+        }
+
+        fn args(dir: &Path, members: SelectMembers) -> BumpCargoArgs {
+            let input = dir.join("Cargo.toml");
+
+            BumpCargoArgs {
+                pre: None,
+                build: None,
+                input: input.clone(),
+                output: Output::File(input),
+                members,
+                git: GitRecord::None,
+            }
+        }
+
+        #[test]
+        fn all() {
+            let dir = fixture("1.2.3", "1.2.3");
+            super::super::minor::run(args(dir.path(), SelectMembers::All), &Format::Text).unwrap();
+
+            assert_eq!(read_version(dir.path(), "crates/one/Cargo.toml"), "1.3.0");
+            assert_eq!(read_version(dir.path(), "crates/two/Cargo.toml"), "1.3.0");
+            assert_eq!(
+                fs::read_to_string(dir.path().join("crates/one/Cargo.toml")).unwrap(),
+                "[package]\nname = \"one\" # first\nversion = \"1.3.0\"\n\n[dependencies]\n"
+            );
+        }
+
+        #[test]
+        fn all_committed() {
+            use crate::git::test_helpers::{git, init, output};
+
+            let dir = fixture("1.2.3", "1.2.3");
+            init(dir.path());
+            git(dir.path(), &["add", "."]);
+            git(dir.path(), &["commit", "--quiet", "-m", "initial"]);
+            let mut args = args(dir.path(), SelectMembers::All);
+            args.git = GitRecord::Tag {
+                message: "Release {version}".to_string(),
+                tag: "v{version}".to_string(),
+            };
+            super::super::minor::run(args, &Format::Text).unwrap();
+
+            assert_eq!(
+                output(dir.path(), &["show", "--format=", "--name-only", "HEAD"]),
+                "crates/one/Cargo.toml\ncrates/two/Cargo.toml"
+            );
+            assert_eq!(output(dir.path(), &["describe"]), "v1.3.0");
+        }
+
+        #[test]
+        fn all_dry_run() {
+            let dir = fixture("1.2.3", "1.2.3");
+            let (sender, receiver) = mpsc::channel();
+            let mut args = args(dir.path(), SelectMembers::All);
+            args.output =
+                Output::DryRun(args.input.clone(), Box::new(WriteableSender::new(sender)));
+            super::super::minor::run(args, &Format::Text).unwrap();
+            let one = dir.path().join("crates/one/Cargo.toml");
+            let two = dir.path().join("crates/two/Cargo.toml");
+
+            assert_eq!(
+                OutputReceiver::new(receiver).into_string(),
+                format!(
+                    "1.2.3 -> 1.3.0\n\
+                     --- {one}\n+++ {one}\n@@ -1,5 +1,5 @@\n [package]\n name = \"one\" # first\n\
+                     -version = \"1.2.3\"\n+version = \"1.3.0\"\n \n [dependencies]\n\
+                     --- {two}\n+++ {two}\n@@ -1,3 +1,3 @@\n [package]\n name = \"two\"\n\
+                     -version = \"1.2.3\"\n+version = \"1.3.0\"\n",
+                    one = one.display(),
+                    two = two.display()
+                )
+            );
+            assert_eq!(read_version(dir.path(), "crates/one/Cargo.toml"), "1.2.3");
+            assert_eq!(read_version(dir.path(), "crates/two/Cargo.toml"), "1.2.3");
+        }
+
+        #[test]
+        fn selected() {
+            let dir = fixture("1.2.3", "0.1.0");
+            let members = SelectMembers::Some(vec!["two".to_string()]);
+            super::super::major::run(args(dir.path(), members), &Format::Text).unwrap();
+
+            assert_eq!(read_version(dir.path(), "crates/one/Cargo.toml"), "1.2.3");
+            assert_eq!(read_version(dir.path(), "crates/two/Cargo.toml"), "1.0.0");
+        }
+
+        #[test]
+        fn set() {
+            let dir = fixture("1.2.3", "1.2.3");
+            let input = dir.path().join("Cargo.toml");
+            super::super::set::run(
+                BumpCargoSetArgs {
+                    input: input.clone(),
+                    output: Output::File(input),
+                    members: SelectMembers::All,
+                    set: SetVersion::Version("2.0.0-rc.1".to_string()),
+                    git: GitRecord::None,
+                },
+                &Format::Text,
+            )
+            .unwrap();
+
+            assert_eq!(
+                read_version(dir.path(), "crates/one/Cargo.toml"),
+                "2.0.0-rc.1"
+            );
+            assert_eq!(
+                read_version(dir.path(), "crates/two/Cargo.toml"),
+                "2.0.0-rc.1"
+            );
+        }
+
+        #[test]
+        fn dependents() {
+            let dir = fixture("1.2.3", "0.1.0");
+            write(
+                dir.path(),
+                "crates/two/Cargo.toml",
+                concat!(
+                    "[package]\nname = \"two\"\nversion = \"0.1.0\"\n\n",
+                    "[dependencies]\none = { path = \"../one\", version = \"=1.2.3\" }\n",
+                ),
+            );
+            let members = SelectMembers::Some(vec!["one".to_string()]);
+            super::super::minor::run(args(dir.path(), members), &Format::Text).unwrap();
+
+            assert_eq!(read_version(dir.path(), "crates/one/Cargo.toml"), "1.3.0");
+            assert_eq!(
+                fs::read_to_string(dir.path().join("crates/two/Cargo.toml")).unwrap(),
+                concat!(
+                    "[package]\nname = \"two\"\nversion = \"0.1.0\"\n\n",
+                    "[dependencies]\none = { path = \"../one\", version = \"=1.3.0\" }\n",
+                )
+            );
+            assert_eq!(
+                fs::read_to_string(dir.path().join("Cargo.toml")).unwrap(),
+                "[workspace]\nmembers = [\"crates/*\"]\n"
+            );
+        }
+
+        #[test]
+        fn inherited() {
+            let dir = fixture("1.2.3", "1.2.3");
+            write(
+                dir.path(),
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nversion = \"0.9.0\"\n",
+            );
+            write(
+                dir.path(),
+                "crates/one/Cargo.toml",
+                "[package]\nname = \"one\"\nversion.workspace = true\n",
+            );
+            let input = dir.path().join("crates/one/Cargo.toml");
+            super::super::minor::run(
+                BumpCargoArgs {
+                    pre: None,
+                    build: None,
+                    input: input.clone(),
+                    output: Output::File(input),
+                    members: SelectMembers::None,
+                    git: GitRecord::None,
+                },
+                &Format::Text,
+            )
+            .unwrap();
+
+            assert_eq!(
+                fs::read_to_string(dir.path().join("crates/one/Cargo.toml")).unwrap(),
+                "[package]\nname = \"one\"\nversion.workspace = true\n"
+            );
+            assert_eq!(
+                fs::read_to_string(dir.path().join("Cargo.toml")).unwrap(),
+                "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nversion = \"0.10.0\"\n"
+            );
+        }
+
+        #[test]
+        fn inherited_dependents() {
+            let dir = fixture("1.2.3", "1.2.3");
+            write(
+                dir.path(),
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nversion = \"1.2.3\"\n",
+            );
+            write(
+                dir.path(),
+                "crates/one/Cargo.toml",
+                "[package]\nname = \"one\"\nversion.workspace = true\n",
+            );
+            write(
+                dir.path(),
+                "crates/two/Cargo.toml",
+                concat!(
+                    "[package]\nname = \"two\"\nversion.workspace = true\n\n",
+                    "[dependencies]\none = { path = \"../one\", version = \"=1.2.3\" }\n",
+                ),
+            );
+            let input = dir.path().join("crates/two/Cargo.toml");
+            super::super::minor::run(
+                BumpCargoArgs {
+                    pre: None,
+                    build: None,
+                    input: input.clone(),
+                    output: Output::File(input),
+                    members: SelectMembers::None,
+                    git: GitRecord::None,
+                },
+                &Format::Text,
+            )
+            .unwrap();
+
+            assert_eq!(
+                fs::read_to_string(dir.path().join("crates/two/Cargo.toml")).unwrap(),
+                concat!(
+                    "[package]\nname = \"two\"\nversion.workspace = true\n\n",
+                    "[dependencies]\none = { path = \"../one\", version = \"=1.3.0\" }\n",
+                )
+            );
+            assert_eq!(
+                fs::read_to_string(dir.path().join("Cargo.toml")).unwrap(),
+                "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nversion = \"1.3.0\"\n"
+            );
+        }
+
+        #[test]
+        fn inherited_workspace() {
+            let dir = fixture("1.2.3", "1.2.3");
+            write(
+                dir.path(),
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nversion = \"1.2.3\"\n",
+            );
+            write(
+                dir.path(),
+                "crates/one/Cargo.toml",
+                "[package]\nname = \"one\"\nversion.workspace = true\n",
+            );
+            super::super::patch::run(args(dir.path(), SelectMembers::All), &Format::Text).unwrap();
+
+            assert_eq!(
+                fs::read_to_string(dir.path().join("Cargo.toml")).unwrap(),
+                "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nversion = \"1.2.4\"\n"
+            );
+            assert_eq!(read_version(dir.path(), "crates/two/Cargo.toml"), "1.2.4");
+        }
+
+        #[test]
+        fn differing_versions() {
+            let dir = fixture("1.2.3", "0.1.0");
+            match super::super::patch::run(args(dir.path(), SelectMembers::All), &Format::Text) {
+                Err(err) => assert!(err.to_string().contains("differing versions")),
+                Ok(_) => panic!("should not bump differing versions"),
+            }
+
+            assert_eq!(read_version(dir.path(), "crates/one/Cargo.toml"), "1.2.3");
+            assert_eq!(read_version(dir.path(), "crates/two/Cargo.toml"), "0.1.0");
+        }
+    }
+
     fn new_args<S: AsRef<str>>(
         input: S,
         pre: Option<&str>,
@@ -481,6 +1284,63 @@ mod tests {
             build,
             input,
             output,
+            members: SelectMembers::None,
+            git: GitRecord::None,
+        };
+
+        (args, tempfile)
+    }
+
+    fn new_pre_args<S: AsRef<str>>(
+        input: S,
+        channel: Option<&str>,
+        build: Option<&str>,
+    ) -> (BumpCargoPreArgs, NamedTempFile) {
+        let (args, tempfile) = new_args(input, channel, build);
+
+        let args = BumpCargoPreArgs {
+            channel: args.pre,
+            build: args.build,
+            input: args.input,
+            output: args.output,
+            members: args.members,
+            git: args.git,
+        };
+
+        (args, tempfile)
+    }
+
+    fn new_release_args<S: AsRef<str>>(
+        input: S,
+        build: Option<&str>,
+        allow_released: bool,
+    ) -> (BumpCargoReleaseArgs, NamedTempFile) {
+        let (args, tempfile) = new_args(input, None, build);
+
+        let args = BumpCargoReleaseArgs {
+            pre: args.pre,
+            build: args.build,
+            input: args.input,
+            output: args.output,
+            members: args.members,
+            allow_released,
+            git: args.git,
+        };
+
+        (args, tempfile)
+    }
+
+    fn new_auto_args<S: AsRef<str>>(input: S, repo: &Path) -> (BumpCargoAutoArgs, NamedTempFile) {
+        let (args, tempfile) = new_args(input, None, None);
+
+        let args = BumpCargoAutoArgs {
+            pre: args.pre,
+            build: args.build,
+            input: args.input,
+            output: args.output,
+            members: args.members,
+            repo: repo.to_path_buf(),
+            git: args.git,
         };
 
         (args, tempfile)
@@ -516,6 +1376,8 @@ mod tests {
             build,
             input,
             output,
+            members: SelectMembers::None,
+            git: GitRecord::None,
         };
         let output_receiver = OutputReceiver::new(receiver);
 
@@ -535,7 +1397,7 @@ mod tests {
         no_build_metadata: bool,
     ) -> (BumpCargoSetArgs, NamedTempFile) {
         let set = if let Some(version) = version {
-            SetVersion::Version(Version::from_str(version).expect("should be valid version"))
+            SetVersion::Version(version.to_string())
         } else {
             SetVersion::Parts {
                 major,
@@ -572,7 +1434,13 @@ mod tests {
         let input = PathBuf::from(tempfile.path());
         let output = Output::File(input.clone());
 
-        let args = BumpCargoSetArgs { input, output, set };
+        let args = BumpCargoSetArgs {
+            input,
+            output,
+            members: SelectMembers::None,
+            set,
+            git: GitRecord::None,
+        };
 
         (args, tempfile)
     }
@@ -590,7 +1458,7 @@ mod tests {
         no_build_metadata: bool,
     ) -> (BumpCargoSetArgs, OutputReceiver, NamedTempFile) {
         let set = if let Some(version) = version {
-            SetVersion::Version(Version::from_str(version).expect("should be valid version"))
+            SetVersion::Version(version.to_string())
         } else {
             SetVersion::Parts {
                 major,
@@ -628,7 +1496,13 @@ mod tests {
         let (sender, receiver) = mpsc::channel();
         let output = Output::Stdout(Box::new(WriteableSender::new(sender)));
 
-        let args = BumpCargoSetArgs { input, output, set };
+        let args = BumpCargoSetArgs {
+            input,
+            output,
+            members: SelectMembers::None,
+            set,
+            git: GitRecord::None,
+        };
         let output_receiver = OutputReceiver::new(receiver);
 
         (args, output_receiver, tempfile)
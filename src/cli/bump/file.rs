@@ -2,85 +2,361 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::cli::{bump, BumpFileArgs, BumpFileSetArgs, Output};
+use crate::cli::{
+    bump, report, BumpFileArgs, BumpFileAutoArgs, BumpFilePreArgs, BumpFileReleaseArgs,
+    BumpFileSetArgs, Format, GitRecord, Output,
+};
 use crate::io;
-use crate::version::Version;
+use crate::pep440::Pep440Version;
+use crate::version::{Scheme, SchemeVersion, Version, VersionBumper};
 use crate::Result;
-use std::fs::File;
+use std::path::Path;
 
 pub(crate) mod major {
-    use crate::{cli::BumpFileArgs, Result};
+    use crate::cli::{BumpFileArgs, Format};
+    use crate::Result;
 
-    pub(crate) fn run(args: BumpFileArgs) -> Result<()> {
-        super::run_major(args)
+    pub(crate) fn run(args: BumpFileArgs, format: &Format) -> Result<()> {
+        super::run_major(args, format)
     }
 }
 
 pub(crate) mod minor {
-    use crate::{cli::BumpFileArgs, Result};
+    use crate::cli::{BumpFileArgs, Format};
+    use crate::Result;
 
-    pub(crate) fn run(args: BumpFileArgs) -> Result<()> {
-        super::run_minor(args)
+    pub(crate) fn run(args: BumpFileArgs, format: &Format) -> Result<()> {
+        super::run_minor(args, format)
     }
 }
 
 pub(crate) mod patch {
-    use crate::{cli::BumpFileArgs, Result};
+    use crate::cli::{BumpFileArgs, Format};
+    use crate::Result;
 
-    pub(crate) fn run(args: BumpFileArgs) -> Result<()> {
-        super::run_patch(args)
+    pub(crate) fn run(args: BumpFileArgs, format: &Format) -> Result<()> {
+        super::run_patch(args, format)
+    }
+}
+
+pub(crate) mod breaking {
+    use crate::cli::{BumpFileArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpFileArgs, format: &Format) -> Result<()> {
+        super::run_breaking(args, format)
+    }
+}
+
+pub(crate) mod feature {
+    use crate::cli::{BumpFileArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpFileArgs, format: &Format) -> Result<()> {
+        super::run_feature(args, format)
+    }
+}
+
+pub(crate) mod fix {
+    use crate::cli::{BumpFileArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpFileArgs, format: &Format) -> Result<()> {
+        super::run_fix(args, format)
+    }
+}
+
+pub(crate) mod pre {
+    use crate::cli::{BumpFilePreArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpFilePreArgs, format: &Format) -> Result<()> {
+        super::run_pre(args, format)
+    }
+}
+
+pub(crate) mod release {
+    use crate::cli::{BumpFileReleaseArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpFileReleaseArgs, format: &Format) -> Result<()> {
+        super::run_release(args, format)
+    }
+}
+
+pub(crate) mod auto {
+    use crate::cli::{BumpFileAutoArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpFileAutoArgs, format: &Format) -> Result<()> {
+        super::run_auto(args, format)
     }
 }
 
 pub(crate) mod set {
-    use crate::{cli::BumpFileSetArgs, Result};
+    use crate::cli::{BumpFileSetArgs, Format};
+    use crate::Result;
 
-    pub(crate) fn run(args: BumpFileSetArgs) -> Result<()> {
-        super::run_set(args)
+    pub(crate) fn run(args: BumpFileSetArgs, format: &Format) -> Result<()> {
+        super::run_set(args, format)
     }
 }
 
-fn run_major(mut args: BumpFileArgs) -> Result<()> {
-    let version =
-        bump::prepare_version_from_reader(&mut io::bufreader(&args.input)?, args.pre, args.build)?
-            .bump_major();
+fn run_major(args: BumpFileArgs, format: &Format) -> Result<()> {
+    run_bump(
+        args,
+        format,
+        "major",
+        VersionBumper::bump_major,
+        Pep440Version::bump_major,
+    )
+}
+
+fn run_minor(args: BumpFileArgs, format: &Format) -> Result<()> {
+    run_bump(
+        args,
+        format,
+        "minor",
+        VersionBumper::bump_minor,
+        Pep440Version::bump_minor,
+    )
+}
 
-    write_to_dest(&mut args.output, &version)
+fn run_patch(args: BumpFileArgs, format: &Format) -> Result<()> {
+    run_bump(
+        args,
+        format,
+        "patch",
+        VersionBumper::bump_patch,
+        Pep440Version::bump_patch,
+    )
 }
 
-fn run_minor(mut args: BumpFileArgs) -> Result<()> {
-    let version =
-        bump::prepare_version_from_reader(&mut io::bufreader(&args.input)?, args.pre, args.build)?
-            .bump_minor();
+fn run_breaking(args: BumpFileArgs, format: &Format) -> Result<()> {
+    run_bump(
+        args,
+        format,
+        "breaking",
+        VersionBumper::bump_breaking,
+        Pep440Version::bump_breaking,
+    )
+}
 
-    write_to_dest(&mut args.output, &version)
+fn run_feature(args: BumpFileArgs, format: &Format) -> Result<()> {
+    run_bump(
+        args,
+        format,
+        "feature",
+        VersionBumper::bump_feature,
+        Pep440Version::bump_feature,
+    )
 }
 
-fn run_patch(mut args: BumpFileArgs) -> Result<()> {
-    let version =
-        bump::prepare_version_from_reader(&mut io::bufreader(&args.input)?, args.pre, args.build)?
-            .bump_patch();
+fn run_fix(args: BumpFileArgs, format: &Format) -> Result<()> {
+    run_bump(
+        args,
+        format,
+        "fix",
+        VersionBumper::bump_fix,
+        Pep440Version::bump_fix,
+    )
+}
 
-    write_to_dest(&mut args.output, &version)
+/// Bumps the version in the file by `kind`, with `semver` bumping a semantic version once any
+/// pre-release and build metadata are applied to it, and `pep440` bumping a PEP 440 version, which
+/// has neither.
+fn run_bump<S, P>(
+    mut args: BumpFileArgs,
+    format: &Format,
+    kind: &str,
+    semver: S,
+    pep440: P,
+) -> Result<()>
+where
+    S: FnOnce(VersionBumper) -> Version,
+    P: FnOnce(&Pep440Version) -> Pep440Version,
+{
+    let (pre, build) = (args.pre, args.build);
+    match args.scheme {
+        Scheme::Semver => update_file(
+            &args.input,
+            &mut args.output,
+            &args.git,
+            format,
+            kind,
+            |version: Version| {
+                Ok(semver(bump::prepare_version(
+                    || Ok(version.clone()),
+                    pre,
+                    build,
+                )?))
+            },
+        ),
+        Scheme::Pep440 => {
+            bump::pep440_parts(pre, build)?;
+            update_file(
+                &args.input,
+                &mut args.output,
+                &args.git,
+                format,
+                kind,
+                |version: Pep440Version| Ok(pep440(&version)),
+            )
+        }
+    }
 }
 
-fn run_set(mut args: BumpFileSetArgs) -> Result<()> {
-    let version = bump::set_version(
-        io::read_version(&mut io::bufreader(&args.input)?)?,
-        args.set,
-    )?;
+fn run_pre(mut args: BumpFilePreArgs, format: &Format) -> Result<()> {
+    let (channel, build) = (args.channel, args.build);
+    match args.scheme {
+        Scheme::Semver => update_file(
+            &args.input,
+            &mut args.output,
+            &args.git,
+            format,
+            "pre",
+            |version| bump::bump_pre(version, channel, build),
+        ),
+        Scheme::Pep440 => update_file(
+            &args.input,
+            &mut args.output,
+            &args.git,
+            format,
+            "pre",
+            |version| bump::pep440_bump_pre(version, channel, build),
+        ),
+    }
+}
 
-    write_to_dest(&mut args.output, &version)
+fn run_release(mut args: BumpFileReleaseArgs, format: &Format) -> Result<()> {
+    let (pre, build, allow_released) = (args.pre, args.build, args.allow_released);
+    match args.scheme {
+        Scheme::Semver => update_file(
+            &args.input,
+            &mut args.output,
+            &args.git,
+            format,
+            "release",
+            |version| bump::release_version(version, pre, build, allow_released),
+        ),
+        Scheme::Pep440 => update_file(
+            &args.input,
+            &mut args.output,
+            &args.git,
+            format,
+            "release",
+            |version| bump::pep440_release(version, pre, build, allow_released),
+        ),
+    }
 }
 
-fn write_to_dest(output: &mut Output, version: &Version) -> Result<()> {
-    match output {
-        Output::Stdout(writer) => io::write_version(writer, version),
-        Output::File(path) => {
-            // TODO: make more robust
-            let mut file = File::create(path)?;
-            io::write_version(&mut file, version)
+fn run_auto(mut args: BumpFileAutoArgs, format: &Format) -> Result<()> {
+    let (pre, build, repo) = (args.pre, args.build, args.repo);
+    match args.scheme {
+        Scheme::Semver => update_file(
+            &args.input,
+            &mut args.output,
+            &args.git,
+            format,
+            "auto",
+            |version| bump::auto_version(version, &repo, pre, build),
+        ),
+        Scheme::Pep440 => update_file(
+            &args.input,
+            &mut args.output,
+            &args.git,
+            format,
+            "auto",
+            |version| bump::pep440_auto_version(version, &repo, pre, build),
+        ),
+    }
+}
+
+fn run_set(mut args: BumpFileSetArgs, format: &Format) -> Result<()> {
+    let set = args.set;
+    match args.scheme {
+        Scheme::Semver => update_file(
+            &args.input,
+            &mut args.output,
+            &args.git,
+            format,
+            "set",
+            |version| bump::set_version(version, set),
+        ),
+        Scheme::Pep440 => update_file(
+            &args.input,
+            &mut args.output,
+            &args.git,
+            format,
+            "set",
+            |version| bump::set_pep440_version(version, set),
+        ),
+    }
+}
+
+/// Reads the version in the file at `input` and writes the version `next` returns for it, which
+/// was bumped by `kind`, as `output` says.
+fn update_file<V, F>(
+    input: &Path,
+    output: &mut Output,
+    git: &GitRecord,
+    format: &Format,
+    kind: &str,
+    next: F,
+) -> Result<()>
+where
+    V: SchemeVersion,
+    F: FnOnce(V) -> Result<V>,
+{
+    let previous: V = io::read_version_file(input)?;
+    let version = next(previous.clone())?;
+
+    write_to_dest(output, git, format, kind, &previous, &version)
+}
+
+/// Writes the new `version`, which was bumped by `kind` from `previous`, as `output` says.
+///
+/// With the JSON or export format, the bump is reported instead of previewing the change of a
+/// dry run.
+fn write_to_dest<V: SchemeVersion>(
+    output: &mut Output,
+    git: &GitRecord,
+    format: &Format,
+    kind: &str,
+    previous: &V,
+    version: &V,
+) -> Result<()> {
+    match (format, output) {
+        (Format::Text, Output::Stdout(writer)) => io::write_version(writer, version),
+        (_, Output::File(path)) => {
+            let repo = bump::prepare_record(git, path)?;
+            io::write_file(path, |file| io::write_version(file, version))?;
+            bump::record_version(repo, git, std::slice::from_ref(path), version)?;
+
+            report::bumped(
+                &mut std::io::stdout(),
+                format,
+                kind,
+                previous,
+                version,
+                std::slice::from_ref(path),
+                false,
+            )
         }
+        (Format::Text, Output::DryRun(path, writer)) => {
+            bump::announce(writer, &io::read_version_file::<V>(path)?, version)?;
+            bump::preview(writer, path, &format!("{}\n", version))
+        }
+        (_, Output::Stdout(_)) => Err(report::stdout_conflict()),
+        (_, Output::DryRun(path, writer)) => report::bumped(
+            writer,
+            format,
+            kind,
+            previous,
+            version,
+            std::slice::from_ref(path),
+            true,
+        ),
     }
 }
 
@@ -88,12 +364,13 @@ fn write_to_dest(output: &mut Output, version: &Version) -> Result<()> {
 mod tests {
     use crate::cli::bump::test_helpers::{OutputReceiver, WriteableSender};
     use crate::cli::{
-        BuildMetadata, BumpFileArgs, BumpFileSetArgs, Output, PreRelease, SetBuildMetadata,
-        SetPreRelease, SetVersion,
+        BuildMetadata, BumpFileArgs, BumpFileAutoArgs, BumpFilePreArgs, BumpFileReleaseArgs,
+        BumpFileSetArgs, Format, GitRecord, Output, PreRelease, SetBuildMetadata, SetPreRelease,
+        SetVersion,
     };
-    use crate::version::Version;
+    use crate::version::Scheme;
     use std::fs;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
     use std::str::FromStr;
     use std::sync::mpsc;
     use tempfile::NamedTempFile;
@@ -105,7 +382,7 @@ mod tests {
             #[test]
             fn $name() {
                 let (args, tempfile) = new_args($input, None, None);
-                run(args).unwrap();
+                run(args, &Format::Text).unwrap();
                 let output = String::from_utf8(
                     std::fs::read(tempfile.path()).expect("file contents should be read"),
                 )
@@ -123,7 +400,7 @@ mod tests {
             #[test]
             fn $name() {
                 let (args, output, _tempfile) = new_args_stdout($input, None, None);
-                run(args).unwrap();
+                run(args, &Format::Text).unwrap();
 
                 assert_eq!(output.into_string(), $output);
             }
@@ -137,7 +414,7 @@ mod tests {
             #[test]
             fn $name() {
                 let (args, tempfile) = new_args($input, $pre, $build);
-                run(args).unwrap();
+                run(args, &Format::Text).unwrap();
                 let output = String::from_utf8(
                     std::fs::read(tempfile.path()).expect("file contents should be read"),
                 )
@@ -155,7 +432,7 @@ mod tests {
             #[test]
             fn $name() {
                 let (args, output, _tempfile) = new_args_stdout($input, $pre, $build);
-                run(args).unwrap();
+                run(args, &Format::Text).unwrap();
 
                 assert_eq!(output.into_string(), $output);
             }
@@ -180,7 +457,7 @@ mod tests {
                     $no_pre_release,
                     $no_build_metadata,
                 );
-                run(args).unwrap();
+                run(args, &Format::Text).unwrap();
                 let output = String::from_utf8(
                     std::fs::read(tempfile.path()).expect("file contents should be read"),
                 )
@@ -209,13 +486,49 @@ mod tests {
                     $no_pre_release,
                     $no_build_metadata,
                 );
-                run(args).unwrap();
+                run(args, &Format::Text).unwrap();
 
                 assert_eq!(output.into_string(), $output);
             }
         };
     }
 
+    macro_rules! test_pre {
+        (
+            $name:ident, $input:expr, $channel:expr, $build:expr, $output:expr
+        ) => {
+            #[test]
+            fn $name() {
+                let (args, tempfile) = new_pre_args($input, $channel, $build);
+                run(args, &Format::Text).unwrap();
+                let output = String::from_utf8(
+                    std::fs::read(tempfile.path()).expect("file contents should be read"),
+                )
+                .expect("content should be valid utf-8");
+
+                assert_eq!(output, $output);
+            }
+        };
+    }
+
+    macro_rules! test_release {
+        (
+            $name:ident, $input:expr, $build:expr, $allow_released:expr, $output:expr
+        ) => {
+            #[test]
+            fn $name() {
+                let (args, tempfile) = new_release_args($input, $build, $allow_released);
+                run(args, &Format::Text).unwrap();
+                let output = String::from_utf8(
+                    std::fs::read(tempfile.path()).expect("file contents should be read"),
+                )
+                .expect("content should be valid utf-8");
+
+                assert_eq!(output, $output);
+            }
+        };
+    }
+
     mod major {
         use super::super::major::run;
         use super::*;
@@ -405,6 +718,254 @@ mod tests {
         );
     }
 
+    mod breaking {
+        use super::super::breaking::run;
+        use super::*;
+
+        test!(stable, "1.2.3", "2.0.0\n");
+        test!(pre_major, "0.2.3", "0.3.0\n");
+        test!(pre_minor, "0.0.3", "0.0.4\n");
+    }
+
+    mod feature {
+        use super::super::feature::run;
+        use super::*;
+
+        test!(stable, "1.2.3", "1.3.0\n");
+        test!(pre_major, "0.2.3", "0.2.4\n");
+        test!(pre_minor, "0.0.3", "0.0.4\n");
+    }
+
+    mod fix {
+        use super::super::fix::run;
+        use super::*;
+
+        test!(stable, "1.2.3", "1.2.4\n");
+        test!(pre_major, "0.2.3", "0.2.4\n");
+        test!(pre_minor, "0.0.3", "0.0.4\n");
+    }
+
+    mod pre {
+        use super::super::pre::run;
+        use super::*;
+
+        test_pre!(counter, "1.2.0-rc.1", None, None, "1.2.0-rc.2\n");
+        test_pre!(no_counter, "1.2.0-rc\n", None, None, "1.2.0-rc.1\n");
+        test_pre!(
+            channel,
+            "1.2.0-alpha.3",
+            Some("beta"),
+            None,
+            "1.2.0-beta.1\n"
+        );
+        test_pre!(new_channel, "1.2.0", Some("rc"), None, "1.2.1-rc.1\n");
+        test_pre!(
+            build,
+            "1.2.0-rc.1",
+            None,
+            Some("build8"),
+            "1.2.0-rc.2+build8\n"
+        );
+
+        #[test]
+        fn no_pre_release() {
+            let (args, _tempfile) = new_pre_args("1.2.0", None, None);
+
+            match run(args, &Format::Text) {
+                Err(err) => assert!(err.to_string().contains("no pre-release to bump")),
+                Ok(_) => panic!("should not bump a release"),
+            }
+        }
+    }
+
+    mod release {
+        use super::super::release::run;
+        use super::*;
+
+        test_release!(pre_release, "1.2.0-rc.2", None, false, "1.2.0\n");
+        test_release!(
+            pre_release_and_build,
+            "1.2.0-rc.2+b7",
+            None,
+            false,
+            "1.2.0\n"
+        );
+        test_release!(build, "1.2.0-rc.2", Some("b8"), false, "1.2.0+b8\n");
+        test_release!(allow_released, "1.2.0", None, true, "1.2.0\n");
+
+        #[test]
+        fn already_released() {
+            let (args, _tempfile) = new_release_args("1.2.0", None, false);
+
+            match run(args, &Format::Text) {
+                Err(err) => assert!(err.to_string().contains("already a release")),
+                Ok(_) => panic!("should not release a release"),
+            }
+        }
+    }
+
+    mod auto {
+        use super::super::auto::run;
+        use super::*;
+        use crate::git::test_helpers::{commit, git, init};
+
+        #[test]
+        fn breaking() {
+            let repo = tempfile::tempdir().expect("tempdir should be created");
+            init(repo.path());
+            commit(repo.path(), "a", "feat: first");
+            git(repo.path(), &["tag", "v0.2.1"]);
+            commit(repo.path(), "b", "fix: second");
+            commit(repo.path(), "c", "feat!: third");
+            let (args, tempfile) = new_auto_args("0.2.1\n", repo.path());
+            run(args, &Format::Text).unwrap();
+
+            assert_eq!(fs::read_to_string(tempfile.path()).unwrap(), "0.3.0\n");
+        }
+
+        #[test]
+        fn no_release() {
+            let repo = tempfile::tempdir().expect("tempdir should be created");
+            init(repo.path());
+            commit(repo.path(), "a", "feat: first");
+            git(repo.path(), &["tag", "v0.2.1"]);
+            commit(repo.path(), "b", "docs: second");
+            let (args, _tempfile) = new_auto_args("0.2.1\n", repo.path());
+
+            match run(args, &Format::Text) {
+                Err(err) => assert_eq!(
+                    err.to_string(),
+                    "no commits since tag v0.2.1 warrant a new version"
+                ),
+                Ok(_) => panic!("should not bump without changes"),
+            }
+        }
+
+        #[test]
+        fn no_tag() {
+            let repo = tempfile::tempdir().expect("tempdir should be created");
+            init(repo.path());
+            commit(repo.path(), "a", "fix: first");
+            let (args, tempfile) = new_auto_args("1.0.0\n", repo.path());
+            run(args, &Format::Text).unwrap();
+
+            assert_eq!(fs::read_to_string(tempfile.path()).unwrap(), "1.0.1\n");
+        }
+    }
+
+    mod record {
+        use super::super::minor::run;
+        use super::*;
+        use crate::git::test_helpers::{commit, init, output};
+
+        fn repo_args(repo: &Path, git: GitRecord) -> BumpFileArgs {
+            init(repo);
+            commit(repo, "VERSION", "1.2.3\n");
+            commit(repo, "other", "unrelated");
+            let input = repo.join("VERSION");
+
+            BumpFileArgs {
+                scheme: Scheme::Semver,
+                pre: None,
+                build: None,
+                input: input.clone(),
+                output: Output::File(input),
+                git,
+            }
+        }
+
+        #[test]
+        fn commit_only() {
+            let repo = tempfile::tempdir().expect("tempdir should be created");
+            let args = repo_args(
+                repo.path(),
+                GitRecord::Commit {
+                    message: "Release {version}".to_string(),
+                },
+            );
+            run(args, &Format::Text).unwrap();
+
+            assert_eq!(
+                output(repo.path(), &["log", "-1", "--format=%s"]),
+                "Release 1.3.0"
+            );
+            assert_eq!(
+                output(repo.path(), &["show", "--format=", "--name-only", "HEAD"]),
+                "VERSION"
+            );
+            assert_eq!(output(repo.path(), &["tag"]), "");
+        }
+
+        #[test]
+        fn tag() {
+            let repo = tempfile::tempdir().expect("tempdir should be created");
+            let args = repo_args(
+                repo.path(),
+                GitRecord::Tag {
+                    message: "chore: release {version}".to_string(),
+                    tag: "v{version}".to_string(),
+                },
+            );
+            run(args, &Format::Text).unwrap();
+
+            assert_eq!(
+                output(repo.path(), &["log", "-1", "--format=%s"]),
+                "chore: release 1.3.0"
+            );
+            assert_eq!(output(repo.path(), &["describe"]), "v1.3.0");
+            assert_eq!(output(repo.path(), &["status", "--porcelain"]), "");
+        }
+
+        #[test]
+        fn dirty() {
+            let repo = tempfile::tempdir().expect("tempdir should be created");
+            let args = repo_args(
+                repo.path(),
+                GitRecord::Commit {
+                    message: "Release {version}".to_string(),
+                },
+            );
+            fs::write(repo.path().join("other"), "changed").unwrap();
+
+            match run(args, &Format::Text) {
+                Err(err) => assert_eq!(
+                    err.to_string(),
+                    "working tree has uncommitted changes, refusing to commit the new version: \
+                     other"
+                ),
+                Ok(_) => panic!("should not commit with a dirty working tree"),
+            }
+            assert_eq!(
+                fs::read_to_string(repo.path().join("VERSION")).unwrap(),
+                "1.2.3\n"
+            );
+        }
+    }
+
+    mod dry_run {
+        use super::super::minor::run;
+        use super::*;
+
+        #[test]
+        fn preview() {
+            let (mut args, tempfile) = new_args("1.2.3\n", None, None);
+            let (sender, receiver) = mpsc::channel();
+            args.output =
+                Output::DryRun(args.input.clone(), Box::new(WriteableSender::new(sender)));
+            run(args, &Format::Text).unwrap();
+            let path = tempfile.path().display();
+
+            assert_eq!(
+                OutputReceiver::new(receiver).into_string(),
+                format!(
+                    "1.2.3 -> 1.3.0\n--- {}\n+++ {}\n@@ -1 +1 @@\n-1.2.3\n+1.3.0\n",
+                    path, path
+                )
+            );
+            assert_eq!(fs::read_to_string(tempfile.path()).unwrap(), "1.2.3\n");
+        }
+    }
+
     mod set {
         use super::super::set::run;
         use super::*;
@@ -641,10 +1202,67 @@ mod tests {
         let output = Output::File(input.clone());
 
         let args = BumpFileArgs {
+            scheme: Scheme::Semver,
             pre,
             build,
             input,
             output,
+            git: GitRecord::None,
+        };
+
+        (args, tempfile)
+    }
+
+    fn new_pre_args<S: Into<String>>(
+        input: S,
+        channel: Option<&str>,
+        build: Option<&str>,
+    ) -> (BumpFilePreArgs, NamedTempFile) {
+        let (args, tempfile) = new_args(input, channel, build);
+
+        let args = BumpFilePreArgs {
+            scheme: Scheme::Semver,
+            channel: args.pre,
+            build: args.build,
+            input: args.input,
+            output: args.output,
+            git: args.git,
+        };
+
+        (args, tempfile)
+    }
+
+    fn new_release_args<S: Into<String>>(
+        input: S,
+        build: Option<&str>,
+        allow_released: bool,
+    ) -> (BumpFileReleaseArgs, NamedTempFile) {
+        let (args, tempfile) = new_args(input, None, build);
+
+        let args = BumpFileReleaseArgs {
+            scheme: Scheme::Semver,
+            pre: args.pre,
+            build: args.build,
+            input: args.input,
+            output: args.output,
+            allow_released,
+            git: args.git,
+        };
+
+        (args, tempfile)
+    }
+
+    fn new_auto_args<S: Into<String>>(input: S, repo: &Path) -> (BumpFileAutoArgs, NamedTempFile) {
+        let (args, tempfile) = new_args(input, None, None);
+
+        let args = BumpFileAutoArgs {
+            scheme: Scheme::Semver,
+            pre: args.pre,
+            build: args.build,
+            input: args.input,
+            output: args.output,
+            repo: repo.to_path_buf(),
+            git: args.git,
         };
 
         (args, tempfile)
@@ -672,10 +1290,12 @@ mod tests {
         let output = Output::Stdout(Box::new(WriteableSender::new(sender)));
 
         let args = BumpFileArgs {
+            scheme: Scheme::Semver,
             pre,
             build,
             input,
             output,
+            git: GitRecord::None,
         };
         let output_receiver = OutputReceiver::new(receiver);
 
@@ -695,7 +1315,7 @@ mod tests {
         no_build_metadata: bool,
     ) -> (BumpFileSetArgs, NamedTempFile) {
         let set = if let Some(version) = version {
-            SetVersion::Version(Version::from_str(version).expect("should be valid version"))
+            SetVersion::Version(version.to_string())
         } else {
             SetVersion::Parts {
                 major,
@@ -728,7 +1348,13 @@ mod tests {
         let input = PathBuf::from(tempfile.path());
         let output = Output::File(input.clone());
 
-        let args = BumpFileSetArgs { input, output, set };
+        let args = BumpFileSetArgs {
+            scheme: Scheme::Semver,
+            input,
+            output,
+            set,
+            git: GitRecord::None,
+        };
 
         (args, tempfile)
     }
@@ -746,7 +1372,7 @@ mod tests {
         no_build_metadata: bool,
     ) -> (BumpFileSetArgs, OutputReceiver, NamedTempFile) {
         let set = if let Some(version) = version {
-            SetVersion::Version(Version::from_str(version).expect("should be valid version"))
+            SetVersion::Version(version.to_string())
         } else {
             SetVersion::Parts {
                 major,
@@ -780,7 +1406,13 @@ mod tests {
         let (sender, receiver) = mpsc::channel();
         let output = Output::Stdout(Box::new(WriteableSender::new(sender)));
 
-        let args = BumpFileSetArgs { input, output, set };
+        let args = BumpFileSetArgs {
+            scheme: Scheme::Semver,
+            input,
+            output,
+            set,
+            git: GitRecord::None,
+        };
         let output_receiver = OutputReceiver::new(receiver);
 
         (args, output_receiver, tempfile)
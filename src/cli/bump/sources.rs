@@ -0,0 +1,747 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::calver::CalVer;
+use crate::cli::{
+    bump, report, verify, BumpSourcesArgs, BumpSourcesAutoArgs, BumpSourcesCalverArgs,
+    BumpSourcesPreArgs, BumpSourcesReleaseArgs, BumpSourcesSetArgs, Destination, Format, GitRecord,
+};
+use crate::io;
+use crate::pep440::Pep440Version;
+use crate::source::{Edit, Source};
+use crate::version::{Scheme, SchemeVersion, Version, VersionBumper};
+use crate::Result;
+use failure::bail;
+use std::io::Write;
+use std::path::PathBuf;
+
+pub(crate) mod major {
+    use crate::cli::{BumpSourcesArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpSourcesArgs, format: &Format) -> Result<()> {
+        super::run_major(args, format)
+    }
+}
+
+pub(crate) mod minor {
+    use crate::cli::{BumpSourcesArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpSourcesArgs, format: &Format) -> Result<()> {
+        super::run_minor(args, format)
+    }
+}
+
+pub(crate) mod patch {
+    use crate::cli::{BumpSourcesArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpSourcesArgs, format: &Format) -> Result<()> {
+        super::run_patch(args, format)
+    }
+}
+
+pub(crate) mod breaking {
+    use crate::cli::{BumpSourcesArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpSourcesArgs, format: &Format) -> Result<()> {
+        super::run_breaking(args, format)
+    }
+}
+
+pub(crate) mod feature {
+    use crate::cli::{BumpSourcesArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpSourcesArgs, format: &Format) -> Result<()> {
+        super::run_feature(args, format)
+    }
+}
+
+pub(crate) mod fix {
+    use crate::cli::{BumpSourcesArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpSourcesArgs, format: &Format) -> Result<()> {
+        super::run_fix(args, format)
+    }
+}
+
+pub(crate) mod pre {
+    use crate::cli::{BumpSourcesPreArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpSourcesPreArgs, format: &Format) -> Result<()> {
+        super::run_pre(args, format)
+    }
+}
+
+pub(crate) mod post {
+    use crate::cli::{BumpSourcesArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpSourcesArgs, format: &Format) -> Result<()> {
+        super::run_post(args, format)
+    }
+}
+
+pub(crate) mod dev {
+    use crate::cli::{BumpSourcesArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpSourcesArgs, format: &Format) -> Result<()> {
+        super::run_dev(args, format)
+    }
+}
+
+pub(crate) mod calver {
+    use crate::cli::{BumpSourcesCalverArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpSourcesCalverArgs, format: &Format) -> Result<()> {
+        super::run_calver(args, format)
+    }
+}
+
+pub(crate) mod release {
+    use crate::cli::{BumpSourcesReleaseArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpSourcesReleaseArgs, format: &Format) -> Result<()> {
+        super::run_release(args, format)
+    }
+}
+
+pub(crate) mod auto {
+    use crate::cli::{BumpSourcesAutoArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpSourcesAutoArgs, format: &Format) -> Result<()> {
+        super::run_auto(args, format)
+    }
+}
+
+pub(crate) mod set {
+    use crate::cli::{BumpSourcesSetArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpSourcesSetArgs, format: &Format) -> Result<()> {
+        super::run_set(args, format)
+    }
+}
+
+fn run_major(args: BumpSourcesArgs, format: &Format) -> Result<()> {
+    run_bump(
+        args,
+        format,
+        "major",
+        VersionBumper::bump_major,
+        Pep440Version::bump_major,
+    )
+}
+
+fn run_minor(args: BumpSourcesArgs, format: &Format) -> Result<()> {
+    run_bump(
+        args,
+        format,
+        "minor",
+        VersionBumper::bump_minor,
+        Pep440Version::bump_minor,
+    )
+}
+
+fn run_patch(args: BumpSourcesArgs, format: &Format) -> Result<()> {
+    run_bump(
+        args,
+        format,
+        "patch",
+        VersionBumper::bump_patch,
+        Pep440Version::bump_patch,
+    )
+}
+
+fn run_breaking(args: BumpSourcesArgs, format: &Format) -> Result<()> {
+    run_bump(
+        args,
+        format,
+        "breaking",
+        VersionBumper::bump_breaking,
+        Pep440Version::bump_breaking,
+    )
+}
+
+fn run_feature(args: BumpSourcesArgs, format: &Format) -> Result<()> {
+    run_bump(
+        args,
+        format,
+        "feature",
+        VersionBumper::bump_feature,
+        Pep440Version::bump_feature,
+    )
+}
+
+fn run_fix(args: BumpSourcesArgs, format: &Format) -> Result<()> {
+    run_bump(
+        args,
+        format,
+        "fix",
+        VersionBumper::bump_fix,
+        Pep440Version::bump_fix,
+    )
+}
+
+/// Bumps the sources by `kind`, with `semver` bumping a semantic version once any pre-release and
+/// build metadata are applied to it, and `pep440` bumping a PEP 440 version, which has neither.
+fn run_bump<S, P>(
+    mut args: BumpSourcesArgs,
+    format: &Format,
+    kind: &str,
+    semver: S,
+    pep440: P,
+) -> Result<()>
+where
+    S: FnOnce(VersionBumper) -> Version,
+    P: FnOnce(&Pep440Version) -> Pep440Version,
+{
+    let (pre, build) = (args.pre, args.build);
+    match args.sources.load(args.scheme)? {
+        (sources, Scheme::Semver) => update_sources(
+            sources,
+            &mut args.dest,
+            &args.git,
+            format,
+            kind,
+            |version: Version| {
+                Ok(semver(bump::prepare_version(
+                    || Ok(version.clone()),
+                    pre,
+                    build,
+                )?))
+            },
+        ),
+        (sources, Scheme::Pep440) => {
+            bump::pep440_parts(pre, build)?;
+            update_sources(
+                sources,
+                &mut args.dest,
+                &args.git,
+                format,
+                kind,
+                |version: Pep440Version| Ok(pep440(&version)),
+            )
+        }
+    }
+}
+
+fn run_pre(mut args: BumpSourcesPreArgs, format: &Format) -> Result<()> {
+    let (channel, build) = (args.channel, args.build);
+    match args.sources.load(args.scheme)? {
+        (sources, Scheme::Semver) => update_sources(
+            sources,
+            &mut args.dest,
+            &args.git,
+            format,
+            "pre",
+            |version| bump::bump_pre(version, channel, build),
+        ),
+        (sources, Scheme::Pep440) => update_sources(
+            sources,
+            &mut args.dest,
+            &args.git,
+            format,
+            "pre",
+            |version| bump::pep440_bump_pre(version, channel, build),
+        ),
+    }
+}
+
+/// Bumps the post-release of PEP 440 versions, which semantic versions do not have.
+fn run_post(mut args: BumpSourcesArgs, format: &Format) -> Result<()> {
+    match args.sources.load(args.scheme)? {
+        (_, Scheme::Semver) => {
+            bail!("semantic versions have no post-releases, use --scheme pep440")
+        }
+        (sources, Scheme::Pep440) => {
+            bump::pep440_parts(args.pre, args.build)?;
+            update_sources(
+                sources,
+                &mut args.dest,
+                &args.git,
+                format,
+                "post",
+                |version: Pep440Version| Ok(version.bump_post()),
+            )
+        }
+    }
+}
+
+/// Bumps the development release of PEP 440 versions, which semantic versions do not have.
+fn run_dev(mut args: BumpSourcesArgs, format: &Format) -> Result<()> {
+    match args.sources.load(args.scheme)? {
+        (_, Scheme::Semver) => {
+            bail!("semantic versions have no development releases, use --scheme pep440")
+        }
+        (sources, Scheme::Pep440) => {
+            bump::pep440_parts(args.pre, args.build)?;
+            update_sources(
+                sources,
+                &mut args.dest,
+                &args.git,
+                format,
+                "dev",
+                |version: Pep440Version| Ok(version.bump_dev()),
+            )
+        }
+    }
+}
+
+/// Rolls calendar versions to today, which follow their format rather than a scheme.
+fn run_calver(mut args: BumpSourcesCalverArgs, format: &Format) -> Result<()> {
+    if args.scheme.is_some() {
+        bail!("calendar versions follow --calver-format rather than a --scheme");
+    }
+    if args.pre.is_some() || args.build.is_some() {
+        bail!("calendar versions have no pre-release or build metadata");
+    }
+    let (calver_format, today) = (args.format, args.today);
+    let (sources, _) = args.sources.load(None)?;
+
+    update_sources(
+        sources,
+        &mut args.dest,
+        &args.git,
+        format,
+        "calver",
+        |version: CalVer| calver_format.bump(&version, today),
+    )
+}
+
+fn run_release(mut args: BumpSourcesReleaseArgs, format: &Format) -> Result<()> {
+    let (pre, build, allow_released) = (args.pre, args.build, args.allow_released);
+    match args.sources.load(args.scheme)? {
+        (sources, Scheme::Semver) => update_sources(
+            sources,
+            &mut args.dest,
+            &args.git,
+            format,
+            "release",
+            |version| bump::release_version(version, pre, build, allow_released),
+        ),
+        (sources, Scheme::Pep440) => update_sources(
+            sources,
+            &mut args.dest,
+            &args.git,
+            format,
+            "release",
+            |version| bump::pep440_release(version, pre, build, allow_released),
+        ),
+    }
+}
+
+fn run_auto(mut args: BumpSourcesAutoArgs, format: &Format) -> Result<()> {
+    let (pre, build, repo) = (args.pre, args.build, args.repo);
+    match args.sources.load(args.scheme)? {
+        (sources, Scheme::Semver) => update_sources(
+            sources,
+            &mut args.dest,
+            &args.git,
+            format,
+            "auto",
+            |version| bump::auto_version(version, &repo, pre, build),
+        ),
+        (sources, Scheme::Pep440) => update_sources(
+            sources,
+            &mut args.dest,
+            &args.git,
+            format,
+            "auto",
+            |version| bump::pep440_auto_version(version, &repo, pre, build),
+        ),
+    }
+}
+
+fn run_set(mut args: BumpSourcesSetArgs, format: &Format) -> Result<()> {
+    let set = args.set;
+    match args.sources.load(args.scheme)? {
+        (sources, Scheme::Semver) => update_sources(
+            sources,
+            &mut args.dest,
+            &args.git,
+            format,
+            "set",
+            |version| bump::set_version(version, set),
+        ),
+        (sources, Scheme::Pep440) => update_sources(
+            sources,
+            &mut args.dest,
+            &args.git,
+            format,
+            "set",
+            |version| bump::set_pep440_version(version, set),
+        ),
+    }
+}
+
+/// Bumps every source in lock-step from the version they share, failing before any file is
+/// written if they disagree.
+///
+/// With the JSON format, the bump is reported as a bump of `kind` instead of previewing the changes
+/// of a dry run.
+fn update_sources<V, F>(
+    sources: Vec<Source>,
+    dest: &mut Destination,
+    git: &GitRecord,
+    format: &Format,
+    kind: &str,
+    next_version: F,
+) -> Result<()>
+where
+    V: SchemeVersion,
+    F: FnOnce(V) -> Result<V>,
+{
+    if let (Format::Json | Format::Export(_), Destination::Stdout(_)) = (format, &dest) {
+        return Err(report::stdout_conflict());
+    }
+    let mut found = Vec::with_capacity(sources.len());
+    for source in &sources {
+        found.push((source, source.read_version::<V>()?));
+    }
+    verify::agree(&found)?;
+    let previous = found[0].1.clone();
+    let version = next_version(previous.clone())?;
+
+    let mut edits: Vec<Edit> = Vec::with_capacity(sources.len());
+    // The file holding each source's version, as opposed to those kept in step with it
+    let mut primary = Vec::with_capacity(sources.len());
+    for source in &sources {
+        primary.push(edits.len());
+        for edit in source.edits(&version)? {
+            if edits.iter().any(|other| other.path == edit.path) {
+                bail!(
+                    "{} is listed more than once, each file can only be edited by one source",
+                    edit.path.display()
+                );
+            }
+            edits.push(edit);
+        }
+    }
+
+    match dest {
+        Destination::Files => {
+            let repo = bump::prepare_record(git, &edits[0].path)?;
+            for edit in &edits {
+                io::write_file(&edit.path, |file| {
+                    Ok(file.write_all(edit.contents.as_bytes())?)
+                })?;
+                report::edited(&mut std::io::stderr(), edit)?;
+            }
+            let paths: Vec<PathBuf> = edits.into_iter().map(|edit| edit.path).collect();
+            bump::record_version(repo, git, &paths, &version)?;
+
+            report::bumped(
+                &mut std::io::stdout(),
+                format,
+                kind,
+                &previous,
+                &version,
+                &paths,
+                false,
+            )
+        }
+        Destination::Stdout(writer) => {
+            for index in primary {
+                write!(writer, "{}", edits[index].contents)?;
+            }
+
+            Ok(())
+        }
+        Destination::DryRun(writer) if *format != Format::Text => {
+            let paths: Vec<PathBuf> = edits.into_iter().map(|edit| edit.path).collect();
+
+            report::bumped(writer, format, kind, &previous, &version, &paths, true)
+        }
+        Destination::DryRun(writer) => {
+            bump::announce(writer, &previous, &version)?;
+            for edit in &edits {
+                bump::preview(writer, &edit.path, &edit.contents)?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::bump::test_helpers::{OutputReceiver, WriteableSender};
+    use crate::cli::SelectSources;
+    use crate::config::CONFIG_FILE;
+    use crate::error::VersioError;
+    use crate::source::Source;
+    use chrono::NaiveDate;
+    use std::fs;
+    use std::path::Path;
+    use std::sync::mpsc;
+
+    const CONFIG: &str = "[[source]]\nkind = \"cargo\"\npath = \"Cargo.toml\"\n\n\
+                          [[source]]\nkind = \"file\"\npath = \"VERSION.txt\"\n\n\
+                          [[source]]\nkind = \"pattern\"\npath = \"install.sh\"\n\
+                          pattern = 'VERSION=(?P<version>\\S+)'\n";
+
+    fn project(dir: &Path, version: &str) -> PathBuf {
+        fs::write(
+            dir.join("Cargo.toml"),
+            format!("[package]\nname = \"a\"\nversion = \"{}\"\n", version),
+        )
+        .unwrap();
+        fs::write(dir.join("VERSION.txt"), format!("{}\n", version)).unwrap();
+        fs::write(
+            dir.join("install.sh"),
+            format!("#!/bin/sh\nVERSION={}\necho $VERSION\n", version),
+        )
+        .unwrap();
+        let config = dir.join(CONFIG_FILE);
+        fs::write(&config, CONFIG).unwrap();
+
+        config
+    }
+
+    fn new_args(config: PathBuf, dest: Destination) -> BumpSourcesArgs {
+        BumpSourcesArgs {
+            pre: None,
+            build: None,
+            sources: SelectSources::Config(Some(config)),
+            scheme: None,
+            dest,
+            git: GitRecord::None,
+        }
+    }
+
+    fn read(dir: &Path, name: &str) -> String {
+        fs::read_to_string(dir.join(name)).unwrap()
+    }
+
+    #[test]
+    fn minor() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let config = project(dir.path(), "1.2.3");
+        run_minor(new_args(config, Destination::Files), &Format::Text).unwrap();
+
+        assert_eq!(
+            read(dir.path(), "Cargo.toml"),
+            "[package]\nname = \"a\"\nversion = \"1.3.0\"\n"
+        );
+        assert_eq!(read(dir.path(), "VERSION.txt"), "1.3.0\n");
+        assert_eq!(
+            read(dir.path(), "install.sh"),
+            "#!/bin/sh\nVERSION=1.3.0\necho $VERSION\n"
+        );
+    }
+
+    #[test]
+    fn set() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let config = project(dir.path(), "1.2.3");
+        let args = BumpSourcesSetArgs {
+            sources: SelectSources::Config(Some(config)),
+            scheme: None,
+            dest: Destination::Files,
+            git: GitRecord::None,
+            set: crate::cli::SetVersion::Version("2.0.0-rc.1".to_string()),
+        };
+        run_set(args, &Format::Text).unwrap();
+
+        assert_eq!(read(dir.path(), "VERSION.txt"), "2.0.0-rc.1\n");
+        assert_eq!(
+            read(dir.path(), "install.sh"),
+            "#!/bin/sh\nVERSION=2.0.0-rc.1\necho $VERSION\n"
+        );
+    }
+
+    #[test]
+    fn dry_run() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let config = project(dir.path(), "1.2.3");
+        let (tx, rx) = mpsc::channel();
+        let dest = Destination::DryRun(Box::new(WriteableSender::new(tx)));
+        run_patch(new_args(config, dest), &Format::Text).unwrap();
+        let output = OutputReceiver::new(rx).into_string();
+
+        assert!(output.starts_with("1.2.3 -> 1.2.4\n"));
+        assert_eq!(output.matches("\n+++ ").count(), 3);
+        assert!(output.contains("-VERSION=1.2.3\n+VERSION=1.2.4\n"));
+        assert_eq!(read(dir.path(), "VERSION.txt"), "1.2.3\n");
+    }
+
+    #[test]
+    fn dry_run_json() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let config = project(dir.path(), "1.2.3");
+        let (tx, rx) = mpsc::channel();
+        let dest = Destination::DryRun(Box::new(WriteableSender::new(tx)));
+        run_patch(new_args(config, dest), &Format::Json).unwrap();
+        let output = OutputReceiver::new(rx).into_string();
+
+        assert!(output.starts_with(r#"{"bump":"patch","previous":"1.2.3","next":"1.2.4","#));
+        assert_eq!(output.lines().count(), 1);
+        assert!(output.contains(&format!(
+            r#""files":["{}","#,
+            dir.path().join("Cargo.toml").display()
+        )));
+        assert_eq!(read(dir.path(), "VERSION.txt"), "1.2.3\n");
+    }
+
+    #[test]
+    fn stdout() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("version.rs");
+        fs::write(&path, "// a\npub const VERSION: &str = \"1.2.3\";\n").unwrap();
+        let pattern = r#"VERSION: &str = "(?P<version>[^"]+)""#.parse().unwrap();
+        let (tx, rx) = mpsc::channel();
+        let args = BumpSourcesArgs {
+            pre: None,
+            build: None,
+            sources: SelectSources::Given(vec![Source::Pattern(path.clone(), pattern)]),
+            scheme: None,
+            dest: Destination::Stdout(Box::new(WriteableSender::new(tx))),
+            git: GitRecord::None,
+        };
+        run_major(args, &Format::Text).unwrap();
+
+        assert_eq!(
+            OutputReceiver::new(rx).into_string(),
+            "// a\npub const VERSION: &str = \"2.0.0\";\n"
+        );
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "// a\npub const VERSION: &str = \"1.2.3\";\n"
+        );
+    }
+
+    #[test]
+    fn disagree() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let config = project(dir.path(), "1.2.3");
+        fs::write(dir.path().join("VERSION.txt"), "1.2.4\n").unwrap();
+        let err = run_major(new_args(config, Destination::Files), &Format::Text).unwrap_err();
+
+        match err.downcast::<VersioError>() {
+            Ok(err) => assert_eq!(err.exit_code(), 9),
+            Err(err) => panic!("should be a versio error: {:?}", err),
+        }
+        assert_eq!(
+            read(dir.path(), "Cargo.toml"),
+            "[package]\nname = \"a\"\nversion = \"1.2.3\"\n"
+        );
+    }
+
+    #[test]
+    fn pep440_config() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        fs::write(dir.path().join("VERSION.txt"), "1.2.0rc1\n").unwrap();
+        fs::write(dir.path().join("__init__.py"), "__version__ = \"1.2rc1\"\n").unwrap();
+        let config = dir.path().join(CONFIG_FILE);
+        fs::write(
+            &config,
+            "scheme = \"pep440\"\n\n\
+             [[source]]\nkind = \"file\"\npath = \"VERSION.txt\"\n\n\
+             [[source]]\nkind = \"pattern\"\npath = \"__init__.py\"\n\
+             pattern = '__version__ = \"(?P<version>[^\"]+)\"'\n",
+        )
+        .unwrap();
+        run_dev(new_args(config, Destination::Files), &Format::Text).unwrap();
+
+        assert_eq!(read(dir.path(), "VERSION.txt"), "1.2.0rc2.dev1\n");
+        assert_eq!(
+            read(dir.path(), "__init__.py"),
+            "__version__ = \"1.2.0rc2.dev1\"\n"
+        );
+    }
+
+    #[test]
+    fn pep440_cargo() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let config = project(dir.path(), "1.2.3");
+        let mut args = new_args(config, Destination::Files);
+        args.scheme = Some(Scheme::Pep440);
+
+        assert!(run_post(args, &Format::Text).is_err());
+        assert_eq!(read(dir.path(), "VERSION.txt"), "1.2.3\n");
+    }
+
+    #[test]
+    fn calver() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let config = project(dir.path(), "2024.9.3");
+        let args = |format: &str, day: u32| BumpSourcesCalverArgs {
+            pre: None,
+            build: None,
+            sources: SelectSources::Config(Some(config.clone())),
+            scheme: None,
+            dest: Destination::Files,
+            git: GitRecord::None,
+            format: format.parse().unwrap(),
+            today: NaiveDate::from_ymd(2024, 10, day),
+        };
+        run_calver(args("YYYY.MM.MICRO", 18), &Format::Text).unwrap();
+
+        assert_eq!(
+            read(dir.path(), "Cargo.toml"),
+            "[package]\nname = \"a\"\nversion = \"2024.10.0\"\n"
+        );
+        assert_eq!(read(dir.path(), "VERSION.txt"), "2024.10.0\n");
+
+        run_calver(args("YYYY.MM.MICRO", 19), &Format::Text).unwrap();
+
+        assert_eq!(read(dir.path(), "VERSION.txt"), "2024.10.1\n");
+        assert!(run_calver(args("YYYY.0M.0D", 5), &Format::Text).is_err());
+        assert_eq!(read(dir.path(), "VERSION.txt"), "2024.10.1\n");
+    }
+
+    #[test]
+    fn same_file_twice() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let config = project(dir.path(), "1.2.3");
+        fs::write(
+            &config,
+            "[[source]]\nkind = \"file\"\npath = \"VERSION.txt\"\n\n\
+             [[source]]\nkind = \"pattern\"\npath = \"VERSION.txt\"\n\
+             pattern = '(?P<version>.+)'\n",
+        )
+        .unwrap();
+
+        assert!(run_minor(new_args(config, Destination::Files), &Format::Text).is_err());
+        assert_eq!(read(dir.path(), "VERSION.txt"), "1.2.3\n");
+    }
+
+    mod record {
+        use super::*;
+        use crate::git::test_helpers::{git, init, output};
+
+        #[test]
+        fn tag() {
+            let dir = tempfile::tempdir().expect("tempdir should be created");
+            init(dir.path());
+            let config = project(dir.path(), "1.2.3");
+            git(dir.path(), &["add", "--all"]);
+            git(dir.path(), &["commit", "--quiet", "-m", "Initial commit"]);
+            let mut args = new_args(config, Destination::Files);
+            args.git = GitRecord::Tag {
+                message: "Release {version}".to_string(),
+                tag: "v{version}".to_string(),
+            };
+            run_minor(args, &Format::Text).unwrap();
+
+            assert_eq!(output(dir.path(), &["describe"]), "v1.3.0");
+            assert_eq!(
+                output(dir.path(), &["show", "--name-only", "--format=%s", "HEAD"]),
+                "Release 1.3.0\n\nCargo.toml\nVERSION.txt\ninstall.sh"
+            );
+            assert_eq!(output(dir.path(), &["status", "--porcelain"]), "");
+        }
+    }
+}
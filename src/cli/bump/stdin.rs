@@ -2,84 +2,334 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::cli::{bump, BumpStdinArgs, BumpStdinSetArgs};
+use crate::cli::{
+    bump, report, BumpStdinArgs, BumpStdinAutoArgs, BumpStdinPreArgs, BumpStdinReleaseArgs,
+    BumpStdinSetArgs, Format,
+};
 use crate::io;
-use crate::version::Version;
+use crate::pep440::Pep440Version;
+use crate::version::{Scheme, SchemeVersion, Version, VersionBumper};
 use crate::Result;
-use std::io::Write;
+use std::io::{Read, Write};
 
 pub(crate) mod major {
-    use crate::{cli::BumpStdinArgs, Result};
+    use crate::cli::{BumpStdinArgs, Format};
+    use crate::Result;
 
-    pub(crate) fn run(args: BumpStdinArgs) -> Result<()> {
-        super::run_major(args)
+    pub(crate) fn run(args: BumpStdinArgs, format: &Format) -> Result<()> {
+        super::run_major(args, format)
     }
 }
 
 pub(crate) mod minor {
-    use crate::{cli::BumpStdinArgs, Result};
+    use crate::cli::{BumpStdinArgs, Format};
+    use crate::Result;
 
-    pub(crate) fn run(args: BumpStdinArgs) -> Result<()> {
-        super::run_minor(args)
+    pub(crate) fn run(args: BumpStdinArgs, format: &Format) -> Result<()> {
+        super::run_minor(args, format)
     }
 }
 
 pub(crate) mod patch {
-    use crate::{cli::BumpStdinArgs, Result};
+    use crate::cli::{BumpStdinArgs, Format};
+    use crate::Result;
 
-    pub(crate) fn run(args: BumpStdinArgs) -> Result<()> {
-        super::run_patch(args)
+    pub(crate) fn run(args: BumpStdinArgs, format: &Format) -> Result<()> {
+        super::run_patch(args, format)
+    }
+}
+
+pub(crate) mod breaking {
+    use crate::cli::{BumpStdinArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpStdinArgs, format: &Format) -> Result<()> {
+        super::run_breaking(args, format)
+    }
+}
+
+pub(crate) mod feature {
+    use crate::cli::{BumpStdinArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpStdinArgs, format: &Format) -> Result<()> {
+        super::run_feature(args, format)
+    }
+}
+
+pub(crate) mod fix {
+    use crate::cli::{BumpStdinArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpStdinArgs, format: &Format) -> Result<()> {
+        super::run_fix(args, format)
+    }
+}
+
+pub(crate) mod pre {
+    use crate::cli::{BumpStdinPreArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpStdinPreArgs, format: &Format) -> Result<()> {
+        super::run_pre(args, format)
+    }
+}
+
+pub(crate) mod release {
+    use crate::cli::{BumpStdinReleaseArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpStdinReleaseArgs, format: &Format) -> Result<()> {
+        super::run_release(args, format)
+    }
+}
+
+pub(crate) mod auto {
+    use crate::cli::{BumpStdinAutoArgs, Format};
+    use crate::Result;
+
+    pub(crate) fn run(args: BumpStdinAutoArgs, format: &Format) -> Result<()> {
+        super::run_auto(args, format)
     }
 }
 
 pub(crate) mod set {
-    use crate::{cli::BumpStdinSetArgs, Result};
+    use crate::cli::{BumpStdinSetArgs, Format};
+    use crate::Result;
 
-    pub(crate) fn run(args: BumpStdinSetArgs) -> Result<()> {
-        super::run_set(args)
+    pub(crate) fn run(args: BumpStdinSetArgs, format: &Format) -> Result<()> {
+        super::run_set(args, format)
     }
 }
 
-fn run_major(mut args: BumpStdinArgs) -> Result<()> {
-    let version =
-        bump::prepare_version_from_reader(&mut args.input, args.pre, args.build)?.bump_major();
+fn run_major(args: BumpStdinArgs, format: &Format) -> Result<()> {
+    run_bump(
+        args,
+        format,
+        "major",
+        VersionBumper::bump_major,
+        Pep440Version::bump_major,
+    )
+}
 
-    write_to_dest(&mut args.output, &version)
+fn run_minor(args: BumpStdinArgs, format: &Format) -> Result<()> {
+    run_bump(
+        args,
+        format,
+        "minor",
+        VersionBumper::bump_minor,
+        Pep440Version::bump_minor,
+    )
 }
 
-fn run_minor(mut args: BumpStdinArgs) -> Result<()> {
-    let version =
-        bump::prepare_version_from_reader(&mut args.input, args.pre, args.build)?.bump_minor();
+fn run_patch(args: BumpStdinArgs, format: &Format) -> Result<()> {
+    run_bump(
+        args,
+        format,
+        "patch",
+        VersionBumper::bump_patch,
+        Pep440Version::bump_patch,
+    )
+}
 
-    write_to_dest(&mut args.output, &version)
+fn run_breaking(args: BumpStdinArgs, format: &Format) -> Result<()> {
+    run_bump(
+        args,
+        format,
+        "breaking",
+        VersionBumper::bump_breaking,
+        Pep440Version::bump_breaking,
+    )
 }
 
-fn run_patch(mut args: BumpStdinArgs) -> Result<()> {
-    let version =
-        bump::prepare_version_from_reader(&mut args.input, args.pre, args.build)?.bump_patch();
+fn run_feature(args: BumpStdinArgs, format: &Format) -> Result<()> {
+    run_bump(
+        args,
+        format,
+        "feature",
+        VersionBumper::bump_feature,
+        Pep440Version::bump_feature,
+    )
+}
 
-    write_to_dest(&mut args.output, &version)
+fn run_fix(args: BumpStdinArgs, format: &Format) -> Result<()> {
+    run_bump(
+        args,
+        format,
+        "fix",
+        VersionBumper::bump_fix,
+        Pep440Version::bump_fix,
+    )
 }
 
-fn run_set(mut args: BumpStdinSetArgs) -> Result<()> {
-    let version = bump::set_version(io::read_version(&mut args.input)?, args.set)?;
+/// Bumps the version read from standard input by `kind`, with `semver` bumping a semantic version
+/// once any pre-release and build metadata are applied to it, and `pep440` bumping a PEP 440
+/// version, which has neither.
+fn run_bump<S, P>(
+    mut args: BumpStdinArgs,
+    format: &Format,
+    kind: &str,
+    semver: S,
+    pep440: P,
+) -> Result<()>
+where
+    S: FnOnce(VersionBumper) -> Version,
+    P: FnOnce(&Pep440Version) -> Pep440Version,
+{
+    let (pre, build) = (args.pre, args.build);
+    match args.scheme {
+        Scheme::Semver => update_stdin(
+            &mut args.input,
+            &mut args.output,
+            format,
+            kind,
+            |version: Version| {
+                Ok(semver(bump::prepare_version(
+                    || Ok(version.clone()),
+                    pre,
+                    build,
+                )?))
+            },
+        ),
+        Scheme::Pep440 => {
+            bump::pep440_parts(pre, build)?;
+            update_stdin(
+                &mut args.input,
+                &mut args.output,
+                format,
+                kind,
+                |version: Pep440Version| Ok(pep440(&version)),
+            )
+        }
+    }
+}
 
-    write_to_dest(&mut args.output, &version)
+fn run_pre(mut args: BumpStdinPreArgs, format: &Format) -> Result<()> {
+    let (channel, build) = (args.channel, args.build);
+    match args.scheme {
+        Scheme::Semver => update_stdin(
+            &mut args.input,
+            &mut args.output,
+            format,
+            "pre",
+            |version| bump::bump_pre(version, channel, build),
+        ),
+        Scheme::Pep440 => update_stdin(
+            &mut args.input,
+            &mut args.output,
+            format,
+            "pre",
+            |version| bump::pep440_bump_pre(version, channel, build),
+        ),
+    }
 }
 
-fn write_to_dest<W: Write>(writer: &mut W, version: &Version) -> Result<()> {
-    io::write_version(writer, version)
+fn run_release(mut args: BumpStdinReleaseArgs, format: &Format) -> Result<()> {
+    let (pre, build, allow_released) = (args.pre, args.build, args.allow_released);
+    match args.scheme {
+        Scheme::Semver => update_stdin(
+            &mut args.input,
+            &mut args.output,
+            format,
+            "release",
+            |version| bump::release_version(version, pre, build, allow_released),
+        ),
+        Scheme::Pep440 => update_stdin(
+            &mut args.input,
+            &mut args.output,
+            format,
+            "release",
+            |version| bump::pep440_release(version, pre, build, allow_released),
+        ),
+    }
+}
+
+fn run_auto(mut args: BumpStdinAutoArgs, format: &Format) -> Result<()> {
+    let (pre, build, repo) = (args.pre, args.build, args.repo);
+    match args.scheme {
+        Scheme::Semver => update_stdin(
+            &mut args.input,
+            &mut args.output,
+            format,
+            "auto",
+            |version| bump::auto_version(version, &repo, pre, build),
+        ),
+        Scheme::Pep440 => update_stdin(
+            &mut args.input,
+            &mut args.output,
+            format,
+            "auto",
+            |version| bump::pep440_auto_version(version, &repo, pre, build),
+        ),
+    }
+}
+
+fn run_set(mut args: BumpStdinSetArgs, format: &Format) -> Result<()> {
+    let set = args.set;
+    match args.scheme {
+        Scheme::Semver => update_stdin(
+            &mut args.input,
+            &mut args.output,
+            format,
+            "set",
+            |version| bump::set_version(version, set),
+        ),
+        Scheme::Pep440 => update_stdin(
+            &mut args.input,
+            &mut args.output,
+            format,
+            "set",
+            |version| bump::set_pep440_version(version, set),
+        ),
+    }
+}
+
+/// Reads the version from `input` and writes the version `next` returns for it, which was bumped
+/// by `kind`, to `output`.
+fn update_stdin<V, F, R, W>(
+    input: &mut R,
+    output: &mut W,
+    format: &Format,
+    kind: &str,
+    next: F,
+) -> Result<()>
+where
+    V: SchemeVersion,
+    F: FnOnce(V) -> Result<V>,
+    R: Read,
+    W: Write,
+{
+    let previous: V = io::read_version(input)?;
+    let version = next(previous.clone())?;
+
+    write_to_dest(output, format, kind, &previous, &version)
+}
+
+/// Writes the new `version`, which was bumped by `kind` from `previous`, or reports the bump with
+/// the JSON or export format.
+fn write_to_dest<W: Write, V: SchemeVersion>(
+    writer: &mut W,
+    format: &Format,
+    kind: &str,
+    previous: &V,
+    version: &V,
+) -> Result<()> {
+    match format {
+        Format::Text => io::write_version(writer, version),
+        _ => report::bumped(writer, format, kind, previous, version, &[], false),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::cli::bump::test_helpers::{OutputReceiver, WriteableSender};
     use crate::cli::{
-        BuildMetadata, BumpStdinArgs, BumpStdinSetArgs, PreRelease, SetBuildMetadata,
-        SetPreRelease, SetVersion,
+        BuildMetadata, BumpStdinArgs, BumpStdinAutoArgs, BumpStdinPreArgs, BumpStdinReleaseArgs,
+        BumpStdinSetArgs, Format, PreRelease, SetBuildMetadata, SetPreRelease, SetVersion,
     };
-    use crate::version::Version;
+    use crate::version::Scheme;
     use std::io::Cursor;
+    use std::path::Path;
     use std::str::FromStr;
     use std::sync::mpsc;
 
@@ -90,7 +340,7 @@ mod tests {
             #[test]
             fn $name() {
                 let (args, output) = new_args($input, None, None);
-                run(args).unwrap();
+                run(args, &Format::Text).unwrap();
 
                 assert_eq!(output.into_string(), $output);
             }
@@ -104,7 +354,7 @@ mod tests {
             #[test]
             fn $name() {
                 let (args, output) = new_args($input, $pre, $build);
-                run(args).unwrap();
+                run(args, &Format::Text).unwrap();
 
                 assert_eq!(output.into_string(), $output);
             }
@@ -129,7 +379,35 @@ mod tests {
                     $no_pre_release,
                     $no_build_metadata,
                 );
-                run(args).unwrap();
+                run(args, &Format::Text).unwrap();
+
+                assert_eq!(output.into_string(), $output);
+            }
+        };
+    }
+
+    macro_rules! test_pre {
+        (
+            $name:ident, $input:expr, $channel:expr, $build:expr, $output:expr
+        ) => {
+            #[test]
+            fn $name() {
+                let (args, output) = new_pre_args($input, $channel, $build);
+                run(args, &Format::Text).unwrap();
+
+                assert_eq!(output.into_string(), $output);
+            }
+        };
+    }
+
+    macro_rules! test_release {
+        (
+            $name:ident, $input:expr, $build:expr, $allow_released:expr, $output:expr
+        ) => {
+            #[test]
+            fn $name() {
+                let (args, output) = new_release_args($input, $build, $allow_released);
+                run(args, &Format::Text).unwrap();
 
                 assert_eq!(output.into_string(), $output);
             }
@@ -220,6 +498,112 @@ mod tests {
         );
     }
 
+    mod breaking {
+        use super::super::breaking::run;
+        use super::*;
+
+        test!(stable, "1.2.3", "2.0.0\n");
+        test!(pre_major, "0.2.3", "0.3.0\n");
+        test!(pre_minor, "0.0.3", "0.0.4\n");
+    }
+
+    mod feature {
+        use super::super::feature::run;
+        use super::*;
+
+        test!(stable, "1.2.3", "1.3.0\n");
+        test!(pre_major, "0.2.3", "0.2.4\n");
+        test!(pre_minor, "0.0.3", "0.0.4\n");
+    }
+
+    mod fix {
+        use super::super::fix::run;
+        use super::*;
+
+        test!(stable, "1.2.3", "1.2.4\n");
+        test!(pre_major, "0.2.3", "0.2.4\n");
+        test!(pre_minor, "0.0.3", "0.0.4\n");
+    }
+
+    mod pre {
+        use super::super::pre::run;
+        use super::*;
+
+        test_pre!(counter, "1.2.0-rc.1", None, None, "1.2.0-rc.2\n");
+        test_pre!(no_counter, "1.2.0-rc", None, None, "1.2.0-rc.1\n");
+        test_pre!(
+            channel,
+            "1.2.0-alpha.3",
+            Some("beta"),
+            None,
+            "1.2.0-beta.1\n"
+        );
+        test_pre!(new_channel, "1.2.0\n", Some("rc"), None, "1.2.1-rc.1\n");
+        test_pre!(
+            build,
+            "1.2.0-rc.1",
+            None,
+            Some("build8"),
+            "1.2.0-rc.2+build8\n"
+        );
+
+        #[test]
+        fn no_pre_release() {
+            let (args, _output) = new_pre_args("1.2.0", None, None);
+
+            match run(args, &Format::Text) {
+                Err(err) => assert!(err.to_string().contains("no pre-release to bump")),
+                Ok(_) => panic!("should not bump a release"),
+            }
+        }
+    }
+
+    mod release {
+        use super::super::release::run;
+        use super::*;
+
+        test_release!(pre_release, "1.2.0-rc.2", None, false, "1.2.0\n");
+        test_release!(
+            pre_release_and_build,
+            "1.2.0-rc.2+b7",
+            None,
+            false,
+            "1.2.0\n"
+        );
+        test_release!(build, "1.2.0-rc.2", Some("b8"), false, "1.2.0+b8\n");
+        test_release!(allow_released, "1.2.0", None, true, "1.2.0\n");
+
+        #[test]
+        fn already_released() {
+            let (args, _output) = new_release_args("1.2.0", None, false);
+
+            match run(args, &Format::Text) {
+                Err(err) => assert!(err.to_string().contains("already a release")),
+                Ok(_) => panic!("should not release a release"),
+            }
+        }
+    }
+
+    mod auto {
+        use super::super::auto::run;
+        use super::*;
+        use crate::git::test_helpers::{commit, git, init};
+
+        #[test]
+        fn breaking() {
+            let repo = tempfile::tempdir().expect("tempdir should be created");
+            init(repo.path());
+            commit(repo.path(), "a", "feat: first");
+            git(repo.path(), &["tag", "v0.2.1"]);
+            commit(repo.path(), "b", "fix: second");
+            commit(repo.path(), "c", "feat!: third");
+            let (args, output) = new_auto_args("0.2.1\n", repo.path());
+            run(args, &Format::Text).unwrap();
+
+            assert_eq!(output.into_string(), "0.3.0\n");
+        }
+    }
+
     mod set {
         use super::super::set::run;
         use super::*;
@@ -348,6 +732,7 @@ mod tests {
         let (sender, receiver) = mpsc::channel();
 
         let args = BumpStdinArgs {
+            scheme: Scheme::Semver,
             pre,
             build,
             input: Box::new(Cursor::new(input.into())),
@@ -358,6 +743,61 @@ mod tests {
         (args, output_receiver)
     }
 
+    fn new_pre_args<S: Into<String>>(
+        input: S,
+        channel: Option<&str>,
+        build: Option<&str>,
+    ) -> (BumpStdinPreArgs, OutputReceiver) {
+        let (args, output_receiver) = new_args(input, channel, build);
+
+        let args = BumpStdinPreArgs {
+            scheme: Scheme::Semver,
+            channel: args.pre,
+            build: args.build,
+            input: args.input,
+            output: args.output,
+        };
+
+        (args, output_receiver)
+    }
+
+    fn new_release_args<S: Into<String>>(
+        input: S,
+        build: Option<&str>,
+        allow_released: bool,
+    ) -> (BumpStdinReleaseArgs, OutputReceiver) {
+        let (args, output_receiver) = new_args(input, None, build);
+
+        let args = BumpStdinReleaseArgs {
+            scheme: Scheme::Semver,
+            pre: args.pre,
+            build: args.build,
+            input: args.input,
+            output: args.output,
+            allow_released,
+        };
+
+        (args, output_receiver)
+    }
+
+    fn new_auto_args<S: Into<String>>(
+        input: S,
+        repo: &Path,
+    ) -> (BumpStdinAutoArgs, OutputReceiver) {
+        let (args, output_receiver) = new_args(input, None, None);
+
+        let args = BumpStdinAutoArgs {
+            scheme: Scheme::Semver,
+            pre: args.pre,
+            build: args.build,
+            input: args.input,
+            output: args.output,
+            repo: repo.to_path_buf(),
+        };
+
+        (args, output_receiver)
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn new_set_args<S: Into<String>>(
         input: S,
@@ -371,7 +811,7 @@ mod tests {
         no_build_metadata: bool,
     ) -> (BumpStdinSetArgs, OutputReceiver) {
         let set = if let Some(version) = version {
-            SetVersion::Version(Version::from_str(version).expect("should be valid version"))
+            SetVersion::Version(version.to_string())
         } else {
             SetVersion::Parts {
                 major,
@@ -402,6 +842,7 @@ mod tests {
         let (sender, receiver) = mpsc::channel();
 
         let args = BumpStdinSetArgs {
+            scheme: Scheme::Semver,
             input: Box::new(Cursor::new(input.into())),
             output: Box::new(WriteableSender::new(sender)),
             set,
@@ -0,0 +1,146 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::cli::{report, Expected, Format};
+use crate::error::VersioError;
+use crate::version::{SchemeVersion, Version};
+use crate::Result;
+use failure::bail;
+use log::info;
+use std::str::FromStr;
+
+pub(crate) mod cargo;
+pub(crate) mod file;
+pub(crate) mod stdin;
+
+/// Fails with a `VersioError::UnexpectedVersion` unless `version` is as `expected`.
+///
+/// With the JSON format, the outcome of the check is reported on standard output either way, while
+/// the export format only exports the version once it matches. A requirement can only be checked
+/// against a semantic version.
+fn check_version<V: SchemeVersion>(
+    version: &V,
+    expected: &Expected,
+    format: &Format,
+) -> Result<()> {
+    let (matches, expected) = match expected {
+        Expected::Version(expected) => {
+            let expected = V::parse(expected)?;
+            (*version == expected, expected.to_string())
+        }
+        Expected::Requirement(req) => match Version::from_str(&version.to_string()) {
+            Ok(semver) => (req.matches(&semver), req.to_string()),
+            Err(_) => bail!(
+                "requirements only apply to semantic versions, not {}, use --version instead",
+                version
+            ),
+        },
+    };
+    if *format == Format::Json {
+        report::write(
+            &mut std::io::stdout(),
+            &report::check(version, &expected, matches),
+        )?;
+    }
+    if !matches {
+        return Err(VersioError::UnexpectedVersion {
+            path: None,
+            found: version.to_string(),
+            expected,
+        }
+        .into());
+    }
+    info!(
+        "version matches; version={}, expected={}",
+        version, expected
+    );
+    if let Format::Export(path) = format {
+        report::export(
+            &mut std::io::stdout(),
+            path.as_deref(),
+            &report::exports(version, None),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pep440::Pep440Version;
+    use crate::version::VersionReq;
+
+    fn check(version: &str, expected: Expected) -> Result<()> {
+        check_version(
+            &Version::from_str(version).unwrap(),
+            &expected,
+            &Format::Text,
+        )
+    }
+
+    fn version(version: &str) -> Expected {
+        Expected::Version(version.to_string())
+    }
+
+    fn req(req: &str) -> Expected {
+        Expected::Requirement(VersionReq::from_str(req).unwrap())
+    }
+
+    #[test]
+    fn version_matches() {
+        assert!(check("1.4.0", version("1.4.0")).is_ok());
+        assert!(check("1.4.0+b8", version("1.4.0")).is_ok());
+    }
+
+    #[test]
+    fn version_differs() {
+        let err = check("1.4.0-rc.1", version("1.4.0")).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "version '1.4.0-rc.1' does not match '1.4.0'"
+        );
+    }
+
+    #[test]
+    fn pep440_version_matches() {
+        let version = Pep440Version::from_str("1.4.0rc1").unwrap();
+
+        assert!(check_version(&version, &self::version("1.4.0-rc.1"), &Format::Text).is_ok());
+        assert!(check_version(&version, &self::version("1.4.0"), &Format::Text).is_err());
+    }
+
+    #[test]
+    fn pep440_requirement() {
+        let err = check_version(
+            &Pep440Version::from_str("1.4.0rc1").unwrap(),
+            &req(">=1.4, <2"),
+            &Format::Text,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "requirements only apply to semantic versions, not 1.4.0rc1, use --version instead"
+        );
+    }
+
+    #[test]
+    fn requirement_matches() {
+        assert!(check("1.9.2", req(">=1.4, <2")).is_ok());
+    }
+
+    #[test]
+    fn requirement_unsatisfied() {
+        let err = check("2.0.0", req(">=1.4, <2")).unwrap_err();
+
+        assert_eq!(
+            err.downcast::<VersioError>()
+                .expect("should be a versio error")
+                .exit_code(),
+            8
+        );
+    }
+}
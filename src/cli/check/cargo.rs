@@ -0,0 +1,50 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::cargo;
+use crate::cli::{check, CheckCargoArgs, Format};
+use crate::error;
+use crate::Result;
+
+pub(crate) fn run(args: CheckCargoArgs, format: &Format) -> Result<()> {
+    let version = cargo::read_version(&args.input)?;
+
+    check::check_version(&version, &args.expected, format)
+        .map_err(|err| error::with_path(err, &args.input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use crate::cli::{CheckCargoArgs, Expected, Format};
+    use crate::version::VersionReq;
+    use std::fs;
+    use std::str::FromStr;
+
+    fn check(manifest: &str, expected: Expected) -> crate::Result<()> {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let input = dir.path().join("Cargo.toml");
+        fs::write(&input, manifest).unwrap();
+
+        run(CheckCargoArgs { expected, input }, &Format::Text)
+    }
+
+    #[test]
+    fn version() {
+        let expected = Expected::Version("1.4.0".to_string());
+
+        assert!(check("[package]\nversion = \"1.4.0\"\n", expected).is_ok());
+    }
+
+    #[test]
+    fn requirement_unsatisfied() {
+        let expected = Expected::Requirement(VersionReq::from_str(">=1.4, <2").unwrap());
+        let err = check("[package]\nversion = \"1.3.9\"\n", expected).unwrap_err();
+
+        assert!(err.to_string().starts_with("version '1.3.9' in "));
+        assert!(err
+            .to_string()
+            .ends_with("Cargo.toml does not match '>= 1.4, < 2'"));
+    }
+}
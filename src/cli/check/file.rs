@@ -0,0 +1,65 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::cli::{check, CheckFileArgs, Format};
+use crate::error;
+use crate::io;
+use crate::pep440::Pep440Version;
+use crate::version::{Scheme, Version};
+use crate::Result;
+
+pub(crate) fn run(args: CheckFileArgs, format: &Format) -> Result<()> {
+    match args.scheme {
+        Scheme::Semver => check::check_version(
+            &io::read_version_file::<Version>(&args.input)?,
+            &args.expected,
+            format,
+        ),
+        Scheme::Pep440 => check::check_version(
+            &io::read_version_file::<Pep440Version>(&args.input)?,
+            &args.expected,
+            format,
+        ),
+    }
+    .map_err(|err| error::with_path(err, &args.input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use crate::cli::{CheckFileArgs, Expected, Format};
+    use crate::version::Scheme;
+    use std::fs;
+    use std::path::PathBuf;
+    use tempfile::NamedTempFile;
+
+    fn check(input: &str, expected: &str, scheme: Scheme) -> crate::Result<()> {
+        let tempfile = NamedTempFile::new().expect("tempfile should be created");
+        fs::write(tempfile.path(), input).expect("input file content should be written");
+
+        run(
+            CheckFileArgs {
+                expected: Expected::Version(expected.to_string()),
+                scheme,
+                input: PathBuf::from(tempfile.path()),
+            },
+            &Format::Text,
+        )
+    }
+
+    #[test]
+    fn matches() {
+        assert!(check("1.4.0\n", "1.4.0", Scheme::Semver).is_ok());
+    }
+
+    #[test]
+    fn matches_pep440() {
+        assert!(check("1.4.0.post1\n", "1.4.0-post1", Scheme::Pep440).is_ok());
+    }
+
+    #[test]
+    fn differs() {
+        assert!(check("1.4.1\n", "1.4.0", Scheme::Semver).is_err());
+    }
+}
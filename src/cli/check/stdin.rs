@@ -0,0 +1,54 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::cli::{check, CheckStdinArgs, Format};
+use crate::io;
+use crate::pep440::Pep440Version;
+use crate::version::{Scheme, Version};
+use crate::Result;
+
+pub(crate) fn run(mut args: CheckStdinArgs, format: &Format) -> Result<()> {
+    match args.scheme {
+        Scheme::Semver => check::check_version(
+            &io::read_version::<Version, _>(&mut args.input)?,
+            &args.expected,
+            format,
+        ),
+        Scheme::Pep440 => check::check_version(
+            &io::read_version::<Pep440Version, _>(&mut args.input)?,
+            &args.expected,
+            format,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use crate::cli::{CheckStdinArgs, Expected, Format};
+    use crate::version::{Scheme, VersionReq};
+    use std::io::Cursor;
+    use std::str::FromStr;
+
+    fn check(input: &str, req: &str) -> crate::Result<()> {
+        run(
+            CheckStdinArgs {
+                expected: Expected::Requirement(VersionReq::from_str(req).unwrap()),
+                scheme: Scheme::Semver,
+                input: Box::new(Cursor::new(input.to_string())),
+            },
+            &Format::Text,
+        )
+    }
+
+    #[test]
+    fn satisfied() {
+        assert!(check("1.4.0\n", "^1.2").is_ok());
+    }
+
+    #[test]
+    fn unsatisfied() {
+        assert!(check("2.0.0\n", "^1.2").is_err());
+    }
+}
@@ -0,0 +1,99 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::cli::{report, Format, VersionPart};
+use crate::version::SchemeVersion;
+use crate::Result;
+use std::io::Write;
+
+pub(crate) mod cargo;
+pub(crate) mod file;
+pub(crate) mod stdin;
+
+/// Writes `part` of `version` to `writer`, or the whole version and its parts with the JSON or
+/// export format.
+fn write_version<W: Write, V: SchemeVersion>(
+    writer: &mut W,
+    version: &V,
+    part: VersionPart,
+    format: &Format,
+) -> Result<()> {
+    match format {
+        Format::Text => write_part(writer, version, part),
+        Format::Json => report::write(writer, &report::version(version)),
+        Format::Export(path) => {
+            report::export(writer, path.as_deref(), &report::exports(version, None))
+        }
+    }
+}
+
+/// Writes `part` of `version` to `writer` on a line of its own.
+fn write_part<W: Write, V: SchemeVersion>(
+    writer: &mut W,
+    version: &V,
+    part: VersionPart,
+) -> Result<()> {
+    let components = version.components();
+    let text = match part {
+        VersionPart::All => version.to_string(),
+        VersionPart::Major => components.major.to_string(),
+        VersionPart::Minor => components.minor.to_string(),
+        VersionPart::Patch => components.patch.to_string(),
+        VersionPart::PreRelease => components.pre.unwrap_or_default(),
+        VersionPart::BuildMetadata => components.build.unwrap_or_default(),
+        VersionPart::MajorMinor => format!("{}.{}", components.major, components.minor),
+    };
+
+    Ok(writeln!(writer, "{}", text)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pep440::Pep440Version;
+    use crate::version::Version;
+
+    fn part(version: &str, part: VersionPart) -> String {
+        let mut buf = Vec::new();
+        write_part(&mut buf, &Version::parse(version).unwrap(), part).unwrap();
+
+        String::from_utf8(buf).expect("output should be valid utf-8")
+    }
+
+    fn pep440_part(version: &str, part: VersionPart) -> String {
+        let mut buf = Vec::new();
+        write_part(&mut buf, &Pep440Version::parse(version).unwrap(), part).unwrap();
+
+        String::from_utf8(buf).expect("output should be valid utf-8")
+    }
+
+    #[test]
+    fn all() {
+        assert_eq!(part("1.2.3-rc.1+b8", VersionPart::All), "1.2.3-rc.1+b8\n");
+    }
+
+    #[test]
+    fn components() {
+        assert_eq!(part("1.2.3-rc.1+b8", VersionPart::Major), "1\n");
+        assert_eq!(part("1.2.3-rc.1+b8", VersionPart::Minor), "2\n");
+        assert_eq!(part("1.2.3-rc.1+b8", VersionPart::Patch), "3\n");
+        assert_eq!(part("1.2.3-rc.1+b8", VersionPart::PreRelease), "rc.1\n");
+        assert_eq!(part("1.2.3-rc.1+b8", VersionPart::BuildMetadata), "b8\n");
+        assert_eq!(part("1.2.3-rc.1+b8", VersionPart::MajorMinor), "1.2\n");
+    }
+
+    #[test]
+    fn pep440_components() {
+        assert_eq!(pep440_part("1.2rc1+b8", VersionPart::All), "1.2rc1+b8\n");
+        assert_eq!(pep440_part("1.2rc1+b8", VersionPart::Patch), "0\n");
+        assert_eq!(pep440_part("1.2rc1+b8", VersionPart::PreRelease), "rc1\n");
+        assert_eq!(pep440_part("1.2rc1+b8", VersionPart::BuildMetadata), "b8\n");
+    }
+
+    #[test]
+    fn empty_components() {
+        assert_eq!(part("1.2.3", VersionPart::PreRelease), "\n");
+        assert_eq!(part("1.2.3", VersionPart::BuildMetadata), "\n");
+    }
+}
@@ -0,0 +1,67 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::cargo;
+use crate::cli::{get, Format, GetCargoArgs};
+use crate::Result;
+
+pub(crate) fn run(mut args: GetCargoArgs, format: &Format) -> Result<()> {
+    let version = cargo::read_version(&args.input)?;
+
+    get::write_version(&mut args.output, &version, args.part, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use crate::cli::bump::test_helpers::{OutputReceiver, WriteableSender};
+    use crate::cli::{Format, GetCargoArgs, VersionPart};
+    use std::fs;
+    use std::path::Path;
+    use std::sync::mpsc;
+
+    fn new_args(input: &Path, part: VersionPart) -> (GetCargoArgs, OutputReceiver) {
+        let (sender, receiver) = mpsc::channel();
+
+        let args = GetCargoArgs {
+            part,
+            input: input.to_path_buf(),
+            output: Box::new(WriteableSender::new(sender)),
+        };
+
+        (args, OutputReceiver::new(receiver))
+    }
+
+    #[test]
+    fn version() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("Cargo.toml");
+        fs::write(&path, "[package]\nversion = \"0.4.1-rc.2\"\n").unwrap();
+        let (args, output) = new_args(&path, VersionPart::PreRelease);
+        run(args, &Format::Text).unwrap();
+
+        assert_eq!(output.into_string(), "rc.2\n");
+    }
+
+    #[test]
+    fn inherited() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"one\"]\n\n[workspace.package]\nversion = \"2.1.0\"\n",
+        )
+        .unwrap();
+        fs::create_dir(dir.path().join("one")).unwrap();
+        let path = dir.path().join("one/Cargo.toml");
+        fs::write(
+            &path,
+            "[package]\nname = \"one\"\nversion.workspace = true\n",
+        )
+        .unwrap();
+        let (args, output) = new_args(&path, VersionPart::All);
+        run(args, &Format::Text).unwrap();
+
+        assert_eq!(output.into_string(), "2.1.0\n");
+    }
+}
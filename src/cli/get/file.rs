@@ -0,0 +1,66 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::cli::{get, Format, GetFileArgs};
+use crate::io;
+use crate::pep440::Pep440Version;
+use crate::version::{Scheme, Version};
+use crate::Result;
+
+pub(crate) fn run(mut args: GetFileArgs, format: &Format) -> Result<()> {
+    match args.scheme {
+        Scheme::Semver => {
+            let version = io::read_version_file::<Version>(&args.input)?;
+            get::write_version(&mut args.output, &version, args.part, format)
+        }
+        Scheme::Pep440 => {
+            let version = io::read_version_file::<Pep440Version>(&args.input)?;
+            get::write_version(&mut args.output, &version, args.part, format)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use crate::cli::bump::test_helpers::{OutputReceiver, WriteableSender};
+    use crate::cli::{Format, GetFileArgs, VersionPart};
+    use crate::version::Scheme;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::mpsc;
+    use tempfile::NamedTempFile;
+
+    fn new_args(input: &str, part: VersionPart) -> (GetFileArgs, OutputReceiver, NamedTempFile) {
+        let tempfile = NamedTempFile::new().expect("tempfile should be created");
+        fs::write(tempfile.path(), input).expect("input file content should be written");
+        let (sender, receiver) = mpsc::channel();
+
+        let args = GetFileArgs {
+            scheme: Scheme::Semver,
+            part,
+            input: PathBuf::from(tempfile.path()),
+            output: Box::new(WriteableSender::new(sender)),
+        };
+
+        (args, OutputReceiver::new(receiver), tempfile)
+    }
+
+    #[test]
+    fn all() {
+        let (args, output, tempfile) = new_args("1.2.3+b8\n", VersionPart::All);
+        run(args, &Format::Text).unwrap();
+
+        assert_eq!(output.into_string(), "1.2.3+b8\n");
+        assert_eq!(fs::read_to_string(tempfile.path()).unwrap(), "1.2.3+b8\n");
+    }
+
+    #[test]
+    fn patch() {
+        let (args, output, _tempfile) = new_args("1.2.3+b8\n", VersionPart::Patch);
+        run(args, &Format::Text).unwrap();
+
+        assert_eq!(output.into_string(), "3\n");
+    }
+}
@@ -0,0 +1,61 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::cli::{get, Format, GetStdinArgs};
+use crate::io;
+use crate::pep440::Pep440Version;
+use crate::version::{Scheme, Version};
+use crate::Result;
+
+pub(crate) fn run(mut args: GetStdinArgs, format: &Format) -> Result<()> {
+    match args.scheme {
+        Scheme::Semver => {
+            let version = io::read_version::<Version, _>(&mut args.input)?;
+            get::write_version(&mut args.output, &version, args.part, format)
+        }
+        Scheme::Pep440 => {
+            let version = io::read_version::<Pep440Version, _>(&mut args.input)?;
+            get::write_version(&mut args.output, &version, args.part, format)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use crate::cli::bump::test_helpers::{OutputReceiver, WriteableSender};
+    use crate::cli::{Format, GetStdinArgs, VersionPart};
+    use crate::version::Scheme;
+    use std::io::Cursor;
+    use std::sync::mpsc;
+
+    fn new_args(input: &str, part: VersionPart) -> (GetStdinArgs, OutputReceiver) {
+        let (sender, receiver) = mpsc::channel();
+
+        let args = GetStdinArgs {
+            scheme: Scheme::Semver,
+            part,
+            input: Box::new(Cursor::new(input.to_string())),
+            output: Box::new(WriteableSender::new(sender)),
+        };
+
+        (args, OutputReceiver::new(receiver))
+    }
+
+    #[test]
+    fn all() {
+        let (args, output) = new_args("  1.2.3-rc.1\n", VersionPart::All);
+        run(args, &Format::Text).unwrap();
+
+        assert_eq!(output.into_string(), "1.2.3-rc.1\n");
+    }
+
+    #[test]
+    fn major_minor() {
+        let (args, output) = new_args("1.2.3-rc.1\n", VersionPart::MajorMinor);
+        run(args, &Format::Text).unwrap();
+
+        assert_eq!(output.into_string(), "1.2\n");
+    }
+}
@@ -0,0 +1,290 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::cli::Format;
+use crate::io;
+use crate::json::Value;
+use crate::source::{Edit, Source};
+use crate::version::SchemeVersion;
+use crate::Result;
+use failure::format_err;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Describes a bump of `kind` from the `previous` to the `next` version, which wrote `files`, or
+/// would have written them in a dry run.
+pub(crate) fn bump<V: SchemeVersion>(
+    kind: &str,
+    previous: &V,
+    next: &V,
+    files: &[PathBuf],
+    dry_run: bool,
+) -> Value {
+    Value::Object(vec![
+        ("bump", Value::String(kind.to_string())),
+        ("previous", Value::String(previous.to_string())),
+        ("next", Value::String(next.to_string())),
+        ("components", components(next)),
+        (
+            "files",
+            Value::Array(
+                files
+                    .iter()
+                    .map(|path| Value::String(path.display().to_string()))
+                    .collect(),
+            ),
+        ),
+        ("dry_run", Value::Bool(dry_run)),
+    ])
+}
+
+/// Describes the `version` which was read.
+pub(crate) fn version<V: SchemeVersion>(version: &V) -> Value {
+    Value::Object(vec![
+        ("version", Value::String(version.to_string())),
+        ("components", components(version)),
+    ])
+}
+
+/// Describes a check of `version` against what was `expected`.
+pub(crate) fn check<V: SchemeVersion>(version: &V, expected: &str, matches: bool) -> Value {
+    Value::Object(vec![
+        ("version", Value::String(version.to_string())),
+        ("expected", Value::String(expected.to_string())),
+        ("matches", Value::Bool(matches)),
+    ])
+}
+
+/// Describes the version `found` in each source, along with the version they share, if they
+/// agree.
+pub(crate) fn verify<V: SchemeVersion>(found: &[(&Source, V)]) -> Value {
+    let first = &found[0].1;
+    let agree = found.iter().all(|(_, version)| version == first);
+
+    Value::Object(vec![
+        ("version", Some(first.to_string()).filter(|_| agree).into()),
+        ("agree", Value::Bool(agree)),
+        (
+            "sources",
+            Value::Array(
+                found
+                    .iter()
+                    .map(|(source, version)| {
+                        Value::Object(vec![
+                            ("kind", Value::String(source.kind().to_string())),
+                            ("path", Value::String(source.path().display().to_string())),
+                            ("version", Value::String(version.to_string())),
+                        ])
+                    })
+                    .collect(),
+            ),
+        ),
+    ])
+}
+
+/// Returns the parts of `version`.
+fn components<V: SchemeVersion>(version: &V) -> Value {
+    let components = version.components();
+
+    Value::Object(vec![
+        ("major", Value::Number(components.major)),
+        ("minor", Value::Number(components.minor)),
+        ("patch", Value::Number(components.patch)),
+        ("pre", components.pre.into()),
+        ("build", components.build.into()),
+    ])
+}
+
+/// Returns the shell variables describing `version`, along with the `previous` one after a bump.
+///
+/// A missing pre-release is an empty `VERSIO_PRE`, so that every variable is always set.
+pub(crate) fn exports<V: SchemeVersion>(
+    version: &V,
+    previous: Option<&V>,
+) -> Vec<(&'static str, String)> {
+    let components = version.components();
+    let mut exports = vec![
+        ("VERSIO_VERSION", version.to_string()),
+        ("VERSIO_MAJOR", components.major.to_string()),
+        ("VERSIO_MINOR", components.minor.to_string()),
+        ("VERSIO_PATCH", components.patch.to_string()),
+        ("VERSIO_PRE", components.pre.unwrap_or_default()),
+    ];
+    if let Some(previous) = previous {
+        exports.push(("VERSIO_PREVIOUS", previous.to_string()));
+    }
+
+    exports
+}
+
+/// Writes an `export` line for each of `exports` to `writer`, then appends them as `KEY=VALUE`
+/// lines to the file at `path`, if any.
+pub(crate) fn export<W: Write + ?Sized>(
+    writer: &mut W,
+    path: Option<&Path>,
+    exports: &[(&'static str, String)],
+) -> Result<()> {
+    for (key, value) in exports {
+        writeln!(writer, "export {}={}", key, shell_quote(value))?;
+    }
+    if let Some(path) = path {
+        let lines: String = exports
+            .iter()
+            .map(|(key, value)| format!("{}={}\n", key, value))
+            .collect();
+        io::append_file(path, &lines)?;
+    }
+
+    Ok(())
+}
+
+/// Quotes `value` for a POSIX shell, within single quotes which are ended around each single quote
+/// of `value` to escape it.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Reports a bump of `kind` from the `previous` to the `next` version to `writer` in `format`,
+/// which says nothing more for text.
+pub(crate) fn bumped<W: Write + ?Sized, V: SchemeVersion>(
+    writer: &mut W,
+    format: &Format,
+    kind: &str,
+    previous: &V,
+    next: &V,
+    files: &[PathBuf],
+    dry_run: bool,
+) -> Result<()> {
+    match format {
+        Format::Text => Ok(()),
+        Format::Json => write(writer, &bump(kind, previous, next, files, dry_run)),
+        Format::Export(path) => export(writer, path.as_deref(), &exports(next, Some(previous))),
+    }
+}
+
+/// Writes each line noted by `edit` to `writer`, as `updating <path> line <n>: <line>`.
+pub(crate) fn edited<W: Write + ?Sized>(writer: &mut W, edit: &Edit) -> Result<()> {
+    let lines: Vec<&str> = edit.contents.lines().collect();
+    for &line in &edit.lines {
+        writeln!(
+            writer,
+            "updating {} line {}: {}",
+            edit.path.display(),
+            line,
+            lines[line - 1].trim()
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Writes `value` to `writer` on a line of its own.
+pub(crate) fn write<W: Write + ?Sized>(writer: &mut W, value: &Value) -> Result<()> {
+    writeln!(writer, "{}", value)?;
+
+    Ok(())
+}
+
+/// Returns the error for asking for both the new contents of files and a report on standard
+/// output, which could not be told apart.
+pub(crate) fn stdout_conflict() -> failure::Error {
+    format_err!("--stdout cannot be used with --format json or --export")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pep440::Pep440Version;
+    use crate::version::Version;
+    use std::path::Path;
+
+    #[test]
+    fn bump_report() {
+        let previous = Version::parse("1.2.3").unwrap();
+        let next = Version::parse("1.3.0-rc.1+b8").unwrap();
+
+        assert_eq!(
+            bump("minor", &previous, &next, &[PathBuf::from("Cargo.toml")], false).to_string(),
+            "{\"bump\":\"minor\",\"previous\":\"1.2.3\",\"next\":\"1.3.0-rc.1+b8\",\
+             \"components\":{\"major\":1,\"minor\":3,\"patch\":0,\"pre\":\"rc.1\",\"build\":\"b8\"},\
+             \"files\":[\"Cargo.toml\"],\"dry_run\":false}"
+        );
+    }
+
+    #[test]
+    fn pep440_components() {
+        let version = Pep440Version::parse("2!1.4rc2.post1.dev3+ubuntu.1").unwrap();
+
+        assert_eq!(
+            components(&version).to_string(),
+            r#"{"major":1,"minor":4,"patch":0,"pre":"rc2.post1.dev3","build":"ubuntu.1"}"#
+        );
+    }
+
+    #[test]
+    fn edited_lines() {
+        let edit = Edit {
+            path: PathBuf::from("Dockerfile"),
+            contents: "FROM scratch\nARG VERSION=1.3.0\n  LABEL v=1.3.0 \\\n".to_string(),
+            lines: vec![2, 3],
+        };
+        let mut buf = Vec::new();
+        edited(&mut buf, &edit).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "updating Dockerfile line 2: ARG VERSION=1.3.0\n\
+             updating Dockerfile line 3: LABEL v=1.3.0 \\\n"
+        );
+    }
+
+    #[test]
+    fn quote() {
+        assert_eq!(shell_quote("1.2.3"), "'1.2.3'");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("it's $HOME"), "'it'\\''s $HOME'");
+    }
+
+    #[test]
+    fn export_lines() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("output");
+        let version = Version::parse("2.0.0-beta.1+b8").unwrap();
+        let mut lines = Vec::new();
+        export(&mut lines, Some(&path), &exports(&version, None)).unwrap();
+        export(&mut Vec::new(), Some(&path), &exports(&version, None)).unwrap();
+
+        assert_eq!(
+            String::from_utf8(lines).unwrap(),
+            "export VERSIO_VERSION='2.0.0-beta.1+b8'\n\
+             export VERSIO_MAJOR='2'\n\
+             export VERSIO_MINOR='0'\n\
+             export VERSIO_PATCH='0'\n\
+             export VERSIO_PRE='beta.1'\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "VERSIO_VERSION=2.0.0-beta.1+b8\nVERSIO_MAJOR=2\nVERSIO_MINOR=0\nVERSIO_PATCH=0\n\
+             VERSIO_PRE=beta.1\n"
+                .repeat(2)
+        );
+    }
+
+    #[test]
+    fn verify_disagree() {
+        let cargo = Source::Cargo(Path::new("Cargo.toml").to_path_buf());
+        let file = Source::File(Path::new("VERSION").to_path_buf());
+        let found = vec![
+            (&cargo, Version::parse("1.2.3").unwrap()),
+            (&file, Version::parse("1.2.4").unwrap()),
+        ];
+
+        assert_eq!(
+            verify(&found).to_string(),
+            "{\"version\":null,\"agree\":false,\"sources\":[\
+             {\"kind\":\"cargo\",\"path\":\"Cargo.toml\",\"version\":\"1.2.3\"},\
+             {\"kind\":\"file\",\"path\":\"VERSION\",\"version\":\"1.2.4\"}]}"
+        );
+    }
+}
@@ -2,6 +2,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use crate::error::VersioError;
 use chrono::{SecondsFormat, Utc};
 use std::env;
 use std::io;
@@ -100,6 +101,16 @@ pub(crate) fn is_pipe_error(err: &failure::Error) -> bool {
     false
 }
 
+/// Returns the process exit code for an error, which is that of the first `VersioError` in its
+/// causal chain, or 1 for any other error.
+pub(crate) fn exit_code(err: &failure::Error) -> i32 {
+    err.iter_chain()
+        .filter_map(|cause| cause.downcast_ref::<VersioError>())
+        .map(VersioError::exit_code)
+        .next()
+        .unwrap_or(1)
+}
+
 /// Return a prettily formatted error, including its entire causal chain.
 ///
 /// Thanks again to the imdb-rename crate and wasm-pack which form the basis of this
@@ -0,0 +1,207 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::cli::{report, Format, VerifyArgs};
+use crate::error::VersioError;
+use crate::pep440::Pep440Version;
+use crate::source::Source;
+use crate::version::{Scheme, SchemeVersion, Version};
+use crate::Result;
+use std::io::Write;
+
+pub(crate) fn run(mut args: VerifyArgs, format: &Format) -> Result<()> {
+    match args.sources.load(args.scheme)? {
+        (sources, Scheme::Semver) => verify::<Version, _>(&mut args.output, &sources, format),
+        (sources, Scheme::Pep440) => verify::<Pep440Version, _>(&mut args.output, &sources, format),
+    }
+}
+
+fn verify<V: SchemeVersion, W: Write>(
+    writer: &mut W,
+    sources: &[Source],
+    format: &Format,
+) -> Result<()> {
+    let mut found = Vec::with_capacity(sources.len());
+    for source in sources {
+        found.push((source, source.read_version::<V>()?));
+    }
+
+    match format {
+        Format::Text => write_table(writer, &found)?,
+        Format::Json => report::write(writer, &report::verify(&found))?,
+        Format::Export(_) => {}
+    }
+    agree(&found)?;
+
+    // The shared version is only exported once the sources are known to agree on it
+    match format {
+        Format::Export(path) => {
+            report::export(writer, path.as_deref(), &report::exports(&found[0].1, None))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Fails unless the same version was found in every source.
+pub(crate) fn agree<V: SchemeVersion>(found: &[(&Source, V)]) -> Result<()> {
+    let first = &found[0].1;
+    if found.iter().all(|(_, version)| version == first) {
+        return Ok(());
+    }
+    Err(VersioError::VersionsDisagree {
+        found: found
+            .iter()
+            .map(|(source, version)| (source.path().to_path_buf(), version.to_string()))
+            .collect(),
+    }
+    .into())
+}
+
+/// Writes a table of each source with the version which was found in it.
+fn write_table<V: SchemeVersion, W: Write>(writer: &mut W, found: &[(&Source, V)]) -> Result<()> {
+    let rows: Vec<[String; 3]> = found
+        .iter()
+        .map(|(source, version)| {
+            [
+                source.kind().to_string(),
+                source.path().display().to_string(),
+                version.to_string(),
+            ]
+        })
+        .collect();
+    let header = [
+        "KIND".to_string(),
+        "PATH".to_string(),
+        "VERSION".to_string(),
+    ];
+    let kind_width = rows
+        .iter()
+        .map(|row| row[0].len())
+        .fold(header[0].len(), usize::max);
+    let path_width = rows
+        .iter()
+        .map(|row| row[1].len())
+        .fold(header[1].len(), usize::max);
+
+    for row in Some(&header).into_iter().chain(&rows) {
+        writeln!(
+            writer,
+            "{:kind_width$}  {:path_width$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            kind_width = kind_width,
+            path_width = path_width
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use crate::cli::bump::test_helpers::{OutputReceiver, WriteableSender};
+    use crate::cli::{Format, SelectSources, VerifyArgs};
+    use crate::error::VersioError;
+    use crate::pattern::Pattern;
+    use crate::source::Source;
+    use std::fs;
+    use std::path::Path;
+    use std::str::FromStr;
+    use std::sync::mpsc;
+
+    fn sources(dir: &Path, file: &str) -> Vec<Source> {
+        fs::write(dir.join("Cargo.toml"), "[package]\nversion = \"1.2.3\"\n").unwrap();
+        fs::write(dir.join("VERSION.txt"), file).unwrap();
+        fs::write(dir.join("lib.rs"), "const VERSION: &str = \"1.2.3\";\n").unwrap();
+
+        vec![
+            Source::Cargo(dir.join("Cargo.toml")),
+            Source::File(dir.join("VERSION.txt")),
+            Source::Pattern(
+                dir.join("lib.rs"),
+                Pattern::from_str(r#"VERSION: &str = "(?P<version>[^"]+)""#).unwrap(),
+            ),
+        ]
+    }
+
+    fn new_args(sources: Vec<Source>) -> (VerifyArgs, OutputReceiver) {
+        let (sender, receiver) = mpsc::channel();
+
+        let args = VerifyArgs {
+            scheme: None,
+            sources: SelectSources::Given(sources),
+            output: Box::new(WriteableSender::new(sender)),
+        };
+
+        (args, OutputReceiver::new(receiver))
+    }
+
+    #[test]
+    fn agree() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let (args, output) = new_args(sources(dir.path(), "1.2.3\n"));
+        run(args, &Format::Text).unwrap();
+        let file = dir.path().join("VERSION.txt").display().to_string();
+        let width = file.len();
+
+        assert_eq!(
+            output.into_string(),
+            format!(
+                "KIND     {:width$}  VERSION\n\
+                 cargo    {:width$}  1.2.3\n\
+                 file     {:width$}  1.2.3\n\
+                 pattern  {:width$}  1.2.3\n",
+                "PATH",
+                dir.path().join("Cargo.toml").display(),
+                file,
+                dir.path().join("lib.rs").display(),
+                width = width
+            )
+        );
+    }
+
+    #[test]
+    fn disagree() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let (args, output) = new_args(sources(dir.path(), "1.2.4\n"));
+        let err = run(args, &Format::Text).unwrap_err();
+
+        assert!(output.into_string().contains("1.2.4\n"));
+        match err.downcast::<VersioError>() {
+            Ok(err) => {
+                assert_eq!(err.exit_code(), 9);
+                assert_eq!(
+                    err.to_string(),
+                    format!(
+                        "versions disagree: 1.2.3 in {}, 1.2.4 in {}, 1.2.3 in {}",
+                        dir.path().join("Cargo.toml").display(),
+                        dir.path().join("VERSION.txt").display(),
+                        dir.path().join("lib.rs").display()
+                    )
+                );
+            }
+            Err(err) => panic!("should be a versio error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn config() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        sources(dir.path(), "1.2.3\n");
+        let config = dir.path().join(".versio.toml");
+        fs::write(
+            &config,
+            "[[source]]\nkind = \"cargo\"\npath = \"Cargo.toml\"\n\n\
+             [[source]]\nkind = \"file\"\npath = \"VERSION.txt\"\n",
+        )
+        .unwrap();
+        let (mut args, output) = new_args(Vec::new());
+        args.sources = SelectSources::Config(Some(config));
+        run(args, &Format::Text).unwrap();
+
+        assert_eq!(output.into_string().lines().count(), 3);
+    }
+}
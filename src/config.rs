@@ -0,0 +1,336 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::error::{self, VersioError};
+use crate::helm::AppVersion;
+use crate::io;
+use crate::pattern::Pattern;
+use crate::source::Source;
+use crate::version::Scheme;
+use crate::Result;
+use failure::{bail, format_err};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use toml_edit::{Document, Item, Table};
+
+/// The name of the project configuration file.
+pub const CONFIG_FILE: &str = ".versio.toml";
+
+/// A project configuration, which lists every file holding the version and how to edit it.
+///
+/// Each file is a `[[source]]` table with a `kind` and a `path`, relative to the directory of the
+/// configuration file:
+///
+/// ```toml
+/// [[source]]
+/// kind = "cargo"
+/// path = "Cargo.toml"
+///
+/// [[source]]
+/// kind = "pattern"
+/// path = "install.sh"
+/// pattern = 'VERSION=(?P<version>\S+)'
+/// max-matches = 2
+/// ```
+///
+/// The versions follow Semantic Versioning unless a top-level `scheme` says otherwise, or every
+/// source is a `pyproject`, whose versions follow PEP 440.
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub path: PathBuf,
+    pub scheme: Option<Scheme>,
+    pub sources: Vec<Source>,
+}
+
+impl Config {
+    /// Loads the configuration file at `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = io::read_text_file(path)?;
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        let (scheme, sources) = parse(&text, dir).map_err(|err| error::with_path(err, path))?;
+
+        Ok(Config {
+            path: path.to_path_buf(),
+            scheme,
+            sources,
+        })
+    }
+
+    /// Loads the configuration file in the current directory or the nearest of its parents.
+    pub fn find() -> Result<Self> {
+        let cwd = env::current_dir()?;
+        Self::find_from(Path::new(""), &cwd)
+    }
+
+    /// Loads the configuration file in `start` or the nearest of its parents, where `absolute` is
+    /// `start` as an absolute path. The paths of sources are kept relative to `start`.
+    fn find_from(start: &Path, absolute: &Path) -> Result<Self> {
+        let mut dir = start.to_path_buf();
+        for _ in absolute.ancestors() {
+            let candidate = dir.join(CONFIG_FILE);
+            if candidate.is_file() {
+                return Self::load(&candidate);
+            }
+            dir.push("..");
+        }
+
+        bail!(
+            "cannot find {} in {} or any of its parents",
+            CONFIG_FILE,
+            absolute.display()
+        )
+    }
+}
+
+/// Parses the version scheme and the sources of a configuration, resolving the paths of the
+/// sources against `dir`.
+fn parse(text: &str, dir: &Path) -> Result<(Option<Scheme>, Vec<Source>)> {
+    let document = Document::from_str(text).map_err(|err| VersioError::toml(text, &err))?;
+    let scheme = if document.contains_key("scheme") {
+        Some(Scheme::from_str(string(document.as_table(), "scheme")?)?)
+    } else {
+        None
+    };
+
+    Ok((scheme, parse_sources(&document, dir)?))
+}
+
+/// Parses the `[[source]]` tables of a configuration, resolving their paths against `dir`.
+fn parse_sources(document: &Document, dir: &Path) -> Result<Vec<Source>> {
+    let tables = match document.get("source") {
+        Some(Item::ArrayOfTables(tables)) => tables,
+        Some(_) => bail!("'source' must be an array of tables, such as [[source]]"),
+        None => bail!("no files are listed, add a [[source]] table for each"),
+    };
+
+    tables
+        .iter()
+        .enumerate()
+        .map(|(index, table)| {
+            parse_source(table, dir)
+                .map_err(|err| format_err!("invalid source {}: {}", index + 1, err))
+        })
+        .collect()
+}
+
+fn parse_source(table: &Table, dir: &Path) -> Result<Source> {
+    let path = dir.join(string(table, "path")?);
+
+    match string(table, "kind")? {
+        "cargo" => Ok(Source::Cargo(path)),
+        "file" => Ok(Source::File(path)),
+        "npm" => Ok(Source::Npm(path)),
+        "pyproject" => Ok(Source::PyProject(path)),
+        "maven" => Ok(Source::Maven(path)),
+        "gradle" => Ok(Source::Gradle(path)),
+        "dockerfile" => Ok(Source::Dockerfile(path)),
+        "helm" => {
+            let app_version = match (table.get("app-version"), table.get("app-version-from")) {
+                (Some(_), Some(_)) => {
+                    bail!("'app-version' and 'app-version-from' cannot both be given")
+                }
+                (Some(app_version), None) => match app_version.as_bool() {
+                    Some(true) => AppVersion::Chart,
+                    Some(false) => AppVersion::Keep,
+                    None => bail!("'app-version' must be a boolean"),
+                },
+                (None, Some(_)) => AppVersion::Crate(dir.join(string(table, "app-version-from")?)),
+                (None, None) => AppVersion::Keep,
+            };
+
+            Ok(Source::Helm(path, app_version))
+        }
+        "pattern" => {
+            let mut pattern = Pattern::from_str(string(table, "pattern")?)?;
+            if let Some(max_matches) = table.get("max-matches") {
+                match max_matches.as_integer() {
+                    Some(max_matches) if max_matches > 0 => {
+                        pattern = pattern.with_max_matches(max_matches as usize)
+                    }
+                    _ => bail!("'max-matches' must be a positive integer"),
+                }
+            }
+
+            Ok(Source::Pattern(path, pattern))
+        }
+        kind => bail!(
+            "unknown kind '{}', expected one of cargo, dockerfile, file, gradle, helm, maven, npm, \
+             pattern or pyproject",
+            kind
+        ),
+    }
+}
+
+/// Returns the string value under `key`, failing if it is missing or not a string.
+fn string<'a>(table: &'a Table, key: &str) -> Result<&'a str> {
+    match table.get(key).map(Item::as_str) {
+        Some(Some(value)) => Ok(value),
+        Some(None) => bail!("'{}' must be a string", key),
+        None => bail!("'{}' is missing", key),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn parse_sources(text: &str, dir: &Path) -> Result<Vec<Source>> {
+        super::parse(text, dir).map(|(_, sources)| sources)
+    }
+
+    fn paths(config: &Config) -> Vec<(&str, PathBuf)> {
+        config
+            .sources
+            .iter()
+            .map(|source| (source.kind(), source.path().to_path_buf()))
+            .collect()
+    }
+
+    #[test]
+    fn parse() {
+        let sources = parse_sources(
+            "[[source]]\nkind = \"cargo\"\npath = \"Cargo.toml\"\n\n\
+             [[source]]\nkind = \"pattern\"\npath = \"bin/install.sh\"\n\
+             pattern = 'VERSION=(?P<version>\\S+)'\n",
+            Path::new("project"),
+        )
+        .unwrap();
+
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[0].path(), Path::new("project/Cargo.toml"));
+        assert_eq!(sources[1].kind(), "pattern");
+        assert_eq!(sources[1].path(), Path::new("project/bin/install.sh"));
+    }
+
+    #[test]
+    fn parse_scheme() {
+        let source = "[[source]]\nkind = \"file\"\npath = \"VERSION.txt\"\n";
+
+        assert_eq!(super::parse(source, Path::new("")).unwrap().0, None);
+        assert_eq!(
+            super::parse(&format!("scheme = \"pep440\"\n\n{}", source), Path::new(""))
+                .unwrap()
+                .0,
+            Some(Scheme::Pep440)
+        );
+        assert_eq!(
+            super::parse(&format!("scheme = \"calver\"\n\n{}", source), Path::new(""))
+                .unwrap_err()
+                .to_string(),
+            "unknown version scheme 'calver', expected semver or pep440"
+        );
+    }
+
+    #[test]
+    fn parse_unknown_kind() {
+        let err =
+            parse_sources("[[source]]\nkind = \"gem\"\npath = \"x\"\n", Path::new("")).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid source 1: unknown kind 'gem', expected one of cargo, dockerfile, file, gradle, \
+             helm, maven, npm, pattern or pyproject"
+        );
+    }
+
+    #[test]
+    fn parse_missing_path() {
+        let err = parse_sources("[[source]]\nkind = \"file\"\n", Path::new("")).unwrap_err();
+
+        assert_eq!(err.to_string(), "invalid source 1: 'path' is missing");
+    }
+
+    #[test]
+    fn parse_max_matches() {
+        let err = parse_sources(
+            "[[source]]\nkind = \"pattern\"\npath = \"x\"\n\
+             pattern = '(?P<version>.+)'\nmax-matches = 0\n",
+            Path::new(""),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid source 1: 'max-matches' must be a positive integer"
+        );
+    }
+
+    #[test]
+    fn parse_helm() {
+        let sources = parse_sources(
+            "[[source]]\nkind = \"helm\"\npath = \"chart/Chart.yaml\"\n\n\
+             [[source]]\nkind = \"helm\"\npath = \"lib/Chart.yaml\"\napp-version = true\n\n\
+             [[source]]\nkind = \"helm\"\npath = \"svc/Chart.yaml\"\n\
+             app-version-from = \"svc/Cargo.toml\"\n",
+            Path::new("project"),
+        )
+        .unwrap();
+
+        match &sources[0] {
+            Source::Helm(path, AppVersion::Keep) => {
+                assert_eq!(path, Path::new("project/chart/Chart.yaml"))
+            }
+            source => panic!("should keep the app version: {:?}", source),
+        }
+        assert!(matches!(sources[1], Source::Helm(_, AppVersion::Chart)));
+        match &sources[2] {
+            Source::Helm(_, AppVersion::Crate(manifest)) => {
+                assert_eq!(manifest, Path::new("project/svc/Cargo.toml"))
+            }
+            source => panic!("should follow the crate version: {:?}", source),
+        }
+        assert_eq!(
+            parse_sources(
+                "[[source]]\nkind = \"helm\"\npath = \"Chart.yaml\"\napp-version = \"yes\"\n",
+                Path::new(""),
+            )
+            .unwrap_err()
+            .to_string(),
+            "invalid source 1: 'app-version' must be a boolean"
+        );
+    }
+
+    #[test]
+    fn parse_no_sources() {
+        assert!(parse_sources("", Path::new("")).is_err());
+    }
+
+    #[test]
+    fn parse_invalid_toml() {
+        let err = parse_sources("[[source]\n", Path::new("")).unwrap_err();
+
+        match err.downcast::<VersioError>() {
+            Ok(err) => assert_eq!(err.exit_code(), 4),
+            Err(err) => panic!("should be a versio error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn find_in_parent() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        fs::write(
+            dir.path().join(CONFIG_FILE),
+            "[[source]]\nkind = \"file\"\npath = \"VERSION.txt\"\n",
+        )
+        .unwrap();
+        let nested = dir.path().join("a/b");
+        fs::create_dir_all(&nested).unwrap();
+        let config = Config::find_from(&nested, &nested).unwrap();
+
+        assert_eq!(config.path, nested.join("../..").join(CONFIG_FILE));
+        assert_eq!(
+            paths(&config),
+            vec![("file", nested.join("../../VERSION.txt"))]
+        );
+    }
+
+    #[test]
+    fn find_missing() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+
+        assert!(Config::find_from(dir.path(), dir.path()).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::fmt;
+
+/// The kind of change a commit makes according to the Conventional Commits specification, in
+/// increasing order of significance.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Change {
+    /// A commit which does not warrant a new version, such as `docs:`, `chore:`, or one which
+    /// does not follow the specification.
+    None,
+    /// A `fix:` commit.
+    Fix,
+    /// A `feat:` commit.
+    Feature,
+    /// A commit marked with `!` before the colon or with a `BREAKING CHANGE:` footer.
+    Breaking,
+}
+
+impl Change {
+    /// Classifies a full commit message.
+    pub fn of(message: &str) -> Self {
+        let mut lines = message.lines();
+        let header = lines.next().unwrap_or("").trim();
+        let (kind, breaking) = match parse_header(header) {
+            Some(parsed) => parsed,
+            None => return Change::None,
+        };
+
+        if breaking
+            || lines.any(|line| {
+                line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+            })
+        {
+            Change::Breaking
+        } else if kind.eq_ignore_ascii_case("feat") {
+            Change::Feature
+        } else if kind.eq_ignore_ascii_case("fix") {
+            Change::Fix
+        } else {
+            Change::None
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Change::None => "change without a release",
+            Change::Fix => "fix",
+            Change::Feature => "feature",
+            Change::Breaking => "breaking change",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Parses a header of the form `type(scope)!: description`, where the scope and `!` are
+/// optional, returning the type and whether the `!` is present.
+fn parse_header(header: &str) -> Option<(&str, bool)> {
+    let colon = header.find(": ")?;
+    let prefix = &header[..colon];
+    let (prefix, breaking) = match prefix.strip_suffix('!') {
+        Some(prefix) => (prefix, true),
+        None => (prefix, false),
+    };
+    let kind = match prefix.find('(') {
+        Some(open) if prefix.ends_with(')') => &prefix[..open],
+        Some(_) => return None,
+        None => prefix,
+    };
+
+    if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return None;
+    }
+
+    Some((kind, breaking))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feature() {
+        assert_eq!(Change::of("feat: add a thing"), Change::Feature);
+        assert_eq!(Change::of("feat(cli): add a thing"), Change::Feature);
+        assert_eq!(Change::of("FEAT: add a thing"), Change::Feature);
+    }
+
+    #[test]
+    fn fix() {
+        assert_eq!(
+            Change::of("fix: mend a thing\n\nWith details.\n"),
+            Change::Fix
+        );
+        assert_eq!(Change::of("fix(io): mend a thing"), Change::Fix);
+    }
+
+    #[test]
+    fn breaking_bang() {
+        assert_eq!(Change::of("feat!: drop a thing"), Change::Breaking);
+        assert_eq!(Change::of("refactor(api)!: rename"), Change::Breaking);
+    }
+
+    #[test]
+    fn breaking_footer() {
+        assert_eq!(
+            Change::of("fix: mend\n\nBREAKING CHANGE: the old way is gone\n"),
+            Change::Breaking
+        );
+        assert_eq!(
+            Change::of("chore: tidy\n\nBREAKING-CHANGE: config moved\n"),
+            Change::Breaking
+        );
+    }
+
+    #[test]
+    fn none() {
+        assert_eq!(Change::of("docs: explain a thing"), Change::None);
+        assert_eq!(Change::of("chore(deps): update"), Change::None);
+        assert_eq!(Change::of("Merge branch 'main'"), Change::None);
+        assert_eq!(Change::of("feat:no space"), Change::None);
+        assert_eq!(Change::of("feat(oops: bad scope"), Change::None);
+        assert_eq!(Change::of("a feat: in prose"), Change::None);
+        assert_eq!(Change::of(""), Change::None);
+    }
+
+    #[test]
+    fn breaking_text_in_body_only_at_line_start() {
+        assert_eq!(
+            Change::of("fix: mend\n\nThis is not a BREAKING CHANGE: really\n"),
+            Change::Fix
+        );
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(Change::Breaking > Change::Feature);
+        assert!(Change::Feature > Change::Fix);
+        assert!(Change::Fix > Change::None);
+    }
+}
@@ -0,0 +1,195 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::cmp;
+use std::path::Path;
+
+/// The number of unchanged lines shown around each change.
+const CONTEXT: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// One line of an edit script, with the positions in the old and new text it applies at.
+#[derive(Clone, Copy, Debug)]
+struct Op {
+    kind: Kind,
+    old: usize,
+    new: usize,
+}
+
+/// Returns a unified diff which turns `old` into `new`, naming `path` as both sides.
+///
+/// The diff is empty when the texts are identical.
+pub fn unified(path: &Path, old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.split_inclusive('\n').collect();
+    let new: Vec<&str> = new.split_inclusive('\n').collect();
+    let ops = edit_script(&old, &new);
+
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (index, _) in ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| op.kind != Kind::Equal)
+    {
+        let start = index.saturating_sub(CONTEXT);
+        let end = cmp::min(index + 1 + CONTEXT, ops.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+    if hunks.is_empty() {
+        return String::new();
+    }
+
+    let mut diff = format!("--- {}\n+++ {}\n", path.display(), path.display());
+    for (start, end) in hunks {
+        let ops = &ops[start..end];
+        let old_count = ops.iter().filter(|op| op.kind != Kind::Insert).count();
+        let new_count = ops.iter().filter(|op| op.kind != Kind::Delete).count();
+        diff.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(ops[0].old, old_count),
+            range(ops[0].new, new_count)
+        ));
+        for op in ops {
+            let (prefix, line) = match op.kind {
+                Kind::Equal => (' ', old[op.old]),
+                Kind::Delete => ('-', old[op.old]),
+                Kind::Insert => ('+', new[op.new]),
+            };
+            diff.push(prefix);
+            diff.push_str(line);
+            if !line.ends_with('\n') {
+                diff.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+    }
+
+    diff
+}
+
+/// Returns the shortest edit script turning `old` into `new`, found from their longest common
+/// subsequence of lines.
+fn edit_script(old: &[&str], new: &[&str]) -> Vec<Op> {
+    // lengths[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut lengths = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                cmp::max(lengths[i + 1][j], lengths[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut ops = Vec::with_capacity(cmp::max(old.len(), new.len()));
+    while i < old.len() || j < new.len() {
+        let kind = if i < old.len() && j < new.len() && old[i] == new[j] {
+            Kind::Equal
+        } else if i < old.len() && (j == new.len() || lengths[i + 1][j] >= lengths[i][j + 1]) {
+            Kind::Delete
+        } else {
+            Kind::Insert
+        };
+        ops.push(Op {
+            kind,
+            old: i,
+            new: j,
+        });
+        match kind {
+            Kind::Equal => {
+                i += 1;
+                j += 1;
+            }
+            Kind::Delete => i += 1,
+            Kind::Insert => j += 1,
+        }
+    }
+
+    ops
+}
+
+/// Formats the line range of a hunk, where `start` is the zero-based index of its first line.
+fn range(start: usize, count: usize) -> String {
+    match count {
+        0 => format!("{},0", start),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, count),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(old: &str, new: &str) -> String {
+        unified(Path::new("Cargo.toml"), old, new)
+    }
+
+    #[test]
+    fn identical() {
+        assert_eq!(diff("a\nb\n", "a\nb\n"), "");
+    }
+
+    #[test]
+    fn single_line() {
+        assert_eq!(
+            diff("1.2.3\n", "1.3.0\n"),
+            "--- Cargo.toml\n+++ Cargo.toml\n@@ -1 +1 @@\n-1.2.3\n+1.3.0\n"
+        );
+    }
+
+    #[test]
+    fn context() {
+        let old = "a\nb\nc\nd\nversion\ne\nf\ng\nh\n";
+        let new = "a\nb\nc\nd\nchanged\ne\nf\ng\nh\n";
+
+        assert_eq!(
+            diff(old, new),
+            "--- Cargo.toml\n+++ Cargo.toml\n@@ -2,7 +2,7 @@\n b\n c\n d\n-version\n+changed\n \
+             e\n f\n g\n"
+        );
+    }
+
+    #[test]
+    fn separate_hunks() {
+        let old = "1\na\nb\nc\nd\ne\nf\ng\n2\n";
+        let new = "one\na\nb\nc\nd\ne\nf\ng\ntwo\n";
+
+        assert_eq!(
+            diff(old, new),
+            "--- Cargo.toml\n+++ Cargo.toml\n@@ -1,4 +1,4 @@\n-1\n+one\n a\n b\n c\n\
+             @@ -6,4 +6,4 @@\n e\n f\n g\n-2\n+two\n"
+        );
+    }
+
+    #[test]
+    fn insert_and_delete() {
+        assert_eq!(
+            diff("a\nb\n", "a\nc\nb\n"),
+            "--- Cargo.toml\n+++ Cargo.toml\n@@ -1,2 +1,3 @@\n a\n+c\n b\n"
+        );
+        assert_eq!(
+            diff("a\n", ""),
+            "--- Cargo.toml\n+++ Cargo.toml\n@@ -1 +0,0 @@\n-a\n"
+        );
+    }
+
+    #[test]
+    fn no_newline_at_end() {
+        assert_eq!(
+            diff("1.2.3", "1.3.0\n"),
+            "--- Cargo.toml\n+++ Cargo.toml\n@@ -1 +1 @@\n-1.2.3\n\\ No newline at end of file\n\
+             +1.3.0\n"
+        );
+    }
+}
@@ -0,0 +1,316 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::io;
+use crate::source::Edit;
+use crate::version::SchemeVersion;
+use crate::Result;
+use failure::bail;
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+
+/// The label holding the version of an image, as defined by the OCI image spec.
+const LABEL_KEY: &str = "org.opencontainers.image.version";
+
+/// The build argument holding the version of an image.
+const ARG_KEY: &str = "VERSION";
+
+/// A version found in an instruction of a Dockerfile.
+#[derive(Debug)]
+struct Found {
+    /// The byte range of the version, without any quotes around it.
+    range: Range<usize>,
+    /// The line holding the version, starting at 1.
+    line: usize,
+}
+
+/// Reads the version of the Dockerfile at `path`, failing if its instructions do not agree.
+pub fn read_version<V: SchemeVersion>(path: &Path) -> Result<V> {
+    let text = io::read_text_file(path)?;
+    let found = find(&text, path)?;
+    let mut versions = Vec::with_capacity(found.len());
+    for found in &found {
+        versions.push(V::parse(&text[found.range.clone()]).map_err(|err| err.with_path(path))?);
+    }
+
+    let first = &versions[0];
+    if versions.iter().all(|version| version == first) {
+        return Ok(first.clone());
+    }
+    let found: Vec<_> = found
+        .iter()
+        .zip(&versions)
+        .map(|(found, version)| format!("{} on line {}", version, found.line))
+        .collect();
+    bail!(
+        "{} holds differing versions: {}",
+        path.display(),
+        found.join(", ")
+    )
+}
+
+/// Returns the edit which changes every version of the Dockerfile at `path` to `version`, noting
+/// each line it changes.
+pub fn edit<V: fmt::Display>(path: &Path, version: &V) -> Result<Edit> {
+    let text = io::read_text_file(path)?;
+    let found = find(&text, path)?;
+    let version = version.to_string();
+
+    let mut contents = String::with_capacity(text.len());
+    let mut last = 0;
+    for found in &found {
+        contents.push_str(&text[last..found.range.start]);
+        contents.push_str(&version);
+        last = found.range.end;
+    }
+    contents.push_str(&text[last..]);
+
+    let mut lines: Vec<usize> = found.iter().map(|found| found.line).collect();
+    lines.dedup();
+
+    Ok(Edit {
+        path: path.to_path_buf(),
+        contents,
+        lines,
+    })
+}
+
+/// Finds every version held in a `LABEL org.opencontainers.image.version=...` or an
+/// `ARG VERSION=...` instruction of `text`, failing if there are none.
+fn find(text: &str, path: &Path) -> Result<Vec<Found>> {
+    let mut found = Vec::new();
+    for instruction in instructions(text) {
+        let key = match instruction.first() {
+            Some(keyword) if text[keyword.range.clone()].eq_ignore_ascii_case("LABEL") => LABEL_KEY,
+            Some(keyword) if text[keyword.range.clone()].eq_ignore_ascii_case("ARG") => ARG_KEY,
+            _ => continue,
+        };
+
+        for word in &instruction[1..] {
+            let pair = &text[word.range.clone()];
+            let separator = match pair.find('=') {
+                Some(separator) if unquote(&pair[..separator]) == key => separator,
+                _ => continue,
+            };
+            let start = word.range.start + separator + 1;
+            let value = &text[start..word.range.end];
+            let range = match value.chars().next() {
+                Some(quote @ '"') | Some(quote @ '\'')
+                    if value.len() > 1 && value.ends_with(quote) =>
+                {
+                    start + 1..word.range.end - 1
+                }
+                _ => start..word.range.end,
+            };
+            if !range.is_empty() {
+                found.push(Found {
+                    range,
+                    line: word.line,
+                });
+            }
+        }
+    }
+
+    if found.is_empty() {
+        bail!(
+            "cannot find the version in {}, expected LABEL {}=... or ARG {}=...",
+            path.display(),
+            LABEL_KEY,
+            ARG_KEY
+        );
+    }
+
+    Ok(found)
+}
+
+fn unquote(text: &str) -> &str {
+    text.trim_matches(|c| c == '"' || c == '\'')
+}
+
+/// A word of an instruction, which is separated from the next by whitespace outside of quotes.
+#[derive(Debug)]
+struct Word {
+    range: Range<usize>,
+    line: usize,
+}
+
+/// Splits `text` into the words of each instruction, joining the lines which are continued by the
+/// escape character, `\` unless a `# escape=` parser directive says otherwise.
+///
+/// Comments and blank lines are skipped, even within a continued instruction.
+fn instructions(text: &str) -> Vec<Vec<Word>> {
+    let mut escape = '\\';
+    let mut directives = true;
+    let mut instructions = Vec::new();
+    let mut current: Vec<Word> = Vec::new();
+    let mut start = 0;
+
+    for (index, line) in text.split_inclusive('\n').enumerate() {
+        let line_start = start;
+        start += line.len();
+        let content = line.trim_end();
+        let trimmed = content.trim_start();
+
+        if let Some(comment) = trimmed.strip_prefix('#') {
+            if directives {
+                if let Some(value) = comment.trim().strip_prefix("escape=") {
+                    escape = value.trim().chars().next().unwrap_or(escape);
+                }
+            }
+            continue;
+        }
+        directives = false;
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let (content, continues) = match content.strip_suffix(escape) {
+            Some(content) => (content, true),
+            None => (content, false),
+        };
+        current.extend(words(content, line_start, index + 1));
+        if !continues {
+            instructions.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        instructions.push(current);
+    }
+
+    instructions
+}
+
+/// Splits a line starting at the byte `offset` of the text into words.
+fn words(line: &str, offset: usize, number: usize) -> Vec<Word> {
+    let mut words = Vec::new();
+    let mut word_start = None;
+    let mut quote = None;
+    for (index, c) in line.char_indices() {
+        match quote {
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            None if c.is_whitespace() => {
+                if let Some(start) = word_start.take() {
+                    words.push(Word {
+                        range: offset + start..offset + index,
+                        line: number,
+                    });
+                }
+                continue;
+            }
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None => {}
+        }
+        if word_start.is_none() {
+            word_start = Some(index);
+        }
+    }
+    if let Some(start) = word_start {
+        words.push(Word {
+            range: offset + start..offset + line.len(),
+            line: number,
+        });
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::Version;
+    use std::fs;
+
+    const DOCKERFILE: &str = "# syntax=docker/dockerfile:1\n\
+                              FROM rust:1.75 AS build\n\
+                              ARG VERSION=1.2.3\n\
+                              RUN cargo build --release\n\
+                              \n\
+                              FROM debian:bookworm-slim\n\
+                              LABEL org.opencontainers.image.title=\"service\" \\\n      \
+                              # the version is bumped by versio\n      \
+                              org.opencontainers.image.version=\"1.2.3\" \\\n      \
+                              org.opencontainers.image.description=\"A version 9.9.9 service\"\n\
+                              COPY --from=build /target/release/service /usr/bin/\n";
+
+    fn lines(text: &str) -> Vec<usize> {
+        find(text, Path::new("Dockerfile"))
+            .unwrap()
+            .iter()
+            .map(|found| found.line)
+            .collect()
+    }
+
+    #[test]
+    fn find_continued() {
+        let found = find(DOCKERFILE, Path::new("Dockerfile")).unwrap();
+
+        assert_eq!(lines(DOCKERFILE), vec![3, 9]);
+        assert_eq!(&DOCKERFILE[found[0].range.clone()], "1.2.3");
+        assert_eq!(&DOCKERFILE[found[1].range.clone()], "1.2.3");
+    }
+
+    #[test]
+    fn find_forms() {
+        assert_eq!(lines("arg VERSION='1.2.3'\n"), vec![1]);
+        assert_eq!(
+            lines("LABEL \"org.opencontainers.image.version\"=1.2.3\r\n"),
+            vec![1]
+        );
+        assert_eq!(
+            lines(
+                "# escape=`\nFROM scratch\nLABEL a=b `\n  org.opencontainers.image.version=1.2.3\n"
+            ),
+            vec![4]
+        );
+    }
+
+    #[test]
+    fn find_ignores_others() {
+        let err = find(
+            "ARG VERSION\nENV VERSION=1.2.3\nLABEL version=1.2.3\nRUN echo VERSION=1.2.3\n",
+            Path::new("Dockerfile"),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "cannot find the version in Dockerfile, expected LABEL \
+             org.opencontainers.image.version=... or ARG VERSION=..."
+        );
+    }
+
+    #[test]
+    fn read_disagree() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("Dockerfile");
+        fs::write(
+            &path,
+            "ARG VERSION=1.2.3\nLABEL org.opencontainers.image.version=1.2.4\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            read_version::<Version>(&path).unwrap_err().to_string(),
+            format!(
+                "{} holds differing versions: 1.2.3 on line 1, 1.2.4 on line 2",
+                path.display()
+            )
+        );
+    }
+
+    #[test]
+    fn edit_all() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("Dockerfile");
+        fs::write(&path, DOCKERFILE).unwrap();
+        let version: Version = read_version(&path).unwrap();
+        let edit = edit(&path, &Version::parse("1.3.0").unwrap()).unwrap();
+
+        assert_eq!(version.to_string(), "1.2.3");
+        assert_eq!(edit.contents, DOCKERFILE.replace("1.2.3", "1.3.0"));
+        assert_eq!(edit.lines, vec![3, 9]);
+    }
+}
@@ -0,0 +1,345 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use failure::Fail;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use toml_edit::TomlError;
+
+/// An error reading, parsing, or writing a version or the file which holds it.
+///
+/// Each variant is a distinct category of failure with its own process exit code, so that callers
+/// can tell, for example, a missing file apart from an invalid version.
+#[derive(Debug)]
+pub enum VersioError {
+    /// A file could not be read or written.
+    Io { path: PathBuf, cause: io::Error },
+    /// A TOML, JSON or XML document could not be parsed.
+    Syntax {
+        format: &'static str,
+        path: Option<PathBuf>,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// A document has no version under the expected key.
+    MissingVersion { path: Option<PathBuf>, key: String },
+    /// A document has a value under the expected version key which is not a string.
+    VersionNotString { path: Option<PathBuf>, key: String },
+    /// A version string could not be parsed by its scheme.
+    InvalidVersion {
+        path: Option<PathBuf>,
+        text: String,
+        cause: failure::Error,
+    },
+    /// A version does not match the expected version or requirement.
+    UnexpectedVersion {
+        path: Option<PathBuf>,
+        found: String,
+        expected: String,
+    },
+    /// Several sources hold differing versions.
+    VersionsDisagree { found: Vec<(PathBuf, String)> },
+    /// A document declares its version as computed by a build tool, so it is not in the file.
+    DynamicVersion { path: Option<PathBuf>, key: String },
+    /// A manifest inherits its version from the workspace rather than declaring its own.
+    InheritedVersion { path: Option<PathBuf> },
+}
+
+impl VersioError {
+    pub fn io(path: &Path, cause: io::Error) -> Self {
+        VersioError::Io {
+            path: path.to_path_buf(),
+            cause,
+        }
+    }
+
+    /// Converts a TOML parse error, locating it by line and column in `source`.
+    pub fn toml(source: &str, err: &TomlError) -> Self {
+        let offset = err.span().map(|span| span.start).unwrap_or(0);
+
+        Self::syntax("TOML", source, offset, err.message())
+    }
+
+    /// Creates a JSON syntax error at the byte `offset` in `source`.
+    pub fn json(source: &str, offset: usize, message: &str) -> Self {
+        Self::syntax("JSON", source, offset, message)
+    }
+
+    /// Creates an XML syntax error at the byte `offset` in `source`.
+    pub fn xml(source: &str, offset: usize, message: &str) -> Self {
+        Self::syntax("XML", source, offset, message)
+    }
+
+    fn syntax(format: &'static str, source: &str, offset: usize, message: &str) -> Self {
+        let before = source.get(..offset).unwrap_or(source);
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+
+        VersioError::Syntax {
+            format,
+            path: None,
+            line,
+            column,
+            message: message.to_string(),
+        }
+    }
+
+    /// Returns the process exit code for this category of error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            VersioError::Io { .. } => 3,
+            VersioError::Syntax { .. } => 4,
+            VersioError::MissingVersion { .. } => 5,
+            VersioError::VersionNotString { .. } => 6,
+            VersioError::InvalidVersion { .. } => 7,
+            VersioError::UnexpectedVersion { .. } => 8,
+            VersioError::VersionsDisagree { .. } => 9,
+            VersioError::DynamicVersion { .. } => 10,
+            VersioError::InheritedVersion { .. } => 11,
+        }
+    }
+
+    /// Attaches `path` to this error if it does not already name a file.
+    pub fn with_path(mut self, new_path: &Path) -> Self {
+        match &mut self {
+            VersioError::Io { .. } | VersioError::VersionsDisagree { .. } => {}
+            VersioError::Syntax { path, .. }
+            | VersioError::MissingVersion { path, .. }
+            | VersioError::VersionNotString { path, .. }
+            | VersioError::InvalidVersion { path, .. }
+            | VersioError::UnexpectedVersion { path, .. }
+            | VersioError::DynamicVersion { path, .. }
+            | VersioError::InheritedVersion { path } => {
+                if path.is_none() {
+                    *path = Some(new_path.to_path_buf());
+                }
+            }
+        }
+        self
+    }
+}
+
+impl fmt::Display for VersioError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VersioError::Io { path, .. } => write!(f, "I/O error on file {}", path.display()),
+            VersioError::Syntax {
+                format,
+                path,
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "invalid {}{} at line {}, column {}: {}",
+                format,
+                InPath(path),
+                line,
+                column,
+                message.trim()
+            ),
+            VersioError::MissingVersion { path, key } => {
+                write!(f, "cannot find version key '{}'{}", key, InPath(path))
+            }
+            VersioError::VersionNotString { path, key } => {
+                write!(f, "version key '{}'{} is not a string", key, InPath(path))
+            }
+            VersioError::InvalidVersion { path, text, .. } => {
+                write!(f, "invalid version '{}'{}", text, InPath(path))
+            }
+            VersioError::UnexpectedVersion {
+                path,
+                found,
+                expected,
+            } => write!(
+                f,
+                "version '{}'{} does not match '{}'",
+                found,
+                InPath(path),
+                expected
+            ),
+            VersioError::VersionsDisagree { found } => {
+                let found: Vec<_> = found
+                    .iter()
+                    .map(|(path, version)| format!("{} in {}", version, path.display()))
+                    .collect();
+                write!(f, "versions disagree: {}", found.join(", "))
+            }
+            VersioError::DynamicVersion { path, key } => write!(
+                f,
+                "version{} is declared dynamic in {} and is set by the build backend",
+                InPath(path),
+                key
+            ),
+            VersioError::InheritedVersion { path } => write!(
+                f,
+                "version{} is inherited from the workspace (version.workspace = true)",
+                InPath(path)
+            ),
+        }
+    }
+}
+
+impl Fail for VersioError {
+    fn cause(&self) -> Option<&dyn Fail> {
+        match self {
+            VersioError::Io { cause, .. } => Some(cause),
+            VersioError::InvalidVersion { cause, .. } => Some(cause.as_fail()),
+            _ => None,
+        }
+    }
+}
+
+/// Attaches `path` to an error which does not yet name a file.
+///
+/// A bare I/O error is categorized as a `VersioError::Io` for `path`.
+pub fn with_path(err: failure::Error, path: &Path) -> failure::Error {
+    let err = match err.downcast::<VersioError>() {
+        Ok(err) => return err.with_path(path).into(),
+        Err(err) => err,
+    };
+    match err.downcast::<io::Error>() {
+        Ok(cause) => VersioError::io(path, cause).into(),
+        Err(err) => err,
+    }
+}
+
+/// Displays an optional path as an " in <path>" suffix.
+struct InPath<'a>(&'a Option<PathBuf>);
+
+impl<'a> fmt::Display for InPath<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(path) => write!(f, " in {}", path.display()),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn semver_error() -> failure::Error {
+        semver::Version::from_str("nope").unwrap_err().into()
+    }
+
+    #[test]
+    fn display_with_path() {
+        let err = VersioError::InvalidVersion {
+            path: Some(PathBuf::from("Cargo.toml")),
+            text: "nope".to_string(),
+            cause: semver_error(),
+        };
+
+        assert_eq!(err.to_string(), "invalid version 'nope' in Cargo.toml");
+    }
+
+    #[test]
+    fn display_without_path() {
+        let err = VersioError::MissingVersion {
+            path: None,
+            key: "package.version".to_string(),
+        };
+
+        assert_eq!(err.to_string(), "cannot find version key 'package.version'");
+    }
+
+    #[test]
+    fn with_path_fills_missing() {
+        let err = VersioError::VersionNotString {
+            path: None,
+            key: "package.version".to_string(),
+        }
+        .with_path(Path::new("a/Cargo.toml"));
+
+        assert_eq!(
+            err.to_string(),
+            "version key 'package.version' in a/Cargo.toml is not a string"
+        );
+    }
+
+    #[test]
+    fn with_path_keeps_existing() {
+        let err = VersioError::MissingVersion {
+            path: Some(PathBuf::from("a/Cargo.toml")),
+            key: "package.version".to_string(),
+        }
+        .with_path(Path::new("b/Cargo.toml"));
+
+        assert_eq!(
+            err.to_string(),
+            "cannot find version key 'package.version' in a/Cargo.toml"
+        );
+    }
+
+    #[test]
+    fn with_path_io() {
+        let err = with_path(
+            io::Error::new(io::ErrorKind::NotFound, "gone").into(),
+            Path::new("VERSION.txt"),
+        );
+
+        match err.downcast::<VersioError>() {
+            Ok(err) => {
+                assert_eq!(err.exit_code(), 3);
+                assert_eq!(err.to_string(), "I/O error on file VERSION.txt");
+            }
+            Err(err) => panic!("should be a versio error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn exit_codes_distinct() {
+        let codes = vec![
+            VersioError::io(Path::new("x"), io::Error::from(io::ErrorKind::NotFound)).exit_code(),
+            VersioError::Syntax {
+                format: "TOML",
+                path: None,
+                line: 1,
+                column: 1,
+                message: String::new(),
+            }
+            .exit_code(),
+            VersioError::MissingVersion {
+                path: None,
+                key: String::new(),
+            }
+            .exit_code(),
+            VersioError::VersionNotString {
+                path: None,
+                key: String::new(),
+            }
+            .exit_code(),
+            VersioError::InvalidVersion {
+                path: None,
+                text: String::new(),
+                cause: semver_error(),
+            }
+            .exit_code(),
+            VersioError::UnexpectedVersion {
+                path: None,
+                found: String::new(),
+                expected: String::new(),
+            }
+            .exit_code(),
+            VersioError::VersionsDisagree { found: Vec::new() }.exit_code(),
+            VersioError::DynamicVersion {
+                path: None,
+                key: String::new(),
+            }
+            .exit_code(),
+            VersioError::InheritedVersion { path: None }.exit_code(),
+        ];
+        let mut deduped = codes.clone();
+        deduped.sort();
+        deduped.dedup();
+
+        assert_eq!(codes.len(), deduped.len());
+        assert!(!codes.contains(&1));
+    }
+}
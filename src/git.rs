@@ -0,0 +1,327 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::version::Version;
+use crate::Result;
+use failure::{bail, format_err};
+use log::debug;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+
+/// A commit, with its abbreviated id and full message.
+#[derive(Clone, Debug)]
+pub struct Commit {
+    pub id: String,
+    pub message: String,
+}
+
+impl Commit {
+    /// Returns the first line of the commit message.
+    pub fn summary(&self) -> &str {
+        self.message.lines().next().unwrap_or("")
+    }
+}
+
+/// A tag which names a version, such as `v1.2.3` or `1.2.3`.
+#[derive(Clone, Debug)]
+pub struct VersionTag {
+    pub name: String,
+    pub version: Version,
+}
+
+/// A local git repository, read by running the `git` command.
+#[derive(Clone, Debug)]
+pub struct Repository {
+    path: PathBuf,
+}
+
+impl Repository {
+    /// Opens the repository containing `path`.
+    pub fn open(path: &Path) -> Result<Self> {
+        let repo = Repository {
+            path: path.to_path_buf(),
+        };
+        repo.git(&["rev-parse", "--git-dir"])?;
+
+        Ok(repo)
+    }
+
+    /// Returns the tag naming the highest version which is reachable from `HEAD`, if any.
+    pub fn last_version_tag(&self) -> Result<Option<VersionTag>> {
+        let tags = self.git(&["tag", "--merged", "HEAD"])?;
+
+        Ok(tags
+            .lines()
+            .filter_map(|name| {
+                let version = Version::from_str(name.strip_prefix('v').unwrap_or(name)).ok()?;
+                Some(VersionTag {
+                    name: name.to_string(),
+                    version,
+                })
+            })
+            .max_by(|a, b| a.version.cmp(&b.version)))
+    }
+
+    /// Returns the commits reachable from `HEAD` but not from the tag `since`, newest first. All
+    /// commits are returned if there is no tag.
+    pub fn commits_since(&self, since: Option<&str>) -> Result<Vec<Commit>> {
+        let range = match since {
+            Some(tag) => format!("refs/tags/{}..HEAD", tag),
+            None => "HEAD".to_string(),
+        };
+        let log = self.git(&["log", "--format=%h%x00%B%x1e", &range])?;
+
+        Ok(log
+            .split('\x1e')
+            .filter_map(|entry| {
+                let mut parts = entry.trim_start_matches('\n').splitn(2, '\0');
+                match (parts.next(), parts.next()) {
+                    (Some(id), Some(message)) => Some(Commit {
+                        id: id.to_string(),
+                        message: message.trim_end().to_string(),
+                    }),
+                    _ => None,
+                }
+            })
+            .collect())
+    }
+
+    /// Returns the tracked paths with uncommitted changes, whether staged or not.
+    ///
+    /// A renamed or copied file is named by its new path. With `-z`, its entry is followed by one
+    /// holding the original path, which is skipped.
+    pub fn dirty_paths(&self) -> Result<Vec<String>> {
+        let status = self.git(&["status", "--porcelain", "-z", "--untracked-files=no"])?;
+
+        let mut paths = Vec::new();
+        let mut entries = status.split('\0').filter(|entry| !entry.is_empty());
+        while let Some(entry) = entries.next() {
+            let (code, path) = match (entry.get(..2), entry.get(3..)) {
+                (Some(code), Some(path)) => (code, path),
+                _ => continue,
+            };
+            if code.contains(['R', 'C']) {
+                entries.next();
+            }
+            paths.push(path.to_string());
+        }
+
+        Ok(paths)
+    }
+
+    /// Stages exactly the files at `paths` and commits them with `message`.
+    pub fn commit(&self, paths: &[PathBuf], message: &str) -> Result<()> {
+        let mut args = vec!["add".to_string(), "--".to_string()];
+        for path in paths {
+            let path = path.canonicalize()?;
+            match path.to_str() {
+                Some(path) => args.push(path.to_string()),
+                None => bail!("path is not valid UTF-8: {}", path.display()),
+            }
+        }
+        self.git(&args.iter().map(String::as_str).collect::<Vec<_>>())?;
+        self.git(&["commit", "--quiet", "-m", message])?;
+
+        Ok(())
+    }
+
+    /// Creates an annotated tag `name` on `HEAD` with `message`.
+    pub fn tag(&self, name: &str, message: &str) -> Result<()> {
+        self.git(&["tag", "-a", name, "-m", message])?;
+
+        Ok(())
+    }
+
+    fn git(&self, args: &[&str]) -> Result<String> {
+        debug!("running git; args={:?}, dir={}", args, self.path.display());
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.path)
+            .args(args)
+            .output()
+            .map_err(|err| format_err!("failed to run git: {}", err))?;
+        if !output.status.success() {
+            bail!(
+                "git {} failed in {}: {}",
+                args.join(" "),
+                self.path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(String::from_utf8(output.stdout)?)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_helpers {
+    use std::fs;
+    use std::path::Path;
+    use std::process::Command;
+
+    /// Runs `git` in `dir`, panicking if it fails.
+    pub(crate) fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .status()
+            .expect("git should run");
+        assert!(status.success(), "git {:?} should succeed", args);
+    }
+
+    /// Creates a repository in `dir` with no commits and a fixed identity.
+    pub(crate) fn init(dir: &Path) {
+        git(dir, &["init", "--quiet"]);
+        git(dir, &["config", "user.name", "Versio"]);
+        git(dir, &["config", "user.email", "versio@example.com"]);
+        git(dir, &["config", "commit.gpgsign", "false"]);
+        git(dir, &["config", "tag.gpgsign", "false"]);
+    }
+
+    /// Runs `git` in `dir`, returning its trimmed output.
+    pub(crate) fn output(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .output()
+            .expect("git should run");
+        assert!(output.status.success(), "git {:?} should succeed", args);
+
+        String::from_utf8(output.stdout)
+            .expect("output should be valid utf-8")
+            .trim()
+            .to_string()
+    }
+
+    /// Writes a file and commits it with `message`.
+    pub(crate) fn commit(dir: &Path, file: &str, message: &str) {
+        fs::write(dir.join(file), message).expect("file should be written");
+        git(dir, &["add", file]);
+        git(dir, &["commit", "--quiet", "-m", message]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_helpers::{commit, git, init, output};
+    use super::*;
+    use std::fs;
+
+    fn summaries(commits: &[Commit]) -> Vec<&str> {
+        commits.iter().map(Commit::summary).collect()
+    }
+
+    #[test]
+    fn open_not_a_repository() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+
+        assert!(Repository::open(dir.path()).is_err());
+    }
+
+    #[test]
+    fn commits_since_tag() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        init(dir.path());
+        commit(dir.path(), "a", "feat: first");
+        git(dir.path(), &["tag", "v1.0.0"]);
+        commit(dir.path(), "b", "fix: second\n\nWith a body.");
+        commit(dir.path(), "c", "docs: third");
+        let repo = Repository::open(dir.path()).unwrap();
+        let tag = repo
+            .last_version_tag()
+            .unwrap()
+            .expect("tag should be found");
+        let commits = repo.commits_since(Some(&tag.name)).unwrap();
+
+        assert_eq!(tag.name, "v1.0.0");
+        assert_eq!(summaries(&commits), vec!["docs: third", "fix: second"]);
+        assert_eq!(commits[1].message, "fix: second\n\nWith a body.");
+    }
+
+    #[test]
+    fn commits_without_tag() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        init(dir.path());
+        commit(dir.path(), "a", "feat: first");
+        commit(dir.path(), "b", "fix: second");
+        let repo = Repository::open(dir.path()).unwrap();
+
+        assert!(repo.last_version_tag().unwrap().is_none());
+        assert_eq!(
+            summaries(&repo.commits_since(None).unwrap()),
+            vec!["fix: second", "feat: first"]
+        );
+    }
+
+    #[test]
+    fn dirty_paths() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        init(dir.path());
+        commit(dir.path(), "a", "feat: first");
+        commit(dir.path(), "b", "feat: second");
+        fs::write(dir.path().join("a"), "changed").unwrap();
+        fs::write(dir.path().join("untracked"), "new").unwrap();
+        let repo = Repository::open(dir.path()).unwrap();
+
+        assert_eq!(repo.dirty_paths().unwrap(), vec!["a"]);
+    }
+
+    #[test]
+    fn dirty_paths_renamed() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        init(dir.path());
+        commit(dir.path(), "a", "feat: first");
+        commit(dir.path(), "b", "feat: second");
+        git(dir.path(), &["mv", "a", "renamed"]);
+        fs::write(dir.path().join("b"), "changed").unwrap();
+        let repo = Repository::open(dir.path()).unwrap();
+
+        assert_eq!(repo.dirty_paths().unwrap(), vec!["b", "renamed"]);
+    }
+
+    #[test]
+    fn commit_and_tag() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        init(dir.path());
+        commit(dir.path(), "a", "feat: first");
+        fs::write(dir.path().join("a"), "changed").unwrap();
+        fs::write(dir.path().join("b"), "not staged").unwrap();
+        let repo = Repository::open(dir.path()).unwrap();
+        repo.commit(&[dir.path().join("a")], "Release 1.0.0")
+            .unwrap();
+        repo.tag("v1.0.0", "Release 1.0.0").unwrap();
+
+        assert_eq!(
+            output(dir.path(), &["log", "-1", "--format=%s"]),
+            "Release 1.0.0"
+        );
+        assert_eq!(
+            output(dir.path(), &["show", "--format=", "--name-only", "HEAD"]),
+            "a"
+        );
+        assert_eq!(output(dir.path(), &["describe"]), "v1.0.0");
+        assert_eq!(output(dir.path(), &["status", "--porcelain"]), "?? b");
+    }
+
+    #[test]
+    fn last_version_tag_highest() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        init(dir.path());
+        commit(dir.path(), "a", "feat: first");
+        git(dir.path(), &["tag", "1.2.0"]);
+        git(dir.path(), &["tag", "v1.10.0"]);
+        git(dir.path(), &["tag", "not-a-version"]);
+        let repo = Repository::open(dir.path()).unwrap();
+        let tag = repo
+            .last_version_tag()
+            .unwrap()
+            .expect("tag should be found");
+
+        assert_eq!(tag.name, "v1.10.0");
+        assert_eq!(tag.version.to_string(), "1.10.0");
+    }
+}
@@ -0,0 +1,107 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::error::{self, VersioError};
+use crate::io;
+use crate::source::Edit;
+use crate::version::SchemeVersion;
+use crate::Result;
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+
+/// The property holding the project version.
+const VERSION_KEY: &str = "version";
+
+/// Reads the version of the `gradle.properties` at `path`.
+pub fn read_version<V: SchemeVersion>(path: &Path) -> Result<V> {
+    let text = io::read_text_file(path)?;
+    let range = version_span(&text).map_err(|err| error::with_path(err, path))?;
+
+    Ok(V::parse(&text[range]).map_err(|err| err.with_path(path))?)
+}
+
+/// Returns the edit which changes the version of the `gradle.properties` at `path` to `version`.
+///
+/// Only the value of the property is replaced, so comments and the other properties are kept as
+/// they are.
+pub fn edit<V: fmt::Display>(path: &Path, version: &V) -> Result<Edit> {
+    let text = io::read_text_file(path)?;
+    let range = version_span(&text).map_err(|err| error::with_path(err, path))?;
+
+    Ok(Edit {
+        path: path.to_path_buf(),
+        contents: format!("{}{}{}", &text[..range.start], version, &text[range.end..]),
+        lines: Vec::new(),
+    })
+}
+
+/// Finds the byte range of the version property's value.
+///
+/// As in any Java properties file, the key may be separated from its value by `=`, `:` or
+/// whitespace, and lines starting with `#` or `!` are comments.
+fn version_span(text: &str) -> Result<Range<usize>> {
+    let mut start = 0;
+    for line in text.split_inclusive('\n') {
+        let line_start = start;
+        start += line.len();
+        let key = line.trim_start();
+        let rest = match key.strip_prefix(VERSION_KEY) {
+            Some(rest) => rest,
+            None => continue,
+        };
+        let value = rest.trim_start_matches([' ', '\t']);
+        let value = match value.chars().next() {
+            Some('=') | Some(':') => value[1..].trim_start_matches([' ', '\t']),
+            _ if value.len() < rest.len() => value,
+            _ => continue,
+        };
+        let value_start = line_start + line.len() - value.len();
+
+        return Ok(value_start..value_start + value.trim_end().len());
+    }
+
+    Err(VersioError::MissingVersion {
+        path: None,
+        key: VERSION_KEY.to_string(),
+    }
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(text: &str) -> &str {
+        &text[version_span(text).unwrap()]
+    }
+
+    #[test]
+    fn separators() {
+        assert_eq!(span("version=1.2.3\n"), "1.2.3");
+        assert_eq!(span("group=com.example\nversion = 1.2.3 \r\n"), "1.2.3");
+        assert_eq!(span("  version: 1.2.3-SNAPSHOT"), "1.2.3-SNAPSHOT");
+        assert_eq!(span("version 1.2.3\n"), "1.2.3");
+    }
+
+    #[test]
+    fn other_keys() {
+        let text = "# version=0.0.1\n\
+                    versionCode=12\n\
+                    kotlinVersion=1.9.0\n\
+                    version=1.2.3\n";
+
+        assert_eq!(span(text), "1.2.3");
+    }
+
+    #[test]
+    fn missing() {
+        let err = version_span("group=com.example\n")
+            .unwrap_err()
+            .downcast::<VersioError>()
+            .expect("should be a versio error");
+
+        assert_eq!(err.exit_code(), 5);
+    }
+}
@@ -0,0 +1,278 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::cargo;
+use crate::error::{self, VersioError};
+use crate::io;
+use crate::source::Edit;
+use crate::version::{SchemeVersion, Version};
+use crate::Result;
+use std::fmt;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// The key of the chart's own version, which Helm requires to be a semantic version.
+const VERSION_KEY: &str = "version";
+
+/// The key of the version of the application the chart deploys.
+const APP_VERSION_KEY: &str = "appVersion";
+
+/// What the `appVersion` of a chart is set to when its version is bumped.
+#[derive(Clone, Debug)]
+pub enum AppVersion {
+    /// The `appVersion` is left as it is.
+    Keep,
+    /// The `appVersion` follows the new chart version, for a chart released in lock-step with the
+    /// application.
+    Chart,
+    /// The `appVersion` is set to the version of the crate whose Cargo manifest is at the path.
+    Crate(PathBuf),
+}
+
+/// Reads the version of the `Chart.yaml` at `path`.
+pub fn read_version(path: &Path) -> Result<Version> {
+    let text = io::read_text_file(path)?;
+    let range = version_span(&text).map_err(|err| error::with_path(err, path))?;
+
+    Ok(Version::parse(&text[range]).map_err(|err| err.with_path(path))?)
+}
+
+/// Returns the edit which changes the version of the `Chart.yaml` at `path` to `version`, and its
+/// `appVersion` as `app_version` says.
+///
+/// Only the version strings are replaced, so comments, key order and quoting are kept as they
+/// are. A missing `appVersion` is added on the line after the version.
+pub fn edit<V: fmt::Display>(path: &Path, version: &V, app_version: &AppVersion) -> Result<Edit> {
+    let text = io::read_text_file(path)?;
+    let contents =
+        replace(&text, version, app_version).map_err(|err| error::with_path(err, path))?;
+
+    Ok(Edit {
+        path: path.to_path_buf(),
+        contents,
+        lines: Vec::new(),
+    })
+}
+
+fn replace<V: fmt::Display>(text: &str, version: &V, app_version: &AppVersion) -> Result<String> {
+    let range = version_span(text)?;
+    let app = match app_version {
+        AppVersion::Keep => None,
+        AppVersion::Chart => Some(version.to_string()),
+        AppVersion::Crate(manifest) => Some(cargo::read_version(manifest)?.to_string()),
+    };
+
+    let mut replaced = String::with_capacity(text.len());
+    match app {
+        None => {
+            replaced.push_str(&text[..range.start]);
+            replaced.push_str(&version.to_string());
+            replaced.push_str(&text[range.end..]);
+        }
+        Some(app) => match value_span(text, APP_VERSION_KEY)? {
+            Some(app_range) => {
+                let mut ranges = vec![(range, version.to_string()), (app_range, app)];
+                ranges.sort_by_key(|(range, _)| range.start);
+                let mut last = 0;
+                for (range, value) in ranges {
+                    replaced.push_str(&text[last..range.start]);
+                    replaced.push_str(&value);
+                    last = range.end;
+                }
+                replaced.push_str(&text[last..]);
+            }
+            None => {
+                let line_end = text[range.end..]
+                    .find('\n')
+                    .map_or(text.len(), |index| range.end + index + 1);
+                let newline = if text[..line_end].ends_with("\r\n") {
+                    "\r\n"
+                } else {
+                    "\n"
+                };
+                replaced.push_str(&text[..range.start]);
+                replaced.push_str(&version.to_string());
+                replaced.push_str(&text[range.end..line_end]);
+                if !replaced.ends_with('\n') {
+                    replaced.push_str(newline);
+                }
+                replaced.push_str(&format!("{}: \"{}\"{}", APP_VERSION_KEY, app, newline));
+                replaced.push_str(&text[line_end..]);
+            }
+        },
+    }
+
+    Ok(replaced)
+}
+
+/// Finds the byte range of the chart version's text.
+fn version_span(text: &str) -> Result<Range<usize>> {
+    match value_span(text, VERSION_KEY)? {
+        Some(range) => Ok(range),
+        None => Err(VersioError::MissingVersion {
+            path: None,
+            key: VERSION_KEY.to_string(),
+        }
+        .into()),
+    }
+}
+
+/// Finds the byte range of the text of the top-level `key`, without any quotes around it.
+///
+/// Charts are plain mappings, so a top-level key is one at the start of a line. Its value must be
+/// a scalar on the same line.
+fn value_span(text: &str, key: &str) -> Result<Option<Range<usize>>> {
+    let mut start = 0;
+    for line in text.split_inclusive('\n') {
+        let line_start = start;
+        start += line.len();
+        let rest = match line.strip_prefix(key) {
+            Some(rest) => rest.trim_start_matches([' ', '\t']),
+            None => continue,
+        };
+        let value = match rest.strip_prefix(':') {
+            Some(value) => value.trim_start_matches([' ', '\t']),
+            None => continue,
+        };
+        let value_start = line_start + line.len() - value.len();
+        let value = value.trim_end_matches(['\r', '\n']);
+        let not_string = || VersioError::VersionNotString {
+            path: None,
+            key: key.to_string(),
+        };
+
+        let range = match value.chars().next() {
+            Some(quote @ '"') | Some(quote @ '\'') => match value[1..].find(quote) {
+                Some(end) => value_start + 1..value_start + 1 + end,
+                None => return Err(not_string().into()),
+            },
+            None | Some('#') | Some('|') | Some('>') | Some('[') | Some('{') => {
+                return Err(not_string().into())
+            }
+            Some(_) => {
+                let end = value.find(" #").unwrap_or(value.len());
+                value_start..value_start + value[..end].trim_end().len()
+            }
+        };
+
+        return Ok(Some(range));
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const CHART: &str = "# The service chart\n\
+                         apiVersion: v2\n\
+                         name: service\n\
+                         version: 1.2.3 # bumped by versio\n\
+                         appVersion: \"0.9.0\"\n\
+                         dependencies:\n  \
+                         - name: redis\n    \
+                         version: 17.0.0\n";
+
+    fn version(text: &str) -> Version {
+        Version::parse(text).unwrap()
+    }
+
+    #[test]
+    fn read() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("Chart.yaml");
+        fs::write(&path, CHART).unwrap();
+
+        assert_eq!(read_version(&path).unwrap(), version("1.2.3"));
+    }
+
+    #[test]
+    fn read_quoted() {
+        let text = "name: service\nversion: '1.2.3'\r\n";
+
+        assert_eq!(&text[version_span(text).unwrap()], "1.2.3");
+    }
+
+    #[test]
+    fn read_missing() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("Chart.yaml");
+        fs::write(&path, "name: service\ndependencies:\n  - version: 1.0.0\n").unwrap();
+        let err = read_version(&path)
+            .unwrap_err()
+            .downcast::<VersioError>()
+            .expect("should be a versio error");
+
+        assert_eq!(err.exit_code(), 5);
+        assert_eq!(
+            err.to_string(),
+            format!("cannot find version key 'version' in {}", path.display())
+        );
+    }
+
+    #[test]
+    fn read_not_string() {
+        let err = version_span("version:\n  major: 1\n")
+            .unwrap_err()
+            .downcast::<VersioError>()
+            .expect("should be a versio error");
+
+        assert_eq!(err.exit_code(), 6);
+    }
+
+    #[test]
+    fn replace_version() {
+        assert_eq!(
+            replace(CHART, &version("1.3.0"), &AppVersion::Keep).unwrap(),
+            CHART.replace("version: 1.2.3", "version: 1.3.0")
+        );
+    }
+
+    #[test]
+    fn replace_app_version() {
+        assert_eq!(
+            replace(CHART, &version("1.3.0"), &AppVersion::Chart).unwrap(),
+            CHART
+                .replace("version: 1.2.3", "version: 1.3.0")
+                .replace("appVersion: \"0.9.0\"", "appVersion: \"1.3.0\"")
+        );
+    }
+
+    #[test]
+    fn replace_app_version_from_crate() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let manifest = dir.path().join("Cargo.toml");
+        fs::write(
+            &manifest,
+            "[package]\nname = \"service\"\nversion = \"0.9.1\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            replace(CHART, &version("1.2.4"), &AppVersion::Crate(manifest)).unwrap(),
+            CHART
+                .replace("version: 1.2.3", "version: 1.2.4")
+                .replace("appVersion: \"0.9.0\"", "appVersion: \"0.9.1\"")
+        );
+    }
+
+    #[test]
+    fn replace_app_version_missing() {
+        assert_eq!(
+            replace(
+                "name: service\r\nversion: 1.2.3\r\n# end\r\n",
+                &version("1.3.0"),
+                &AppVersion::Chart
+            )
+            .unwrap(),
+            "name: service\r\nversion: 1.3.0\r\nappVersion: \"1.3.0\"\r\n# end\r\n"
+        );
+        assert_eq!(
+            replace("version: 1.2.3", &version("1.3.0"), &AppVersion::Chart).unwrap(),
+            "version: 1.3.0\nappVersion: \"1.3.0\"\n"
+        );
+    }
+}
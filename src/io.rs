@@ -3,23 +3,73 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use crate::cargo::Manifest;
-use crate::version::Version;
+use crate::error::{self, VersioError};
+use crate::version::SchemeVersion;
 use crate::Result;
-use std::fs::File;
+use std::fs::{self, File, OpenOptions};
 use std::io::{BufReader, Cursor, Read, Write};
 use std::path::Path;
 use std::str::FromStr;
+use tempfile::NamedTempFile;
 
 pub fn bufreader(path: &Path) -> Result<BufReader<File>> {
-    let file = File::open(path)?;
+    let file = File::open(path).map_err(|cause| VersioError::io(path, cause))?;
     Ok(BufReader::new(file))
 }
 
-pub fn read_version<R: Read>(reader: &mut R) -> Result<Version> {
+/// Replaces the contents of the file at `path` with what `write` produces.
+///
+/// The new contents are written to a temporary file in the same directory which is then renamed
+/// over `path`, so the file is either fully updated or left untouched. The mode of an existing
+/// file is preserved and a symlink is followed to the file it points to.
+pub fn write_file<F>(path: &Path, write: F) -> Result<()>
+where
+    F: FnOnce(&mut File) -> Result<()>,
+{
+    let path = match fs::canonicalize(path) {
+        Ok(target) => target,
+        Err(_) => path.to_path_buf(),
+    };
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let io_err = |cause| VersioError::io(&path, cause);
+
+    let mut tempfile = NamedTempFile::new_in(dir).map_err(io_err)?;
+    if let Ok(metadata) = fs::metadata(&path) {
+        fs::set_permissions(tempfile.path(), metadata.permissions()).map_err(io_err)?;
+    }
+    write(tempfile.as_file_mut())?;
+    tempfile.as_file().sync_all().map_err(io_err)?;
+    tempfile.persist(&path).map_err(|err| io_err(err.error))?;
+
+    Ok(())
+}
+
+/// Appends `contents` to the file at `path`, creating it if it does not exist.
+pub fn append_file(path: &Path, contents: &str) -> Result<()> {
+    let io_err = |cause| VersioError::io(path, cause);
+    let mut file = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(path)
+        .map_err(io_err)?;
+    file.write_all(contents.as_bytes()).map_err(io_err)?;
+
+    Ok(())
+}
+
+pub fn read_version<V: SchemeVersion, R: Read>(reader: &mut R) -> Result<V> {
     let mut buf = String::new();
     reader.read_to_string(&mut buf)?;
 
-    Ok(Version::from_str(buf.trim())?)
+    Ok(V::parse(buf.trim())?)
+}
+
+/// Reads a version from the file at `path`, naming the file in any error.
+pub fn read_version_file<V: SchemeVersion>(path: &Path) -> Result<V> {
+    read_version(&mut bufreader(path)?).map_err(|err| error::with_path(err, path))
 }
 
 pub fn read_manifest<R: Read>(reader: &mut R) -> Result<Manifest> {
@@ -29,7 +79,17 @@ pub fn read_manifest<R: Read>(reader: &mut R) -> Result<Manifest> {
     Ok(Manifest::from_str(&buf)?)
 }
 
-pub fn write_version<W: Write>(writer: &mut W, version: &Version) -> Result<()> {
+/// Reads the whole of the text file at `path`.
+pub fn read_text_file(path: &Path) -> Result<String> {
+    Ok(fs::read_to_string(path).map_err(|cause| VersioError::io(path, cause))?)
+}
+
+/// Reads a Cargo manifest from the file at `path`, naming the file in any error.
+pub fn read_manifest_file(path: &Path) -> Result<Manifest> {
+    read_manifest(&mut bufreader(path)?).map_err(|err| error::with_path(err, path))
+}
+
+pub fn write_version<W: Write, V: SchemeVersion>(writer: &mut W, version: &V) -> Result<()> {
     Ok(writeln!(writer, "{}", version)?)
 }
 
@@ -38,3 +98,77 @@ pub fn write_manifest<W: Write>(writer: &mut W, manifest: &Manifest) -> Result<(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::Version;
+    use failure::bail;
+
+    #[test]
+    fn write_file_replaces_contents() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("VERSION.txt");
+        fs::write(&path, "1.2.3\n").unwrap();
+        write_file(&path, |file| {
+            write_version(file, &Version::from_str("1.3.0").unwrap())
+        })
+        .unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1.3.0\n");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn write_file_failure_keeps_original() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("VERSION.txt");
+        fs::write(&path, "1.2.3\n").unwrap();
+        let result = write_file(&path, |file| {
+            file.write_all(b"1.")?;
+            bail!("serialization failed")
+        });
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1.2.3\n");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_file_preserves_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("VERSION.txt");
+        fs::write(&path, "1.2.3\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o640)).unwrap();
+        write_file(&path, |file| {
+            write_version(file, &Version::from_str("1.3.0").unwrap())
+        })
+        .unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_file_follows_symlink() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let target = dir.path().join("VERSION.txt");
+        let link = dir.path().join("link.txt");
+        fs::write(&target, "1.2.3\n").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+        write_file(&link, |file| {
+            write_version(file, &Version::from_str("1.3.0").unwrap())
+        })
+        .unwrap();
+
+        assert!(fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "1.3.0\n");
+    }
+}
@@ -0,0 +1,501 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::error::VersioError;
+use crate::Result;
+use std::fmt;
+use std::ops::Range;
+
+/// A value found in a JSON document by its path of object keys.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Found {
+    /// A string, with the byte range of its contents between the quotes.
+    String { range: Range<usize>, value: String },
+    /// Any value other than a string.
+    Other,
+}
+
+/// Finds the value under the object keys of `path` in the JSON document `text`, without
+/// otherwise parsing or reformatting it, so the value can be replaced in place.
+///
+/// The whole document is checked to be valid JSON. When a key is repeated, the last value wins,
+/// as it would for most JSON parsers.
+pub fn find(text: &str, path: &[&str]) -> Result<Option<Found>> {
+    let mut scanner = Scanner { text, pos: 0 };
+    scanner.skip_whitespace();
+    let found = scanner.value(Some(path))?;
+    scanner.skip_whitespace();
+    if scanner.pos < text.len() {
+        return Err(scanner.error("expected the end of the document"));
+    }
+
+    Ok(found)
+}
+
+/// A JSON value to be written out, such as a report of what a command did.
+///
+/// The keys of an object are written in the order they are given.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(u64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(&'static str, Value)>),
+}
+
+impl From<Option<String>> for Value {
+    fn from(value: Option<String>) -> Self {
+        value.map_or(Value::Null, Value::String)
+    }
+}
+
+/// Writes the value compactly, on a single line.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Null => f.write_str("null"),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Number(value) => write!(f, "{}", value),
+            Value::String(value) => write_string(f, value),
+            Value::Array(values) => {
+                f.write_str("[")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_str("]")
+            }
+            Value::Object(members) => {
+                f.write_str("{")?;
+                for (index, (key, value)) in members.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, value: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in value.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    f.write_str("\"")
+}
+
+struct Scanner<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn error(&self, message: &str) -> failure::Error {
+        VersioError::json(self.text, self.pos, message).into()
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).cloned()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<()> {
+        if self.peek() != Some(byte) {
+            return Err(self.error(&format!("expected '{}'", byte as char)));
+        }
+        self.pos += 1;
+
+        Ok(())
+    }
+
+    /// Scans a value, looking for `path` within it, or only skipping it when `path` is `None`.
+    fn value(&mut self, path: Option<&[&str]>) -> Result<Option<Found>> {
+        match self.peek() {
+            Some(b'{') => {
+                let found = self.object(path)?;
+                Ok(match path {
+                    Some([]) => Some(Found::Other),
+                    _ => found,
+                })
+            }
+            Some(b'[') => {
+                self.array()?;
+                Ok(match path {
+                    Some([]) => Some(Found::Other),
+                    _ => None,
+                })
+            }
+            Some(b'"') => {
+                let (range, value) = self.string()?;
+                Ok(match path {
+                    Some([]) => Some(Found::String { range, value }),
+                    _ => None,
+                })
+            }
+            Some(_) => {
+                self.literal()?;
+                Ok(match path {
+                    Some([]) => Some(Found::Other),
+                    _ => None,
+                })
+            }
+            None => Err(self.error("expected a value")),
+        }
+    }
+
+    fn object(&mut self, path: Option<&[&str]>) -> Result<Option<Found>> {
+        self.expect(b'{')?;
+        self.skip_whitespace();
+        let mut found = None;
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(found);
+        }
+
+        loop {
+            self.skip_whitespace();
+            let (_, key) = self.string()?;
+            self.skip_whitespace();
+            self.expect(b':')?;
+            self.skip_whitespace();
+            match path {
+                Some([first, rest @ ..]) if *first == key => found = self.value(Some(rest))?,
+                _ => {
+                    self.value(None)?;
+                }
+            }
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(found);
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<()> {
+        self.expect(b'[')?;
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(());
+        }
+
+        loop {
+            self.skip_whitespace();
+            self.value(None)?;
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    /// Scans a string, returning the byte range of its contents and its unescaped value.
+    fn string(&mut self) -> Result<(Range<usize>, String)> {
+        self.expect(b'"')?;
+        let start = self.pos;
+        let mut value = String::new();
+
+        loop {
+            let c = match self.text[self.pos..].chars().next() {
+                Some(c) => c,
+                None => return Err(self.error("unterminated string")),
+            };
+            match c {
+                '"' => {
+                    let range = start..self.pos;
+                    self.pos += 1;
+                    return Ok((range, value));
+                }
+                '\\' => {
+                    self.pos += 1;
+                    value.push(self.escape()?);
+                }
+                c if c < ' ' => return Err(self.error("control character in string")),
+                c => {
+                    value.push(c);
+                    self.pos += c.len_utf8();
+                }
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Result<char> {
+        let c = match self.peek() {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'/') => '/',
+            Some(b'b') => '\u{8}',
+            Some(b'f') => '\u{c}',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') => {
+                let hex = self
+                    .text
+                    .get(self.pos + 1..self.pos + 5)
+                    // `from_str_radix` alone would also accept a leading sign
+                    .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok());
+                let c = match hex {
+                    // Unpaired surrogates only matter for comparing keys, which never hold them
+                    Some(code) => std::char::from_u32(code).unwrap_or('\u{fffd}'),
+                    None => return Err(self.error("invalid unicode escape")),
+                };
+                self.pos += 5;
+                return Ok(c);
+            }
+            _ => return Err(self.error("invalid escape")),
+        };
+        self.pos += 1;
+
+        Ok(c)
+    }
+
+    /// Scans a number, `true`, `false` or `null`.
+    fn literal(&mut self) -> Result<()> {
+        let start = self.pos;
+        while let Some(b'-') | Some(b'+') | Some(b'.') | Some(b'0'..=b'9') | Some(b'a'..=b'z')
+        | Some(b'E') = self.peek()
+        {
+            self.pos += 1;
+        }
+
+        match &self.text[start..self.pos] {
+            "true" | "false" | "null" => Ok(()),
+            number if is_number(number) => Ok(()),
+            _ => {
+                self.pos = start;
+                Err(self.error("expected a value"))
+            }
+        }
+    }
+}
+
+/// Determines whether `text` is a number as JSON defines it: an optional `-`, then `0` or digits
+/// which do not start with `0`, then an optional fraction and an optional exponent.
+fn is_number(text: &str) -> bool {
+    fn digits(text: &str) -> &str {
+        text.trim_start_matches(|c: char| c.is_ascii_digit())
+    }
+
+    let rest = text.strip_prefix('-').unwrap_or(text);
+    let rest = match rest.strip_prefix('0') {
+        Some(rest) => rest,
+        None if rest.starts_with(|c: char| c.is_ascii_digit()) => digits(rest),
+        None => return false,
+    };
+    let rest = match rest.strip_prefix('.') {
+        Some(fraction) if fraction.starts_with(|c: char| c.is_ascii_digit()) => digits(fraction),
+        Some(_) => return false,
+        None => rest,
+    };
+    let rest = match rest.strip_prefix(|c| c == 'e' || c == 'E') {
+        Some(exponent) => {
+            let exponent = exponent
+                .strip_prefix(|c| c == '-' || c == '+')
+                .unwrap_or(exponent);
+            if !exponent.starts_with(|c: char| c.is_ascii_digit()) {
+                return false;
+            }
+            digits(exponent)
+        }
+        None => rest,
+    };
+
+    rest.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(text: &str, path: &[&str]) -> (String, String) {
+        match find(text, path).unwrap() {
+            Some(Found::String { range, value }) => (text[range].to_string(), value),
+            found => panic!("should find a string: {:?}", found),
+        }
+    }
+
+    #[test]
+    fn top_level() {
+        let text = "{\n  \"name\": \"a\",\n  \"version\": \"1.2.3\",\n  \"private\": true\n}\n";
+
+        assert_eq!(
+            string(text, &["version"]),
+            ("1.2.3".to_string(), "1.2.3".to_string())
+        );
+    }
+
+    #[test]
+    fn nested() {
+        let text = r#"{"version":"1.0.0","packages":{"":{"version":"1.2.3"},"node_modules/a":{"version":"9.9.9"}}}"#;
+
+        assert_eq!(string(text, &["packages", "", "version"]).0, "1.2.3");
+        assert_eq!(
+            find(text, &["packages", "node_modules/b", "version"]).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn ignores_nested_keys() {
+        let text = r#"{"dependencies":{"version":"2.0.0"},"list":[{"version":"3"}]}"#;
+
+        assert_eq!(find(text, &["version"]).unwrap(), None);
+    }
+
+    #[test]
+    fn escapes() {
+        let text = r#"{"desc":"a \"quoted\" \\ é value","version":"1.2.3"}"#;
+
+        assert_eq!(string(text, &["version"]).0, "1.2.3");
+        assert_eq!(string(text, &["desc"]).1, "a \"quoted\" \\ \u{e9} value");
+    }
+
+    #[test]
+    fn invalid_unicode_escape() {
+        for text in &[
+            r#"{"a": "\u+041"}"#,
+            r#"{"a": "\u00g1"}"#,
+            r#"{"a": "\u00"}"#,
+        ] {
+            let err = find(text, &["version"]).unwrap_err();
+
+            assert!(
+                err.to_string().contains("invalid unicode escape"),
+                "{}: {}",
+                text,
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn not_a_string() {
+        assert_eq!(
+            find(r#"{"version": 1.5e3}"#, &["version"]).unwrap(),
+            Some(Found::Other)
+        );
+        assert_eq!(
+            find(r#"{"version": {"major": 1}}"#, &["version"]).unwrap(),
+            Some(Found::Other)
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for text in &[
+            "",
+            "{",
+            r#"{"version": "1.2.3",}"#,
+            r#"{"version" "1.2.3"}"#,
+            r#"{"version": nope}"#,
+            r#"{"a": 1} {}"#,
+            r#"{"a": 1.}"#,
+            r#"{"a": 01}"#,
+            r#"{"a": -inf}"#,
+            r#"{"a": .5}"#,
+            r#"{"a": 1e}"#,
+            r#"{"a": +1}"#,
+        ] {
+            assert!(
+                find(text, &["version"]).is_err(),
+                "{} should be invalid",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn numbers() {
+        for number in &["0", "-0", "12.50", "1e10", "-1.5E-3", "2e+8"] {
+            let text = format!("{{\"a\": {}, \"version\": \"1.2.3\"}}", number);
+
+            assert_eq!(
+                string(&text, &["version"]).1,
+                "1.2.3",
+                "{} should be valid",
+                number
+            );
+        }
+    }
+
+    #[test]
+    fn write_value() {
+        let value = Value::Object(vec![
+            ("version", Value::String("1.2.3".to_string())),
+            ("major", Value::Number(1)),
+            ("pre", None.into()),
+            ("dry_run", Value::Bool(false)),
+            (
+                "files",
+                Value::Array(vec![
+                    Value::String("a \"b\"\\c.toml".to_string()),
+                    Value::String("tab\tline\n\u{1}".to_string()),
+                ]),
+            ),
+        ]);
+
+        assert_eq!(
+            value.to_string(),
+            r#"{"version":"1.2.3","major":1,"pre":null,"dry_run":false,"files":["a \"b\"\\c.toml","tab\tline\n\u0001"]}"#
+        );
+    }
+
+    #[test]
+    fn written_value_is_found() {
+        let text =
+            Value::Object(vec![("version", Value::String("1.2.3\u{e9}".to_string()))]).to_string();
+
+        assert_eq!(string(&text, &["version"]).1, "1.2.3\u{e9}");
+    }
+
+    #[test]
+    fn invalid_position() {
+        let err = find(
+            "{\n  \"version\": \"1.2.3\"\n  \"name\": \"a\"\n}",
+            &["version"],
+        )
+        .unwrap_err()
+        .downcast::<VersioError>()
+        .expect("should be a versio error");
+
+        assert_eq!(err.exit_code(), 4);
+        assert_eq!(
+            err.to_string(),
+            "invalid JSON at line 3, column 3: expected ',' or '}'"
+        );
+    }
+}
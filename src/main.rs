@@ -5,11 +5,26 @@
 use log::{debug, info};
 use std::process;
 use std::result;
-use structopt::StructOpt;
 
+mod calver;
 mod cargo;
 mod cli;
+mod config;
+mod conventional;
+mod diff;
+mod dockerfile;
+mod error;
+mod git;
+mod gradle;
+mod helm;
 mod io;
+mod json;
+mod maven;
+mod npm;
+mod pattern;
+mod pep440;
+mod pyproject;
+mod source;
 mod version;
 
 /// Result type alias, using `Failure` to wrap up contexts and causes
@@ -29,19 +44,22 @@ fn main() {
         // Print the error and all of its underlying causes
         eprintln!("{}", cli::util::pretty_error(&err));
 
-        process::exit(1);
+        process::exit(cli::util::exit_code(&err));
     }
 }
 
 fn try_main() -> Result<()> {
-    let args = cli::Args::from_args();
+    let args = cli::Args::parse();
     cli::util::init_logger(args.verbosity());
     debug!("parsed cli arguments; args={:?}", args);
+    let format = args.format()?;
+    let dry_run = args.dry_run();
 
     use cli::SubCommand::*;
 
     match args.subcmd() {
         Bump { subcmd } => {
+            use cli::bump::sources;
             use cli::BumpSubCommand::*;
 
             match subcmd {
@@ -50,10 +68,16 @@ fn try_main() -> Result<()> {
                     use cli::BumpCargoSubCommand::*;
 
                     match subcmd {
-                        Major(args) => cargo::major::run(args.into()),
-                        Minor(args) => cargo::minor::run(args.into()),
-                        Patch(args) => cargo::patch::run(args.into()),
-                        Set(args) => cargo::set::run(args.into()),
+                        Major(args) => cargo::major::run((args, dry_run).into(), &format),
+                        Minor(args) => cargo::minor::run((args, dry_run).into(), &format),
+                        Patch(args) => cargo::patch::run((args, dry_run).into(), &format),
+                        Breaking(args) => cargo::breaking::run((args, dry_run).into(), &format),
+                        Feature(args) => cargo::feature::run((args, dry_run).into(), &format),
+                        Fix(args) => cargo::fix::run((args, dry_run).into(), &format),
+                        Pre(args) => cargo::pre::run((args, dry_run).into(), &format),
+                        Release(args) => cargo::release::run((args, dry_run).into(), &format),
+                        Auto(args) => cargo::auto::run((args, dry_run).into(), &format),
+                        Set(args) => cargo::set::run((args, dry_run).into(), &format),
                     }
                 }
                 File { subcmd } => {
@@ -61,10 +85,133 @@ fn try_main() -> Result<()> {
                     use cli::BumpFileSubCommand::*;
 
                     match subcmd {
-                        Major(args) => file::major::run(args.into()),
-                        Minor(args) => file::minor::run(args.into()),
-                        Patch(args) => file::patch::run(args.into()),
-                        Set(args) => file::set::run(args.into()),
+                        Major(args) => file::major::run((args, dry_run).into(), &format),
+                        Minor(args) => file::minor::run((args, dry_run).into(), &format),
+                        Patch(args) => file::patch::run((args, dry_run).into(), &format),
+                        Breaking(args) => file::breaking::run((args, dry_run).into(), &format),
+                        Feature(args) => file::feature::run((args, dry_run).into(), &format),
+                        Fix(args) => file::fix::run((args, dry_run).into(), &format),
+                        Pre(args) => file::pre::run((args, dry_run).into(), &format),
+                        Release(args) => file::release::run((args, dry_run).into(), &format),
+                        Auto(args) => file::auto::run((args, dry_run).into(), &format),
+                        Set(args) => file::set::run((args, dry_run).into(), &format),
+                    }
+                }
+                Pattern { subcmd } => {
+                    use cli::BumpPatternSubCommand::*;
+
+                    match subcmd {
+                        Major(args) => sources::major::run((args, dry_run).into(), &format),
+                        Minor(args) => sources::minor::run((args, dry_run).into(), &format),
+                        Patch(args) => sources::patch::run((args, dry_run).into(), &format),
+                        Breaking(args) => sources::breaking::run((args, dry_run).into(), &format),
+                        Feature(args) => sources::feature::run((args, dry_run).into(), &format),
+                        Fix(args) => sources::fix::run((args, dry_run).into(), &format),
+                        Pre(args) => sources::pre::run((args, dry_run).into(), &format),
+                        Post(args) => sources::post::run((args, dry_run).into(), &format),
+                        Dev(args) => sources::dev::run((args, dry_run).into(), &format),
+                        Calver(args) => sources::calver::run((args, dry_run).into(), &format),
+                        Release(args) => sources::release::run((args, dry_run).into(), &format),
+                        Auto(args) => sources::auto::run((args, dry_run).into(), &format),
+                        Set(args) => sources::set::run((args, dry_run).into(), &format),
+                    }
+                }
+                Npm { subcmd } => {
+                    use cli::BumpNpmSubCommand::*;
+
+                    match subcmd {
+                        Major(args) => sources::major::run((args, dry_run).into(), &format),
+                        Minor(args) => sources::minor::run((args, dry_run).into(), &format),
+                        Patch(args) => sources::patch::run((args, dry_run).into(), &format),
+                        Breaking(args) => sources::breaking::run((args, dry_run).into(), &format),
+                        Feature(args) => sources::feature::run((args, dry_run).into(), &format),
+                        Fix(args) => sources::fix::run((args, dry_run).into(), &format),
+                        Pre(args) => sources::pre::run((args, dry_run).into(), &format),
+                        Release(args) => sources::release::run((args, dry_run).into(), &format),
+                        Auto(args) => sources::auto::run((args, dry_run).into(), &format),
+                        Set(args) => sources::set::run((args, dry_run).into(), &format),
+                    }
+                }
+                Helm { subcmd } => {
+                    use cli::BumpHelmSubCommand::*;
+
+                    match subcmd {
+                        Major(args) => sources::major::run((args, dry_run).into(), &format),
+                        Minor(args) => sources::minor::run((args, dry_run).into(), &format),
+                        Patch(args) => sources::patch::run((args, dry_run).into(), &format),
+                        Breaking(args) => sources::breaking::run((args, dry_run).into(), &format),
+                        Feature(args) => sources::feature::run((args, dry_run).into(), &format),
+                        Fix(args) => sources::fix::run((args, dry_run).into(), &format),
+                        Pre(args) => sources::pre::run((args, dry_run).into(), &format),
+                        Release(args) => sources::release::run((args, dry_run).into(), &format),
+                        Auto(args) => sources::auto::run((args, dry_run).into(), &format),
+                        Set(args) => sources::set::run((args, dry_run).into(), &format),
+                    }
+                }
+                Maven { subcmd } => {
+                    use cli::BumpMavenSubCommand::*;
+
+                    match subcmd {
+                        Major(args) => sources::major::run((args, dry_run).into(), &format),
+                        Minor(args) => sources::minor::run((args, dry_run).into(), &format),
+                        Patch(args) => sources::patch::run((args, dry_run).into(), &format),
+                        Breaking(args) => sources::breaking::run((args, dry_run).into(), &format),
+                        Feature(args) => sources::feature::run((args, dry_run).into(), &format),
+                        Fix(args) => sources::fix::run((args, dry_run).into(), &format),
+                        Pre(args) => sources::pre::run((args, dry_run).into(), &format),
+                        Release(args) => sources::release::run((args, dry_run).into(), &format),
+                        Auto(args) => sources::auto::run((args, dry_run).into(), &format),
+                        Set(args) => sources::set::run((args, dry_run).into(), &format),
+                    }
+                }
+                Gradle { subcmd } => {
+                    use cli::BumpGradleSubCommand::*;
+
+                    match subcmd {
+                        Major(args) => sources::major::run((args, dry_run).into(), &format),
+                        Minor(args) => sources::minor::run((args, dry_run).into(), &format),
+                        Patch(args) => sources::patch::run((args, dry_run).into(), &format),
+                        Breaking(args) => sources::breaking::run((args, dry_run).into(), &format),
+                        Feature(args) => sources::feature::run((args, dry_run).into(), &format),
+                        Fix(args) => sources::fix::run((args, dry_run).into(), &format),
+                        Pre(args) => sources::pre::run((args, dry_run).into(), &format),
+                        Release(args) => sources::release::run((args, dry_run).into(), &format),
+                        Auto(args) => sources::auto::run((args, dry_run).into(), &format),
+                        Set(args) => sources::set::run((args, dry_run).into(), &format),
+                    }
+                }
+                Dockerfile { subcmd } => {
+                    use cli::BumpDockerfileSubCommand::*;
+
+                    match subcmd {
+                        Major(args) => sources::major::run((args, dry_run).into(), &format),
+                        Minor(args) => sources::minor::run((args, dry_run).into(), &format),
+                        Patch(args) => sources::patch::run((args, dry_run).into(), &format),
+                        Breaking(args) => sources::breaking::run((args, dry_run).into(), &format),
+                        Feature(args) => sources::feature::run((args, dry_run).into(), &format),
+                        Fix(args) => sources::fix::run((args, dry_run).into(), &format),
+                        Pre(args) => sources::pre::run((args, dry_run).into(), &format),
+                        Release(args) => sources::release::run((args, dry_run).into(), &format),
+                        Auto(args) => sources::auto::run((args, dry_run).into(), &format),
+                        Set(args) => sources::set::run((args, dry_run).into(), &format),
+                    }
+                }
+                Pyproject { subcmd } => {
+                    use cli::BumpPyProjectSubCommand::*;
+
+                    match subcmd {
+                        Major(args) => sources::major::run((args, dry_run).into(), &format),
+                        Minor(args) => sources::minor::run((args, dry_run).into(), &format),
+                        Patch(args) => sources::patch::run((args, dry_run).into(), &format),
+                        Breaking(args) => sources::breaking::run((args, dry_run).into(), &format),
+                        Feature(args) => sources::feature::run((args, dry_run).into(), &format),
+                        Fix(args) => sources::fix::run((args, dry_run).into(), &format),
+                        Pre(args) => sources::pre::run((args, dry_run).into(), &format),
+                        Post(args) => sources::post::run((args, dry_run).into(), &format),
+                        Dev(args) => sources::dev::run((args, dry_run).into(), &format),
+                        Release(args) => sources::release::run((args, dry_run).into(), &format),
+                        Auto(args) => sources::auto::run((args, dry_run).into(), &format),
+                        Set(args) => sources::set::run((args, dry_run).into(), &format),
                     }
                 }
                 Stdin { subcmd } => {
@@ -72,13 +219,53 @@ fn try_main() -> Result<()> {
                     use cli::BumpStdinSubCommand::*;
 
                     match subcmd {
-                        Major(args) => stdin::major::run(args.into()),
-                        Minor(args) => stdin::minor::run(args.into()),
-                        Patch(args) => stdin::patch::run(args.into()),
-                        Set(args) => stdin::set::run(args.into()),
+                        Major(args) => stdin::major::run(args.into(), &format),
+                        Minor(args) => stdin::minor::run(args.into(), &format),
+                        Patch(args) => stdin::patch::run(args.into(), &format),
+                        Breaking(args) => stdin::breaking::run(args.into(), &format),
+                        Feature(args) => stdin::feature::run(args.into(), &format),
+                        Fix(args) => stdin::fix::run(args.into(), &format),
+                        Pre(args) => stdin::pre::run(args.into(), &format),
+                        Release(args) => stdin::release::run(args.into(), &format),
+                        Auto(args) => stdin::auto::run(args.into(), &format),
+                        Set(args) => stdin::set::run(args.into(), &format),
                     }
                 }
+                Major(args) => sources::major::run((args, dry_run).into(), &format),
+                Minor(args) => sources::minor::run((args, dry_run).into(), &format),
+                Patch(args) => sources::patch::run((args, dry_run).into(), &format),
+                Breaking(args) => sources::breaking::run((args, dry_run).into(), &format),
+                Feature(args) => sources::feature::run((args, dry_run).into(), &format),
+                Fix(args) => sources::fix::run((args, dry_run).into(), &format),
+                Pre(args) => sources::pre::run((args, dry_run).into(), &format),
+                Post(args) => sources::post::run((args, dry_run).into(), &format),
+                Dev(args) => sources::dev::run((args, dry_run).into(), &format),
+                Calver(args) => sources::calver::run((args, dry_run).into(), &format),
+                Release(args) => sources::release::run((args, dry_run).into(), &format),
+                Auto(args) => sources::auto::run((args, dry_run).into(), &format),
+                Set(args) => sources::set::run((args, dry_run).into(), &format),
+            }
+        }
+        Get { subcmd } => {
+            use cli::get;
+            use cli::GetSubCommand::*;
+
+            match subcmd {
+                Cargo(args) => get::cargo::run(args.into(), &format),
+                File(args) => get::file::run(args.into(), &format),
+                Stdin(args) => get::stdin::run(args.into(), &format),
+            }
+        }
+        Check { subcmd } => {
+            use cli::check;
+            use cli::CheckSubCommand::*;
+
+            match subcmd {
+                Cargo(args) => check::cargo::run(args.into(), &format),
+                File(args) => check::file::run(args.into(), &format),
+                Stdin(args) => check::stdin::run(args.into(), &format),
             }
         }
+        Verify(args) => cli::verify::run(args.into(), &format),
     }
 }
@@ -0,0 +1,175 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::error::{self, VersioError};
+use crate::io;
+use crate::source::Edit;
+use crate::version::SchemeVersion;
+use crate::Result;
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+
+/// The elements leading from the root to the project's own version. The versions of the parent
+/// and of dependencies are nested deeper, so they are never mistaken for it.
+const VERSION_ELEMENTS: &[&str] = &["project", "version"];
+
+/// Reads the project version of the `pom.xml` at `path`.
+pub fn read_version<V: SchemeVersion>(path: &Path) -> Result<V> {
+    let text = io::read_text_file(path)?;
+    let range = version_span(&text).map_err(|err| error::with_path(err, path))?;
+
+    Ok(V::parse(&text[range]).map_err(|err| err.with_path(path))?)
+}
+
+/// Returns the edit which changes the project version of the `pom.xml` at `path` to `version`.
+///
+/// Only the text of the version element is replaced, so the rest of the document is kept
+/// byte for byte.
+pub fn edit<V: fmt::Display>(path: &Path, version: &V) -> Result<Edit> {
+    let text = io::read_text_file(path)?;
+    let range = version_span(&text).map_err(|err| error::with_path(err, path))?;
+
+    Ok(Edit {
+        path: path.to_path_buf(),
+        contents: format!("{}{}{}", &text[..range.start], version, &text[range.end..]),
+        lines: Vec::new(),
+    })
+}
+
+/// Finds the byte range of the project version's text, without the whitespace around it.
+fn version_span(text: &str) -> Result<Range<usize>> {
+    let mut open: Vec<&str> = Vec::new();
+    let mut pos = 0;
+    while let Some(offset) = text[pos..].find('<') {
+        let start = pos + offset;
+        let rest = &text[start..];
+        let terminator = if rest.starts_with("<!--") {
+            "-->"
+        } else if rest.starts_with("<![CDATA[") {
+            "]]>"
+        } else if rest.starts_with("<?") {
+            "?>"
+        } else {
+            ">"
+        };
+        let end = match rest.find(terminator) {
+            Some(end) => start + end + terminator.len(),
+            None => return Err(VersioError::xml(text, start, "unterminated markup").into()),
+        };
+        pos = end;
+
+        let tag = &text[start + 1..end - 1];
+        if rest.starts_with("<!") || rest.starts_with("<?") || tag.ends_with('/') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            match open.pop() {
+                Some(element) if element == name.trim() => {}
+                _ => {
+                    return Err(VersioError::xml(
+                        text,
+                        start,
+                        &format!("unexpected closing tag </{}>", name.trim()),
+                    )
+                    .into())
+                }
+            }
+            continue;
+        }
+
+        let name = tag.split_whitespace().next().unwrap_or("");
+        open.push(name);
+        if open == VERSION_ELEMENTS {
+            let content_end = match text[end..].find('<') {
+                Some(offset) => end + offset,
+                None => return Err(VersioError::xml(text, end, "unterminated element").into()),
+            };
+            let content = &text[end..content_end];
+            let content_start = end + (content.len() - content.trim_start().len());
+
+            return Ok(content_start..content_start + content.trim().len());
+        }
+    }
+
+    Err(VersioError::MissingVersion {
+        path: None,
+        key: VERSION_ELEMENTS.join("."),
+    }
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::Version;
+
+    const POM: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+                       <project xmlns=\"http://maven.apache.org/POM/4.0.0\">\n  \
+                       <modelVersion>4.0.0</modelVersion>\n  \
+                       <!-- <version>0.0.1</version> -->\n  \
+                       <parent>\n    \
+                       <groupId>com.example</groupId>\n    \
+                       <version>9.9.9</version>\n  \
+                       </parent>\n  \
+                       <artifactId>sdk</artifactId>\n  \
+                       <version>\n    1.2.3-SNAPSHOT\n  </version>\n  \
+                       <dependencies>\n    \
+                       <dependency>\n      \
+                       <version>2.0.0</version>\n      \
+                       <optional/>\n    \
+                       </dependency>\n  \
+                       </dependencies>\n\
+                       </project>\n";
+
+    #[test]
+    fn span() {
+        assert_eq!(&POM[version_span(POM).unwrap()], "1.2.3-SNAPSHOT");
+    }
+
+    #[test]
+    fn span_after_dependencies() {
+        let pom = "<project><dependencies><dependency><version>2.0.0</version></dependency>\
+                   </dependencies><version>1.2.3</version></project>";
+
+        assert_eq!(&pom[version_span(pom).unwrap()], "1.2.3");
+    }
+
+    #[test]
+    fn span_inherited() {
+        let err = version_span("<project><parent><version>1.2.3</version></parent></project>")
+            .unwrap_err()
+            .downcast::<VersioError>()
+            .expect("should be a versio error");
+
+        assert_eq!(err.exit_code(), 5);
+        assert_eq!(err.to_string(), "cannot find version key 'project.version'");
+    }
+
+    #[test]
+    fn span_invalid() {
+        let err = version_span("<project>\n  <parent></project>")
+            .unwrap_err()
+            .downcast::<VersioError>()
+            .expect("should be a versio error");
+
+        assert_eq!(err.exit_code(), 4);
+        assert_eq!(
+            err.to_string(),
+            "invalid XML at line 2, column 11: unexpected closing tag </project>"
+        );
+    }
+
+    #[test]
+    fn edit_keeps_document() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("pom.xml");
+        std::fs::write(&path, POM).unwrap();
+        let version: Version = read_version(&path).unwrap();
+        let edit = edit(&path, &Version::parse("1.3.0").unwrap()).unwrap();
+
+        assert_eq!(version.to_string(), "1.2.3-SNAPSHOT");
+        assert_eq!(edit.contents, POM.replace("1.2.3-SNAPSHOT", "1.3.0"));
+    }
+}
@@ -0,0 +1,202 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::error::{self, VersioError};
+use crate::io;
+use crate::json::{self, Found};
+use crate::source::Edit;
+use crate::version::{SchemeVersion, Version};
+use crate::Result;
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+
+/// The name of the lock file written by npm next to `package.json`.
+pub const LOCK_FILE: &str = "package-lock.json";
+
+/// The keys of the package's own version in a lock file. Lock files before version 2 only have
+/// the first.
+const LOCK_VERSION_KEYS: &[&[&str]] = &[&["version"], &["packages", "", "version"]];
+
+/// Reads the top-level version of the `package.json` at `path`.
+pub fn read_version(path: &Path) -> Result<Version> {
+    let text = io::read_text_file(path)?;
+    let (_, version) = version_span(&text).map_err(|err| error::with_path(err, path))?;
+
+    Ok(version)
+}
+
+/// Returns the edits which change the version of the `package.json` at `path` to `version`,
+/// along with the root entries of the `package-lock.json` beside it, if there is one.
+///
+/// Only the version strings are replaced, so key order, indentation and the trailing newline
+/// are kept as they are.
+pub fn edits<V: fmt::Display>(path: &Path, version: &V) -> Result<Vec<Edit>> {
+    let text = io::read_text_file(path)?;
+    let (range, _) = version_span(&text).map_err(|err| error::with_path(err, path))?;
+    let mut edits = vec![Edit {
+        path: path.to_path_buf(),
+        contents: replace(&text, vec![range], version),
+        lines: Vec::new(),
+    }];
+
+    let lock_path = path.with_file_name(LOCK_FILE);
+    if lock_path.is_file() {
+        let text = io::read_text_file(&lock_path)?;
+        let mut ranges = Vec::with_capacity(LOCK_VERSION_KEYS.len());
+        for key in LOCK_VERSION_KEYS {
+            match json::find(&text, key).map_err(|err| error::with_path(err, &lock_path))? {
+                Some(Found::String { range, .. }) => ranges.push(range),
+                Some(Found::Other) => {
+                    return Err(VersioError::VersionNotString {
+                        path: Some(lock_path),
+                        key: key.join("."),
+                    }
+                    .into())
+                }
+                None => {}
+            }
+        }
+        edits.push(Edit {
+            contents: replace(&text, ranges, version),
+            path: lock_path,
+            lines: Vec::new(),
+        });
+    }
+
+    Ok(edits)
+}
+
+/// Finds the top-level version of a `package.json` and the byte range of its text.
+fn version_span(text: &str) -> Result<(Range<usize>, Version)> {
+    match json::find(text, &["version"])? {
+        Some(Found::String { range, value }) => Ok((range, Version::parse(&value)?)),
+        Some(Found::Other) => Err(VersioError::VersionNotString {
+            path: None,
+            key: "version".to_string(),
+        }
+        .into()),
+        None => Err(VersioError::MissingVersion {
+            path: None,
+            key: "version".to_string(),
+        }
+        .into()),
+    }
+}
+
+/// Replaces the text of each range with `version`.
+fn replace<V: fmt::Display>(text: &str, mut ranges: Vec<Range<usize>>, version: &V) -> String {
+    ranges.sort_by_key(|range| range.start);
+    let version = version.to_string();
+    let mut replaced = String::with_capacity(text.len());
+    let mut last = 0;
+    for range in ranges {
+        replaced.push_str(&text[last..range.start]);
+        replaced.push_str(&version);
+        last = range.end;
+    }
+    replaced.push_str(&text[last..]);
+
+    replaced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::str::FromStr;
+
+    const PACKAGE: &str = "{\n  \"name\": \"client\",\n  \"version\": \"1.2.3\",\n  \
+                           \"dependencies\": {\n    \"left-pad\": \"1.3.0\"\n  }\n}\n";
+
+    const LOCK: &str = "{\n\t\"name\": \"client\",\n\t\"version\": \"1.2.3\",\n\t\
+                        \"lockfileVersion\": 3,\n\t\"packages\": {\n\t\t\"\": {\n\t\t\t\
+                        \"version\": \"1.2.3\"\n\t\t},\n\t\t\"node_modules/left-pad\": {\n\t\t\t\
+                        \"version\": \"1.3.0\"\n\t\t}\n\t}\n}";
+
+    fn version(text: &str) -> Version {
+        Version::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn read() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("package.json");
+        fs::write(&path, PACKAGE).unwrap();
+
+        assert_eq!(read_version(&path).unwrap(), version("1.2.3"));
+    }
+
+    #[test]
+    fn read_missing() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("package.json");
+        fs::write(&path, "{\"name\": \"client\"}").unwrap();
+        let err = read_version(&path)
+            .unwrap_err()
+            .downcast::<VersioError>()
+            .expect("should be a versio error");
+
+        assert_eq!(err.exit_code(), 5);
+        assert_eq!(
+            err.to_string(),
+            format!("cannot find version key 'version' in {}", path.display())
+        );
+    }
+
+    #[test]
+    fn read_not_string() {
+        assert_eq!(
+            version_span("{\"version\": 1}")
+                .unwrap_err()
+                .downcast::<VersioError>()
+                .expect("should be a versio error")
+                .exit_code(),
+            6
+        );
+    }
+
+    #[test]
+    fn edit_without_lock() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("package.json");
+        fs::write(&path, PACKAGE).unwrap();
+        let edits = edits(&path, &version("2.0.0")).unwrap();
+
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].contents, PACKAGE.replace("1.2.3", "2.0.0"));
+    }
+
+    #[test]
+    fn edit_with_lock() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("package.json");
+        fs::write(&path, PACKAGE).unwrap();
+        fs::write(dir.path().join(LOCK_FILE), LOCK).unwrap();
+        let edits = edits(&path, &version("1.3.0-rc.1")).unwrap();
+
+        assert_eq!(edits.len(), 2);
+        assert_eq!(edits[1].path, dir.path().join(LOCK_FILE));
+        assert_eq!(edits[1].contents, LOCK.replace("1.2.3", "1.3.0-rc.1"));
+        assert!(edits[1].contents.contains("\"version\": \"1.3.0\"\n\t\t}"));
+    }
+
+    #[test]
+    fn edit_old_lock() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("package.json");
+        fs::write(&path, PACKAGE).unwrap();
+        let lock = "{\n  \"version\": \"1.2.3\",\n  \"lockfileVersion\": 1,\n  \
+                    \"dependencies\": {\n    \"left-pad\": {\n      \"version\": \"1.2.3\"\n    \
+                    }\n  }\n}\n";
+        fs::write(dir.path().join(LOCK_FILE), lock).unwrap();
+        let edits = edits(&path, &version("1.2.4")).unwrap();
+
+        assert_eq!(
+            edits[1].contents,
+            lock.replacen("1.2.3", "1.2.4", 1),
+            "only the root version should change"
+        );
+    }
+}
@@ -0,0 +1,271 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::version::SchemeVersion;
+use crate::Result;
+use failure::{bail, format_err};
+use regex::Regex;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// The name of the capture group which holds the version.
+const GROUP: &str = "version";
+
+/// A regular expression which finds a version within arbitrary text, such as
+/// `const VERSION: &str = "(?P<version>[^"]+)";`.
+///
+/// The version is the text captured by the group named `version`. A pattern matches at most once
+/// unless it is given a higher limit, so that a loose pattern cannot silently edit unrelated text.
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    regex: Regex,
+    max_matches: usize,
+}
+
+/// A version found by a `Pattern`, along with the byte range of its text.
+#[derive(Clone, Debug)]
+pub struct Match<V> {
+    pub range: Range<usize>,
+    pub version: V,
+}
+
+impl Pattern {
+    /// Allows the pattern to match up to `max_matches` times.
+    pub fn with_max_matches(mut self, max_matches: usize) -> Self {
+        self.max_matches = max_matches;
+        self
+    }
+
+    /// Returns every version matched in `text`, in order, failing if there is no match or more
+    /// matches than allowed.
+    pub fn matches<V: SchemeVersion>(&self, text: &str) -> Result<Vec<Match<V>>> {
+        let matches: Vec<Match<V>> = self
+            .regex
+            .captures_iter(text)
+            .filter_map(|captures| captures.name(GROUP))
+            .map(|found| {
+                Ok(Match {
+                    range: found.start()..found.end(),
+                    version: V::parse(found.as_str())?,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        match matches.len() {
+            0 => bail!("pattern '{}' does not match", self),
+            count if count > self.max_matches => bail!(
+                "pattern '{}' matches {} times, but at most {} {} allowed",
+                self,
+                count,
+                self.max_matches,
+                if self.max_matches == 1 { "is" } else { "are" }
+            ),
+            _ => Ok(matches),
+        }
+    }
+
+    /// Returns the version matched in `text`, failing if the matches do not agree.
+    pub fn version<V: SchemeVersion>(&self, text: &str) -> Result<V> {
+        let matches = self.matches::<V>(text)?;
+        let first = &matches[0].version;
+        if matches.iter().all(|m| m.version == *first) {
+            return Ok(first.clone());
+        }
+
+        let found: Vec<_> = matches.iter().map(|m| m.version.to_string()).collect();
+        bail!(
+            "pattern '{}' matches differing versions: {}",
+            self,
+            found.join(", ")
+        )
+    }
+
+    /// Replaces every version matched in `text` with `version`, leaving the rest of the text
+    /// untouched.
+    pub fn replace<V: SchemeVersion>(&self, text: &str, version: &V) -> Result<String> {
+        let matches = self.matches::<V>(text)?;
+        let version = version.to_string();
+        let mut replaced = String::with_capacity(text.len());
+        let mut last = 0;
+        for found in &matches {
+            replaced.push_str(&text[last..found.range.start]);
+            replaced.push_str(&version);
+            last = found.range.end;
+        }
+        replaced.push_str(&text[last..]);
+
+        Ok(replaced)
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = failure::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let regex = Regex::new(s).map_err(|err| format_err!("invalid pattern: {}", err))?;
+        if !regex.capture_names().any(|name| name == Some(GROUP)) {
+            bail!(
+                "pattern '{}' has no capture group named '{}', such as (?P<{}>...)",
+                s,
+                GROUP,
+                GROUP
+            );
+        }
+
+        Ok(Pattern {
+            regex,
+            max_matches: 1,
+        })
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.regex.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pep440::Pep440Version;
+    use crate::version::Version;
+
+    const RUST: &str = r#"const VERSION: &str = "(?P<version>[^"]+)";"#;
+
+    #[test]
+    fn from_str_without_group() {
+        match Pattern::from_str("version = (.+)") {
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "pattern 'version = (.+)' has no capture group named 'version', such as \
+                 (?P<version>...)"
+            ),
+            Ok(_) => panic!("should require a version group"),
+        }
+    }
+
+    #[test]
+    fn from_str_invalid() {
+        assert!(Pattern::from_str("(?P<version>").is_err());
+    }
+
+    #[test]
+    fn matches() {
+        let text = "// a\nconst VERSION: &str = \"1.2.3\";\n";
+        let matches = Pattern::from_str(RUST)
+            .unwrap()
+            .matches::<Version>(text)
+            .unwrap();
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(&text[matches[0].range.clone()], "1.2.3");
+        assert_eq!(matches[0].version.to_string(), "1.2.3");
+    }
+
+    #[test]
+    fn matches_too_many() {
+        let text = "const VERSION: &str = \"1.2.3\";\nconst VERSION: &str = \"1.2.3\";\n";
+
+        match Pattern::from_str(RUST).unwrap().matches::<Version>(text) {
+            Err(err) => assert_eq!(
+                err.to_string(),
+                format!(
+                    "pattern '{}' matches 2 times, but at most 1 is allowed",
+                    RUST
+                )
+            ),
+            Ok(_) => panic!("should allow a single match"),
+        }
+    }
+
+    #[test]
+    fn version_agreeing() {
+        let text = "const VERSION: &str = \"1.2.3\";\nconst VERSION: &str = \"1.2.3\";\n";
+
+        assert_eq!(
+            Pattern::from_str(RUST)
+                .unwrap()
+                .with_max_matches(2)
+                .version::<Version>(text)
+                .unwrap()
+                .to_string(),
+            "1.2.3"
+        );
+    }
+
+    #[test]
+    fn version_agreeing_pep440() {
+        let text = "const VERSION: &str = \"1.0rc1\";\nconst VERSION: &str = \"1.0.0-RC1\";\n";
+
+        assert_eq!(
+            Pattern::from_str(RUST)
+                .unwrap()
+                .with_max_matches(2)
+                .version::<Pep440Version>(text)
+                .unwrap()
+                .to_string(),
+            "1.0rc1"
+        );
+    }
+
+    #[test]
+    fn version_differing() {
+        let text = "const VERSION: &str = \"1.2.3\";\nconst VERSION: &str = \"1.2.4\";\n";
+
+        assert!(Pattern::from_str(RUST)
+            .unwrap()
+            .with_max_matches(2)
+            .version::<Version>(text)
+            .is_err());
+    }
+
+    #[test]
+    fn replace() {
+        let text = "// a\nconst VERSION: &str = \"1.2.3\";\r\n// b \"1.2.3\"\n";
+        let version = Version::from_str("1.3.0").unwrap();
+
+        assert_eq!(
+            Pattern::from_str(RUST)
+                .unwrap()
+                .replace(text, &version)
+                .unwrap(),
+            "// a\nconst VERSION: &str = \"1.3.0\";\r\n// b \"1.2.3\"\n"
+        );
+    }
+
+    #[test]
+    fn replace_several() {
+        let text = "VERSION=1.2.3\necho 1.2.3\nVERSION=1.2.3\n";
+        let version = Version::from_str("2.0.0").unwrap();
+
+        assert_eq!(
+            Pattern::from_str(r"VERSION=(?P<version>\S+)")
+                .unwrap()
+                .with_max_matches(2)
+                .replace(text, &version)
+                .unwrap(),
+            "VERSION=2.0.0\necho 1.2.3\nVERSION=2.0.0\n"
+        );
+    }
+
+    #[test]
+    fn version_no_match() {
+        assert!(Pattern::from_str(RUST)
+            .unwrap()
+            .version::<Version>("nothing")
+            .is_err());
+    }
+
+    #[test]
+    fn version_invalid() {
+        let text = "const VERSION: &str = \"1.2\";\n";
+
+        match Pattern::from_str(RUST).unwrap().version::<Version>(text) {
+            Err(err) => assert_eq!(err.to_string(), "invalid version '1.2'"),
+            Ok(_) => panic!("should not parse an invalid version"),
+        }
+    }
+}
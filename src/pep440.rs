@@ -0,0 +1,587 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::error::VersioError;
+use crate::version::{Components, SchemeVersion};
+use failure::{bail, Fail};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use std::fmt;
+use std::result;
+use std::str::FromStr;
+
+/// The pattern of a PEP 440 version, accepting every spelling which normalizes to a valid one,
+/// as given in the appendix of the PEP.
+const PATTERN: &str = r"(?ix)
+    ^\s*v?
+    (?:(?P<epoch>[0-9]+)!)?
+    (?P<release>[0-9]+(?:\.[0-9]+)*)
+    (?P<pre>
+        [-_.]?
+        (?P<pre_l>alpha|a|beta|b|preview|pre|c|rc)
+        [-_.]?
+        (?P<pre_n>[0-9]+)?
+    )?
+    (?P<post>
+        (?:-(?P<post_n1>[0-9]+))
+        |
+        (?:
+            [-_.]?
+            (?P<post_l>post|rev|r)
+            [-_.]?
+            (?P<post_n2>[0-9]+)?
+        )
+    )?
+    (?P<dev>
+        [-_.]?
+        (?P<dev_l>dev)
+        [-_.]?
+        (?P<dev_n>[0-9]+)?
+    )?
+    (?:\+(?P<local>[a-z0-9]+(?:[-_.][a-z0-9]+)*))?
+    \s*$
+";
+
+lazy_static! {
+    static ref REGEX: Regex = Regex::new(PATTERN).expect("pattern should be valid");
+}
+
+/// The phase of a PEP 440 pre-release.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Phase {
+    Alpha,
+    Beta,
+    Candidate,
+}
+
+impl FromStr for Phase {
+    type Err = failure::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "a" | "alpha" => Ok(Phase::Alpha),
+            "b" | "beta" => Ok(Phase::Beta),
+            "c" | "rc" | "pre" | "preview" => Ok(Phase::Candidate),
+            _ => bail!(
+                "unknown pre-release phase '{}', expected one of a, b or rc",
+                s
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Phase::Alpha => write!(f, "a"),
+            Phase::Beta => write!(f, "b"),
+            Phase::Candidate => write!(f, "rc"),
+        }
+    }
+}
+
+/// An error parsing a PEP 440 version.
+#[derive(Debug)]
+pub struct ParseError;
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "not a PEP 440 version")
+    }
+}
+
+impl Fail for ParseError {}
+
+/// A Python package version, as specified by PEP 440, such as `1!2.0.0rc1.post2.dev3+local`.
+///
+/// Versions are kept in their normalized form, so `1.0-RC-1` is displayed as `1.0rc1`. Unlike a
+/// semantic version, the release may have any number of parts, and trailing zeros do not change
+/// it: `1.0` equals `1.0.0`.
+#[derive(Clone, Debug)]
+pub struct Pep440Version {
+    epoch: u64,
+    release: Vec<u64>,
+    pre: Option<(Phase, u64)>,
+    post: Option<u64>,
+    dev: Option<u64>,
+    local: Option<String>,
+}
+
+impl Pep440Version {
+    /// Bumps the first part of the release, so that `1.2.3rc1` becomes `2.0.0`.
+    pub fn bump_major(&self) -> Self {
+        self.bump_release(0)
+    }
+
+    /// Bumps the second part of the release, so that `1.2.3rc1` becomes `1.3.0`.
+    pub fn bump_minor(&self) -> Self {
+        self.bump_release(1)
+    }
+
+    /// Bumps the third part of the release, so that `1.2.3rc1` becomes `1.2.4`.
+    pub fn bump_patch(&self) -> Self {
+        self.bump_release(2)
+    }
+
+    /// Bumps the version for a breaking change, with the same rules as a semantic version: the
+    /// major version from `1.0.0` on, the minor version of a `0.y.z` version, and the patch
+    /// version of a `0.0.z` version.
+    pub fn bump_breaking(&self) -> Self {
+        if self.part(0) > 0 {
+            self.bump_major()
+        } else if self.part(1) > 0 {
+            self.bump_minor()
+        } else {
+            self.bump_patch()
+        }
+    }
+
+    /// Bumps the version for a backwards compatible feature: the minor version from `1.0.0` on,
+    /// and otherwise the patch version.
+    pub fn bump_feature(&self) -> Self {
+        if self.part(0) > 0 {
+            self.bump_minor()
+        } else {
+            self.bump_patch()
+        }
+    }
+
+    /// Bumps the version for a backwards compatible fix, which is always the patch version.
+    pub fn bump_fix(&self) -> Self {
+        self.bump_patch()
+    }
+
+    /// Bumps the pre-release number, so that `1.2.0rc1` becomes `1.2.0rc2`. A development release
+    /// of a pre-release, such as `1.2.0rc1.dev2`, becomes that pre-release.
+    ///
+    /// When `phase` differs from the current one, the pre-release switches to `phase` at 1, so
+    /// that `1.2.0a3` becomes `1.2.0b1`. A version without a pre-release has its patch bumped to
+    /// start `phase`, unless it is a development release, whose release is kept.
+    ///
+    /// Returns `None` if the version has no pre-release and no `phase` is given.
+    pub fn bump_pre(&self, phase: Option<Phase>) -> Option<Self> {
+        let mut next = self.cleared();
+        next.pre = match (self.pre, phase) {
+            (None, None) => return None,
+            (None, Some(phase)) => {
+                if self.dev.is_none() {
+                    next = next.bump_patch();
+                }
+                Some((phase, 1))
+            }
+            (Some((current, _)), Some(phase)) if current != phase => Some((phase, 1)),
+            (Some((current, number)), _) if self.dev.is_some() && self.post.is_none() => {
+                Some((current, number))
+            }
+            (Some((current, number)), _) => Some((current, number + 1)),
+        };
+
+        Some(next)
+    }
+
+    /// Bumps the post-release number, starting at 1, so that `1.2.0` becomes `1.2.0.post1`. A
+    /// development release of a post-release, such as `1.2.0.post1.dev2`, becomes that
+    /// post-release.
+    pub fn bump_post(&self) -> Self {
+        let mut next = self.cleared();
+        next.pre = self.pre;
+        next.post = match (self.post, self.dev) {
+            (Some(post), Some(_)) => Some(post),
+            (Some(post), None) => Some(post + 1),
+            (None, _) => Some(1),
+        };
+
+        next
+    }
+
+    /// Bumps the development release number, so that `1.2.0.dev1` becomes `1.2.0.dev2`.
+    ///
+    /// A version which is not a development release starts one for its next version at 1: the
+    /// next pre-release or post-release when it is one, and otherwise the next patch, so that
+    /// `1.2.0rc1` becomes `1.2.0rc2.dev1` and `1.2.0` becomes `1.2.1.dev1`.
+    pub fn bump_dev(&self) -> Self {
+        if let Some(dev) = self.dev {
+            let mut next = self.clone();
+            next.dev = Some(dev + 1);
+            next.local = None;
+            return next;
+        }
+
+        let mut next = match (self.pre, self.post) {
+            (_, Some(post)) => {
+                let mut next = self.cleared();
+                next.pre = self.pre;
+                next.post = Some(post + 1);
+                next
+            }
+            (Some((phase, number)), None) => {
+                let mut next = self.cleared();
+                next.pre = Some((phase, number + 1));
+                next
+            }
+            (None, None) => self.bump_patch(),
+        };
+        next.dev = Some(1);
+
+        next
+    }
+
+    /// Promotes a pre-release or development release to its final version, so that
+    /// `1.2.0rc2.dev1` becomes `1.2.0`. A development release of a post-release becomes that
+    /// post-release.
+    ///
+    /// Returns `None` if the version is already a release.
+    pub fn release(&self) -> Option<Self> {
+        if self.pre.is_none() && self.dev.is_none() {
+            return None;
+        }
+        let mut next = self.cleared();
+        if self.pre.is_none() {
+            next.post = self.post;
+        }
+
+        Some(next)
+    }
+
+    /// Sets the part of the release at `index`, adding zeros before it if needed.
+    pub fn set_part(&mut self, index: usize, value: u64) {
+        if self.release.len() <= index {
+            self.release.resize(index + 1, 0);
+        }
+        self.release[index] = value;
+    }
+
+    /// Clears the pre-release, post-release and development release.
+    pub fn clear_pre(&mut self) {
+        self.pre = None;
+        self.post = None;
+        self.dev = None;
+    }
+
+    /// Clears the local version label.
+    pub fn clear_local(&mut self) {
+        self.local = None;
+    }
+
+    fn part(&self, index: usize) -> u64 {
+        self.release.get(index).cloned().unwrap_or(0)
+    }
+
+    /// Returns the release alone, keeping its epoch.
+    fn cleared(&self) -> Self {
+        Pep440Version {
+            epoch: self.epoch,
+            release: self.release.clone(),
+            pre: None,
+            post: None,
+            dev: None,
+            local: None,
+        }
+    }
+
+    /// Bumps the part of the release at `index`, zeroing the parts after it and keeping at least
+    /// three parts.
+    fn bump_release(&self, index: usize) -> Self {
+        let mut next = self.cleared();
+        let len = next.release.len().max(3).max(index + 1);
+        next.release.resize(len, 0);
+        next.release[index] += 1;
+        for part in &mut next.release[index + 1..] {
+            *part = 0;
+        }
+
+        next
+    }
+
+    /// Returns the release without trailing zeros, which do not change a version.
+    fn significant_release(&self) -> &[u64] {
+        let len = self
+            .release
+            .iter()
+            .rposition(|part| *part != 0)
+            .map_or(0, |index| index + 1);
+
+        &self.release[..len]
+    }
+}
+
+impl PartialEq for Pep440Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.epoch == other.epoch
+            && self.significant_release() == other.significant_release()
+            && self.pre == other.pre
+            && self.post == other.post
+            && self.dev == other.dev
+            && self.local == other.local
+    }
+}
+
+impl Eq for Pep440Version {}
+
+impl FromStr for Pep440Version {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let captures = REGEX.captures(s).ok_or(ParseError)?;
+        let number = |name: &str| -> result::Result<Option<u64>, ParseError> {
+            captures
+                .name(name)
+                .map(|found| found.as_str().parse().map_err(|_| ParseError))
+                .transpose()
+        };
+
+        let release = captures["release"]
+            .split('.')
+            .map(|part| part.parse().map_err(|_| ParseError))
+            .collect::<result::Result<Vec<u64>, _>>()?;
+        let pre = match captures.name("pre_l") {
+            Some(phase) => Some((
+                Phase::from_str(phase.as_str()).map_err(|_| ParseError)?,
+                number("pre_n")?.unwrap_or(0),
+            )),
+            None => None,
+        };
+        let post = if captures.name("post").is_some() {
+            Some(number("post_n1")?.or(number("post_n2")?).unwrap_or(0))
+        } else {
+            None
+        };
+        let dev = if captures.name("dev").is_some() {
+            Some(number("dev_n")?.unwrap_or(0))
+        } else {
+            None
+        };
+
+        Ok(Pep440Version {
+            epoch: number("epoch")?.unwrap_or(0),
+            release,
+            pre,
+            post,
+            dev,
+            local: local(&captures),
+        })
+    }
+}
+
+/// Returns the normalized local version label, which is lowercase with `.` separators.
+fn local(captures: &Captures) -> Option<String> {
+    captures
+        .name("local")
+        .map(|local| local.as_str().to_ascii_lowercase().replace(['-', '_'], "."))
+}
+
+impl fmt::Display for Pep440Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.epoch > 0 {
+            write!(f, "{}!", self.epoch)?;
+        }
+        let release: Vec<_> = self.release.iter().map(u64::to_string).collect();
+        write!(f, "{}", release.join("."))?;
+        if let Some((phase, number)) = self.pre {
+            write!(f, "{}{}", phase, number)?;
+        }
+        if let Some(post) = self.post {
+            write!(f, ".post{}", post)?;
+        }
+        if let Some(dev) = self.dev {
+            write!(f, ".dev{}", dev)?;
+        }
+        if let Some(local) = &self.local {
+            write!(f, "+{}", local)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl SchemeVersion for Pep440Version {
+    fn parse(text: &str) -> result::Result<Self, VersioError> {
+        Pep440Version::from_str(text).map_err(|cause| VersioError::InvalidVersion {
+            path: None,
+            text: text.to_string(),
+            cause: cause.into(),
+        })
+    }
+
+    /// The pre-release is made of the pre-release, post-release and development segments, such as
+    /// `rc1.dev2`, and the build metadata is the local version label.
+    fn components(&self) -> Components {
+        let part = |index: usize| self.release.get(index).copied().unwrap_or(0);
+        let mut pre = Vec::new();
+        if let Some((phase, number)) = self.pre {
+            pre.push(format!("{}{}", phase, number));
+        }
+        if let Some(post) = self.post {
+            pre.push(format!("post{}", post));
+        }
+        if let Some(dev) = self.dev {
+            pre.push(format!("dev{}", dev));
+        }
+
+        Components {
+            major: part(0),
+            minor: part(1),
+            patch: part(2),
+            pre: Some(pre.join(".")).filter(|pre| !pre.is_empty()),
+            build: self.local.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(s: &str) -> Pep440Version {
+        Pep440Version::from_str(s).unwrap()
+    }
+
+    fn normalized(s: &str) -> String {
+        version(s).to_string()
+    }
+
+    #[test]
+    fn from_str_normalizes() {
+        assert_eq!(normalized("1.2.0"), "1.2.0");
+        assert_eq!(normalized("1.2.0rc1"), "1.2.0rc1");
+        assert_eq!(normalized("v1.0-RC-1"), "1.0rc1");
+        assert_eq!(normalized("1.0.alpha.2"), "1.0a2");
+        assert_eq!(normalized("1.2.3-beta.1"), "1.2.3b1");
+        assert_eq!(normalized("1.0b"), "1.0b0");
+        assert_eq!(normalized("1.0c3"), "1.0rc3");
+        assert_eq!(normalized("1.2.0.post3"), "1.2.0.post3");
+        assert_eq!(normalized("1.2.0-3"), "1.2.0.post3");
+        assert_eq!(normalized("1.2.0.rev"), "1.2.0.post0");
+        assert_eq!(normalized("1.2.0.dev4"), "1.2.0.dev4");
+        assert_eq!(normalized("1.2.0-dev"), "1.2.0.dev0");
+        assert_eq!(normalized("2!1.0"), "2!1.0");
+        assert_eq!(
+            normalized("1!2.0rc1.post2.dev3+Ubuntu-1_2"),
+            "1!2.0rc1.post2.dev3+ubuntu.1.2"
+        );
+    }
+
+    #[test]
+    fn from_str_err() {
+        for text in &["", "1.", "one", "1.2.3-beta.x", "1.0+", "1.0 2"] {
+            assert!(
+                Pep440Version::from_str(text).is_err(),
+                "{} should not parse",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn eq_ignores_trailing_zeros() {
+        assert_eq!(version("1.0"), version("1.0.0"));
+        assert_eq!(version("0"), version("0.0"));
+        assert_ne!(version("1.0"), version("1.0.1"));
+        assert_ne!(version("1.0"), version("1!1.0"));
+    }
+
+    #[test]
+    fn bump_release() {
+        assert_eq!(version("1.2.3rc1").bump_major().to_string(), "2.0.0");
+        assert_eq!(version("1.2.3.post1").bump_minor().to_string(), "1.3.0");
+        assert_eq!(version("1.2").bump_patch().to_string(), "1.2.1");
+        assert_eq!(version("1.2.3.4").bump_minor().to_string(), "1.3.0.0");
+        assert_eq!(version("2!1.2.3+local").bump_patch().to_string(), "2!1.2.4");
+    }
+
+    #[test]
+    fn bump_by_change() {
+        assert_eq!(version("1.2.3").bump_breaking().to_string(), "2.0.0");
+        assert_eq!(version("0.2.3").bump_breaking().to_string(), "0.3.0");
+        assert_eq!(version("0.0.3").bump_breaking().to_string(), "0.0.4");
+        assert_eq!(version("1.2.3").bump_feature().to_string(), "1.3.0");
+        assert_eq!(version("0.2.3").bump_feature().to_string(), "0.2.4");
+        assert_eq!(version("1.2.3").bump_fix().to_string(), "1.2.4");
+    }
+
+    #[test]
+    fn bump_pre() {
+        let bump = |s: &str, phase: Option<Phase>| {
+            version(s)
+                .bump_pre(phase)
+                .map(|version| version.to_string())
+        };
+
+        assert_eq!(bump("1.2.0rc1", None), Some("1.2.0rc2".to_string()));
+        assert_eq!(
+            bump("1.2.0a3", Some(Phase::Beta)),
+            Some("1.2.0b1".to_string())
+        );
+        assert_eq!(
+            bump("1.2.0b3", Some(Phase::Beta)),
+            Some("1.2.0b4".to_string())
+        );
+        assert_eq!(
+            bump("1.2.0", Some(Phase::Candidate)),
+            Some("1.2.1rc1".to_string())
+        );
+        assert_eq!(
+            bump("1.3.0.dev2", Some(Phase::Alpha)),
+            Some("1.3.0a1".to_string())
+        );
+        assert_eq!(bump("1.2.0rc1.dev2", None), Some("1.2.0rc1".to_string()));
+        assert_eq!(bump("1.2.0", None), None);
+    }
+
+    #[test]
+    fn bump_post() {
+        assert_eq!(version("1.2.0").bump_post().to_string(), "1.2.0.post1");
+        assert_eq!(
+            version("1.2.0.post3").bump_post().to_string(),
+            "1.2.0.post4"
+        );
+        assert_eq!(
+            version("1.2.0.post1.dev2").bump_post().to_string(),
+            "1.2.0.post1"
+        );
+        assert_eq!(
+            version("1.2.0rc1+local").bump_post().to_string(),
+            "1.2.0rc1.post1"
+        );
+    }
+
+    #[test]
+    fn bump_dev() {
+        assert_eq!(version("1.2.0.dev4").bump_dev().to_string(), "1.2.0.dev5");
+        assert_eq!(version("1.2.0").bump_dev().to_string(), "1.2.1.dev1");
+        assert_eq!(version("1.2.0rc1").bump_dev().to_string(), "1.2.0rc2.dev1");
+        assert_eq!(
+            version("1.2.0.post1").bump_dev().to_string(),
+            "1.2.0.post2.dev1"
+        );
+    }
+
+    #[test]
+    fn release() {
+        let release = |s: &str| version(s).release().map(|version| version.to_string());
+
+        assert_eq!(release("1.2.0rc2.dev1"), Some("1.2.0".to_string()));
+        assert_eq!(release("1.2.0rc2.post1"), Some("1.2.0".to_string()));
+        assert_eq!(release("1.2.0.post1.dev1"), Some("1.2.0.post1".to_string()));
+        assert_eq!(release("1.2.0.post1"), None);
+        assert_eq!(release("1.2.0"), None);
+    }
+
+    #[test]
+    fn set_part() {
+        let mut version = version("1.2");
+        version.set_part(2, 5);
+        assert_eq!(version.to_string(), "1.2.5");
+
+        version.set_part(0, 3);
+        assert_eq!(version.to_string(), "3.2.5");
+    }
+
+    #[test]
+    fn phase_from_str() {
+        assert_eq!(Phase::from_str("alpha").unwrap(), Phase::Alpha);
+        assert_eq!(Phase::from_str("RC").unwrap(), Phase::Candidate);
+        assert!(Phase::from_str("gamma").is_err());
+    }
+}
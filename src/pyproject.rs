@@ -0,0 +1,240 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::cargo;
+use crate::error::{self, VersioError};
+use crate::io;
+use crate::version::SchemeVersion;
+use crate::Result;
+use std::fmt;
+use std::path::Path;
+use std::result;
+use std::str::FromStr;
+use toml_edit::{Document, Item};
+
+/// A Python `pyproject.toml`, holding its version under either the PEP 621 `[project]` table or
+/// Poetry's `[tool.poetry]` table.
+#[derive(Clone, Debug)]
+pub struct PyProject(Document);
+
+/// The table which holds the version of a project.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Table {
+    Project,
+    Poetry,
+}
+
+impl Table {
+    fn key(self) -> &'static str {
+        match self {
+            Table::Project => "project.version",
+            Table::Poetry => "tool.poetry.version",
+        }
+    }
+}
+
+impl PyProject {
+    pub fn version<V: SchemeVersion>(&self) -> Result<V> {
+        let table = self.table()?;
+
+        Ok(cargo::parse_version(self.version_item(table), table.key())?)
+    }
+
+    pub fn set_version<V: fmt::Display>(&mut self, version: &V) -> Result<()> {
+        let item = match self.table()? {
+            Table::Project => &mut self.0["project"]["version"],
+            Table::Poetry => &mut self.0["tool"]["poetry"]["version"],
+        };
+        cargo::set_string(item, version.to_string());
+
+        Ok(())
+    }
+
+    /// Finds the table which holds the version, preferring `[project]` when it declares one.
+    ///
+    /// A version listed in `project.dynamic` is computed by the build backend, so it cannot be
+    /// bumped in the file.
+    fn table(&self) -> Result<Table> {
+        let project = self.0.get("project");
+        let dynamic = project
+            .and_then(|project| project.get("dynamic"))
+            .and_then(Item::as_array)
+            .is_some_and(|dynamic| {
+                dynamic
+                    .iter()
+                    .any(|field| field.as_str() == Some("version"))
+            });
+        if dynamic {
+            return Err(VersioError::DynamicVersion {
+                path: None,
+                key: "project.dynamic".to_string(),
+            }
+            .into());
+        }
+
+        let poetry = self.0.get("tool").and_then(|tool| tool.get("poetry"));
+        if self.version_item(Table::Project).is_some() {
+            Ok(Table::Project)
+        } else if self.version_item(Table::Poetry).is_some()
+            || (poetry.is_some() && project.is_none())
+        {
+            Ok(Table::Poetry)
+        } else {
+            Ok(Table::Project)
+        }
+    }
+
+    fn version_item(&self, table: Table) -> Option<&Item> {
+        let table = match table {
+            Table::Project => self.0.get("project"),
+            Table::Poetry => self.0.get("tool").and_then(|tool| tool.get("poetry")),
+        };
+
+        table.and_then(|table| table.get("version"))
+    }
+}
+
+impl FromStr for PyProject {
+    type Err = VersioError;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        Document::from_str(s)
+            .map(PyProject)
+            .map_err(|err| VersioError::toml(s, &err))
+    }
+}
+
+impl fmt::Display for PyProject {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Reads the `pyproject.toml` at `path`.
+pub fn read(path: &Path) -> Result<PyProject> {
+    let text = io::read_text_file(path)?;
+
+    PyProject::from_str(&text).map_err(|err| err.with_path(path).into())
+}
+
+/// Reads the version of the `pyproject.toml` at `path`.
+pub fn read_version<V: SchemeVersion>(path: &Path) -> Result<V> {
+    read(path)?
+        .version()
+        .map_err(|err| error::with_path(err, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pep440::Pep440Version;
+    use crate::version::Version;
+
+    fn version_error(input: &str) -> VersioError {
+        match PyProject::from_str(input).unwrap().version::<Version>() {
+            Err(err) => err
+                .downcast::<VersioError>()
+                .expect("should be a versio error"),
+            Ok(v) => panic!("should not find a version: {:?}", v),
+        }
+    }
+
+    #[test]
+    fn project() {
+        let mut project = PyProject::from_str(
+            "[project]\nname = \"bindings\"\nversion = \"1.2.3\"  # kept in step\n",
+        )
+        .unwrap();
+        assert_eq!(project.version::<Version>().unwrap().to_string(), "1.2.3");
+
+        project
+            .set_version(&Version::from_str("1.3.0").unwrap())
+            .unwrap();
+        assert_eq!(
+            project.to_string(),
+            "[project]\nname = \"bindings\"\nversion = \"1.3.0\"  # kept in step\n"
+        );
+    }
+
+    #[test]
+    fn poetry() {
+        let mut project = PyProject::from_str(
+            "[tool.poetry]\nname = \"bindings\"\nversion = \"0.4.0\"\n\n\
+             [tool.poetry.dependencies]\npython = \"^3.8\"\n",
+        )
+        .unwrap();
+        assert_eq!(project.version::<Version>().unwrap().to_string(), "0.4.0");
+
+        project
+            .set_version(&Version::from_str("0.4.1").unwrap())
+            .unwrap();
+        assert_eq!(
+            project.to_string(),
+            "[tool.poetry]\nname = \"bindings\"\nversion = \"0.4.1\"\n\n\
+             [tool.poetry.dependencies]\npython = \"^3.8\"\n"
+        );
+    }
+
+    #[test]
+    fn pep440() {
+        let mut project =
+            PyProject::from_str("[project]\nname = \"bindings\"\nversion = \"1.2.0rc1\"\n")
+                .unwrap();
+        let version = project.version::<Pep440Version>().unwrap();
+        assert_eq!(version.to_string(), "1.2.0rc1");
+
+        project.set_version(&version.bump_dev()).unwrap();
+        assert_eq!(
+            project.to_string(),
+            "[project]\nname = \"bindings\"\nversion = \"1.2.0rc2.dev1\"\n"
+        );
+    }
+
+    #[test]
+    fn project_preferred() {
+        let project = PyProject::from_str(
+            "[project]\nversion = \"2.0.0\"\n\n[tool.poetry]\nversion = \"1.0.0\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(project.version::<Version>().unwrap().to_string(), "2.0.0");
+    }
+
+    #[test]
+    fn dynamic() {
+        let mut project = PyProject::from_str(
+            "[project]\nname = \"bindings\"\ndynamic = [\"version\", \"readme\"]\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            project.version::<Version>().unwrap_err().to_string(),
+            "version is declared dynamic in project.dynamic and is set by the build backend"
+        );
+        assert!(project
+            .set_version(&Version::from_str("1.0.0").unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn missing() {
+        match version_error("[project]\nname = \"bindings\"\n") {
+            VersioError::MissingVersion { key, .. } => assert_eq!(key, "project.version"),
+            err => panic!("should be missing: {:?}", err),
+        }
+        match version_error("[tool.poetry]\nname = \"bindings\"\n") {
+            VersioError::MissingVersion { key, .. } => assert_eq!(key, "tool.poetry.version"),
+            err => panic!("should be missing: {:?}", err),
+        }
+        match version_error("[build-system]\nrequires = [\"poetry-core\"]\n") {
+            VersioError::MissingVersion { key, .. } => assert_eq!(key, "project.version"),
+            err => panic!("should be missing: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn not_a_string() {
+        assert_eq!(version_error("[project]\nversion = 1\n").exit_code(), 6);
+    }
+}
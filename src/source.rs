@@ -0,0 +1,318 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::cargo;
+use crate::dockerfile;
+use crate::error;
+use crate::gradle;
+use crate::helm::{self, AppVersion};
+use crate::io;
+use crate::maven;
+use crate::npm;
+use crate::pattern::Pattern;
+use crate::pyproject;
+use crate::version::{SchemeVersion, Version};
+use crate::Result;
+use failure::{bail, ResultExt};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// The new contents of a file.
+#[derive(Clone, Debug)]
+pub struct Edit {
+    pub path: PathBuf,
+    pub contents: String,
+    /// The lines holding a changed version, starting at 1, which are reported once the file is
+    /// written. Only kept for files whose versions are spread over several lines.
+    pub lines: Vec<usize>,
+}
+
+/// A file which holds a version, and how the version is found within it.
+#[derive(Clone, Debug)]
+pub enum Source {
+    /// A Cargo manifest, whose version may be inherited from its workspace.
+    Cargo(PathBuf),
+    /// A file holding nothing but the version.
+    File(PathBuf),
+    /// A file holding the version somewhere in its text, found by a pattern.
+    Pattern(PathBuf, Pattern),
+    /// An npm `package.json`, whose lock file is kept in step.
+    Npm(PathBuf),
+    /// A Python `pyproject.toml`.
+    PyProject(PathBuf),
+    /// A Helm `Chart.yaml`, and what its `appVersion` is set to.
+    Helm(PathBuf, AppVersion),
+    /// A Maven `pom.xml`, whose project version is the one held.
+    Maven(PathBuf),
+    /// A Gradle `gradle.properties`.
+    Gradle(PathBuf),
+    /// A Dockerfile, whose version labels and build arguments are kept in step.
+    Dockerfile(PathBuf),
+}
+
+impl Source {
+    /// Returns a short name for the kind of source.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Source::Cargo(_) => "cargo",
+            Source::File(_) => "file",
+            Source::Pattern(..) => "pattern",
+            Source::Npm(_) => "npm",
+            Source::PyProject(_) => "pyproject",
+            Source::Helm(..) => "helm",
+            Source::Maven(_) => "maven",
+            Source::Gradle(_) => "gradle",
+            Source::Dockerfile(_) => "dockerfile",
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            Source::Cargo(path)
+            | Source::File(path)
+            | Source::Pattern(path, _)
+            | Source::Npm(path)
+            | Source::PyProject(path)
+            | Source::Helm(path, _)
+            | Source::Maven(path)
+            | Source::Gradle(path)
+            | Source::Dockerfile(path) => path,
+        }
+    }
+
+    /// Reads the version held in the source, parsed by the scheme of `V`.
+    pub fn read_version<V: SchemeVersion>(&self) -> Result<V> {
+        match self {
+            Source::Cargo(path) => Ok(V::parse(&cargo::read_version(path)?.to_string())?),
+            Source::File(path) => io::read_version_file(path),
+            Source::Pattern(path, pattern) => {
+                let text = io::read_text_file(path)?;
+                Ok(pattern
+                    .version(&text)
+                    .with_context(|_| format!("cannot find the version in {}", path.display()))?)
+            }
+            Source::Npm(path) => Ok(V::parse(&npm::read_version(path)?.to_string())?),
+            Source::PyProject(path) => pyproject::read_version(path),
+            Source::Helm(path, _) => Ok(V::parse(&helm::read_version(path)?.to_string())?),
+            Source::Maven(path) => maven::read_version(path),
+            Source::Gradle(path) => gradle::read_version(path),
+            Source::Dockerfile(path) => dockerfile::read_version(path),
+        }
+    }
+
+    /// Checks that `version` is a semantic version, the only kind Cargo manifests, npm packages
+    /// and Helm charts can hold.
+    fn semver<V: fmt::Display>(&self, version: &V) -> Result<Version> {
+        match Version::from_str(&version.to_string()) {
+            Ok(version) => Ok(version),
+            Err(_) => bail!(
+                "{} can only hold a semantic version, not {}",
+                self.path().display(),
+                version
+            ),
+        }
+    }
+
+    /// Returns the edits which change the version held in the source to `version`. The first edit
+    /// is to the file holding the version, followed by any files which must be kept in step.
+    ///
+    /// A Cargo manifest which inherits its version has its workspace root edited instead, along
+    /// with any path dependency requirements on the members inheriting it. Cargo
+    /// manifests, npm packages and Helm charts fail to be edited unless `version` is a semantic
+    /// version.
+    pub fn edits<V: SchemeVersion>(&self, version: &V) -> Result<Vec<Edit>> {
+        match self {
+            Source::Cargo(path) => edit_manifest(path, &self.semver(version)?),
+            Source::File(path) => Ok(vec![Edit {
+                path: path.clone(),
+                contents: format!("{}\n", version),
+                lines: Vec::new(),
+            }]),
+            Source::Pattern(path, pattern) => {
+                let text = io::read_text_file(path)?;
+                let contents = pattern
+                    .replace(&text, version)
+                    .with_context(|_| format!("cannot find the version in {}", path.display()))?;
+
+                Ok(vec![Edit {
+                    path: path.clone(),
+                    contents,
+                    lines: Vec::new(),
+                }])
+            }
+            Source::Npm(path) => npm::edits(path, &self.semver(version)?),
+            Source::PyProject(path) => {
+                let mut project = pyproject::read(path)?;
+                project
+                    .set_version(version)
+                    .map_err(|err| error::with_path(err, path))?;
+
+                Ok(vec![Edit {
+                    path: path.clone(),
+                    contents: project.to_string(),
+                    lines: Vec::new(),
+                }])
+            }
+            Source::Helm(path, app_version) => {
+                Ok(vec![helm::edit(path, &self.semver(version)?, app_version)?])
+            }
+            Source::Maven(path) => Ok(vec![maven::edit(path, version)?]),
+            Source::Gradle(path) => Ok(vec![gradle::edit(path, version)?]),
+            Source::Dockerfile(path) => Ok(vec![dockerfile::edit(path, version)?]),
+        }
+    }
+}
+
+/// Edits the version of the Cargo manifest at `path`. A manifest which inherits its version has
+/// its workspace root edited instead, along with the requirements on every member inheriting it.
+fn edit_manifest(path: &Path, version: &Version) -> Result<Vec<Edit>> {
+    let mut manifest = io::read_manifest_file(path)?;
+    if !manifest.inherits_version() {
+        manifest.set_version(version);
+        return Ok(vec![Edit {
+            path: path.to_path_buf(),
+            contents: manifest.to_string(),
+            lines: Vec::new(),
+        }]);
+    }
+
+    let root_path = cargo::find_workspace_root(path, &manifest)?;
+    let mut workspace = cargo::Workspace::load(&root_path)?;
+    let names: Vec<String> = manifest.name().map(String::from).into_iter().collect();
+    workspace.select(&names)?;
+
+    Ok(workspace
+        .set_version(&names, version)
+        .into_iter()
+        .map(|member| Edit {
+            path: member.path.clone(),
+            contents: member.manifest.to_string(),
+            lines: Vec::new(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pep440::Pep440Version;
+    use std::fs;
+
+    #[test]
+    fn read_versions() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let manifest = dir.path().join("Cargo.toml");
+        let file = dir.path().join("VERSION.txt");
+        let script = dir.path().join("install.sh");
+        fs::write(&manifest, "[package]\nversion = \"1.2.3\"\n").unwrap();
+        fs::write(&file, "1.2.4\n").unwrap();
+        fs::write(&script, "#!/bin/sh\nVERSION=1.2.5\necho $VERSION\n").unwrap();
+        let pattern = Pattern::from_str(r"VERSION=(?P<version>\S+)").unwrap();
+
+        assert_eq!(
+            Source::Cargo(manifest)
+                .read_version::<Version>()
+                .unwrap()
+                .to_string(),
+            "1.2.3"
+        );
+        assert_eq!(
+            Source::File(file)
+                .read_version::<Version>()
+                .unwrap()
+                .to_string(),
+            "1.2.4"
+        );
+        assert_eq!(
+            Source::Pattern(script, pattern)
+                .read_version::<Version>()
+                .unwrap()
+                .to_string(),
+            "1.2.5"
+        );
+    }
+
+    #[test]
+    fn read_version_pep440() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let manifest = dir.path().join("Cargo.toml");
+        let file = dir.path().join("VERSION.txt");
+        fs::write(&manifest, "[package]\nversion = \"1.2.3\"\n").unwrap();
+        fs::write(&file, "1.2.3.post1\n").unwrap();
+        let version = Source::File(file).read_version::<Pep440Version>().unwrap();
+
+        assert_eq!(version.to_string(), "1.2.3.post1");
+        assert_eq!(
+            Source::Cargo(manifest.clone())
+                .read_version::<Pep440Version>()
+                .unwrap()
+                .to_string(),
+            "1.2.3"
+        );
+        assert_eq!(
+            Source::Cargo(manifest.clone())
+                .edits(&version)
+                .unwrap_err()
+                .to_string(),
+            format!(
+                "{} can only hold a semantic version, not 1.2.3.post1",
+                manifest.display()
+            )
+        );
+    }
+
+    #[test]
+    fn edit_inherited() {
+        let dir = crate::cargo::test_helpers::fixture(
+            "[workspace]\nmembers = [\"one\", \"two\"]\n\n[workspace.package]\nversion = \"1.2.3\"\n",
+            &[
+                (
+                    "one/Cargo.toml",
+                    "[package]\nname = \"one\"\nversion.workspace = true\n",
+                ),
+                (
+                    "two/Cargo.toml",
+                    "[package]\nname = \"two\"\nversion = \"0.1.0\"\n\n[dependencies]\none = { path = \"../one\", version = \"1.2.3\" }\n",
+                ),
+            ],
+        );
+        let edits = Source::Cargo(dir.path().join("one/Cargo.toml"))
+            .edits(&Version::from_str("1.3.0").unwrap())
+            .unwrap();
+        let root = dir.path().canonicalize().unwrap();
+
+        assert_eq!(edits.len(), 2);
+        assert_eq!(edits[0].path, root.join("Cargo.toml"));
+        assert_eq!(
+            edits[0].contents,
+            "[workspace]\nmembers = [\"one\", \"two\"]\n\n[workspace.package]\nversion = \"1.3.0\"\n"
+        );
+        assert_eq!(edits[1].path, root.join("two/Cargo.toml"));
+        assert_eq!(
+            edits[1].contents,
+            "[package]\nname = \"two\"\nversion = \"0.1.0\"\n\n[dependencies]\none = { path = \"../one\", version = \"1.3.0\" }\n"
+        );
+    }
+
+    #[test]
+    fn read_version_pattern_no_match() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("install.sh");
+        fs::write(&path, "#!/bin/sh\n").unwrap();
+        let pattern = Pattern::from_str(r"VERSION=(?P<version>\S+)").unwrap();
+        let err = Source::Pattern(path.clone(), pattern)
+            .read_version::<Version>()
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!("cannot find the version in {}", path.display())
+        );
+        assert_eq!(
+            err.iter_causes().next().unwrap().to_string(),
+            r"pattern 'VERSION=(?P<version>\S+)' does not match"
+        );
+    }
+}
@@ -2,11 +2,64 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use semver::{Identifier, SemVerError};
+use crate::error::VersioError;
+use failure::bail;
+use semver::{Identifier, ReqParseError, SemVerError};
 use std::fmt;
 use std::result;
 use std::str::FromStr;
 
+/// The scheme a version follows, which decides how it is parsed and bumped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Scheme {
+    /// Semantic Versioning, such as `1.2.0-rc.1`.
+    Semver,
+    /// Python's PEP 440, such as `1.2.0rc1`.
+    Pep440,
+}
+
+impl FromStr for Scheme {
+    type Err = failure::Error;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        match s {
+            "semver" => Ok(Scheme::Semver),
+            "pep440" => Ok(Scheme::Pep440),
+            _ => bail!("unknown version scheme '{}', expected semver or pep440", s),
+        }
+    }
+}
+
+impl fmt::Display for Scheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Scheme::Semver => write!(f, "semver"),
+            Scheme::Pep440 => write!(f, "pep440"),
+        }
+    }
+}
+
+/// A version of some scheme, which can be read from the text held in a file.
+pub trait SchemeVersion: Clone + fmt::Display + PartialEq {
+    /// Parses `text` as a version of this scheme.
+    fn parse(text: &str) -> result::Result<Self, VersioError>;
+
+    /// Returns the parts of the version, as they are reported to other tools.
+    fn components(&self) -> Components;
+}
+
+/// The parts of a version, in the shape of a semantic version whatever its scheme.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Components {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// The pre-release, or `None` for a release.
+    pub pre: Option<String>,
+    /// The build metadata, if there is any.
+    pub build: Option<String>,
+}
+
 #[derive(Clone, Debug)]
 pub struct PreRelease(Vec<Identifier>);
 
@@ -20,6 +73,12 @@ impl FromStr for PreRelease {
     }
 }
 
+impl fmt::Display for PreRelease {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&join_identifiers(&self.0))
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct BuildMetadata(Vec<Identifier>);
 
@@ -33,9 +92,41 @@ impl FromStr for BuildMetadata {
     }
 }
 
-#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+#[derive(Debug, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Version(semver::Version);
 
+impl Version {
+    pub fn major(&self) -> u64 {
+        self.0.major
+    }
+
+    pub fn minor(&self) -> u64 {
+        self.0.minor
+    }
+
+    pub fn patch(&self) -> u64 {
+        self.0.patch
+    }
+
+    /// Returns the pre-release, or an empty string if there is none.
+    pub fn pre_release(&self) -> String {
+        join_identifiers(&self.0.pre)
+    }
+
+    /// Returns the build metadata, or an empty string if there is none.
+    pub fn build_metadata(&self) -> String {
+        join_identifiers(&self.0.build)
+    }
+}
+
+fn join_identifiers(identifiers: &[Identifier]) -> String {
+    identifiers
+        .iter()
+        .map(Identifier::to_string)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
 impl FromStr for Version {
     type Err = SemVerError;
 
@@ -50,12 +141,59 @@ impl fmt::Display for Version {
     }
 }
 
+impl SchemeVersion for Version {
+    fn parse(text: &str) -> result::Result<Self, VersioError> {
+        Version::from_str(text).map_err(|cause| VersioError::InvalidVersion {
+            path: None,
+            text: text.to_string(),
+            cause: cause.into(),
+        })
+    }
+
+    fn components(&self) -> Components {
+        let optional = |text: String| Some(text).filter(|text| !text.is_empty());
+
+        Components {
+            major: self.major(),
+            minor: self.minor(),
+            patch: self.patch(),
+            pre: optional(self.pre_release()),
+            build: optional(self.build_metadata()),
+        }
+    }
+}
+
 impl From<semver::Version> for Version {
     fn from(version: semver::Version) -> Self {
         Version(version)
     }
 }
 
+/// A requirement which versions may satisfy, such as `>=1.4, <2`.
+#[derive(Clone, Debug)]
+pub struct VersionReq(semver::VersionReq);
+
+impl VersionReq {
+    /// Returns whether `version` satisfies this requirement.
+    pub fn matches(&self, version: &Version) -> bool {
+        self.0.matches(&version.0)
+    }
+}
+
+impl FromStr for VersionReq {
+    type Err = ReqParseError;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        Ok(VersionReq(semver::VersionReq::parse(s)?))
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 #[derive(Debug)]
 pub struct VersionBumper {
     version: Version,
@@ -138,6 +276,97 @@ impl VersionBumper {
         self
     }
 
+    /// Bumps the version for a breaking change, following Cargo's semver rules: the major version
+    /// from `1.0.0` on, the minor version of a `0.y.z` version, and the patch version of a
+    /// `0.0.z` version.
+    pub fn bump_breaking(self) -> Version {
+        if self.version.0.major > 0 {
+            self.bump_major()
+        } else if self.version.0.minor > 0 {
+            self.bump_minor()
+        } else {
+            self.bump_patch()
+        }
+    }
+
+    /// Bumps the version for a backwards compatible feature, following Cargo's semver rules: the
+    /// minor version from `1.0.0` on, and otherwise the patch version.
+    pub fn bump_feature(self) -> Version {
+        if self.version.0.major > 0 {
+            self.bump_minor()
+        } else {
+            self.bump_patch()
+        }
+    }
+
+    /// Bumps the version for a backwards compatible fix, which is always the patch version.
+    pub fn bump_fix(self) -> Version {
+        self.bump_patch()
+    }
+
+    /// Bumps the pre-release counter, which is the last numeric identifier of the pre-release, so
+    /// that `1.2.0-rc.1` becomes `1.2.0-rc.2` and `1.2.0-alpha.1.x` becomes `1.2.0-alpha.2.x`. A
+    /// pre-release without a counter has `.1` appended.
+    ///
+    /// When `channel` differs from the identifiers before the counter, the pre-release switches to
+    /// `channel` and its counter restarts at 1, so that `1.2.0-alpha.3` becomes `1.2.0-beta.1`. A
+    /// version without a pre-release has its patch bumped to start `channel` at 1, as a
+    /// pre-release sorts before its release.
+    ///
+    /// Returns `None` if the version has no pre-release and no `channel` is given, and fails if the
+    /// counter is already at its maximum. Any existing build metadata is cleared.
+    pub fn bump_pre(mut self, channel: Option<PreRelease>) -> crate::Result<Option<Version>> {
+        let current = std::mem::take(&mut self.version.0.pre);
+        self.version.0.build = Vec::new();
+        self.pre = None;
+
+        let counter = current
+            .iter()
+            .rposition(|identifier| matches!(identifier, Identifier::Numeric(_)));
+        let pre = match (counter, channel) {
+            (None, None) if current.is_empty() => return Ok(None),
+            (None, Some(channel)) if current.is_empty() => {
+                self.version.0.increment_patch();
+                start_pre(channel)
+            }
+            (Some(index), Some(channel)) if channel.0[..] != current[..index] => start_pre(channel),
+            (Some(index), _) => {
+                let mut pre = current;
+                if let Identifier::Numeric(counter) = &mut pre[index] {
+                    *counter = match counter.checked_add(1) {
+                        Some(next) => next,
+                        None => bail!("pre-release counter {} overflows", counter),
+                    };
+                }
+                pre
+            }
+            (None, Some(channel)) if channel.0 != current => start_pre(channel),
+            (None, _) => {
+                let mut pre = current;
+                pre.push(Identifier::Numeric(1));
+                pre
+            }
+        };
+        self.version.0.pre = pre;
+
+        Ok(Some(self.consume()))
+    }
+
+    /// Promotes a pre-release to its final version by clearing its pre-release and build
+    /// metadata, so that `1.2.0-rc.2+b7` becomes `1.2.0`.
+    ///
+    /// Returns `None` if the version is already a release.
+    pub fn release(mut self) -> Option<Version> {
+        if !self.version.0.is_prerelease() {
+            return None;
+        }
+        self.version.0.pre = Vec::new();
+        self.version.0.build = Vec::new();
+        self.pre = None;
+
+        Some(self.consume())
+    }
+
     pub fn no_bump(self) -> Version {
         self.consume()
     }
@@ -154,6 +383,13 @@ impl VersionBumper {
     }
 }
 
+/// Returns the identifiers of a pre-release on `channel` with its counter starting at 1.
+fn start_pre(channel: PreRelease) -> Vec<Identifier> {
+    let mut pre = channel.0;
+    pre.push(Identifier::Numeric(1));
+    pre
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -216,6 +452,25 @@ mod tests {
             }
         }
 
+        #[test]
+        fn parts() {
+            let version = Version::from_str("1.2.3-rc.1+build.8").unwrap();
+
+            assert_eq!(version.major(), 1);
+            assert_eq!(version.minor(), 2);
+            assert_eq!(version.patch(), 3);
+            assert_eq!(version.pre_release(), "rc.1");
+            assert_eq!(version.build_metadata(), "build.8");
+        }
+
+        #[test]
+        fn parts_empty() {
+            let version = Version::from_str("1.2.3").unwrap();
+
+            assert_eq!(version.pre_release(), "");
+            assert_eq!(version.build_metadata(), "");
+        }
+
         #[test]
         fn from_semver_version() {
             let version = semver::Version::from_str("1.2.3").unwrap();
@@ -225,6 +480,29 @@ mod tests {
         }
     }
 
+    mod version_req {
+        use super::*;
+
+        fn matches(req: &str, version: &str) -> bool {
+            VersionReq::from_str(req)
+                .unwrap()
+                .matches(&Version::from_str(version).unwrap())
+        }
+
+        #[test]
+        fn range() {
+            assert!(matches(">=1.4, <2", "1.4.0"));
+            assert!(matches(">=1.4, <2", "1.9.3"));
+            assert!(!matches(">=1.4, <2", "1.3.9"));
+            assert!(!matches(">=1.4, <2", "2.0.0"));
+        }
+
+        #[test]
+        fn from_str_err() {
+            assert!(VersionReq::from_str(">=nope").is_err());
+        }
+    }
+
     mod version_bumper {
         use super::*;
 
@@ -947,5 +1225,177 @@ mod tests {
         fn no_bump_set_patch() {
             assert_eq!(bumper("1.2.3").patch(4).no_bump().to_string(), "1.2.4");
         }
+
+        #[test]
+        fn breaking() {
+            assert_eq!(bumper("1.2.3").bump_breaking().to_string(), "2.0.0");
+            assert_eq!(bumper("0.2.3").bump_breaking().to_string(), "0.3.0");
+            assert_eq!(bumper("0.0.3").bump_breaking().to_string(), "0.0.4");
+        }
+
+        #[test]
+        fn breaking_and_pre() {
+            assert_eq!(
+                bumper("0.2.3-rc.1")
+                    .pre(pre("alpha.1"))
+                    .bump_breaking()
+                    .to_string(),
+                "0.3.0-alpha.1"
+            );
+        }
+
+        #[test]
+        fn feature() {
+            assert_eq!(bumper("1.2.3").bump_feature().to_string(), "1.3.0");
+            assert_eq!(bumper("0.2.3").bump_feature().to_string(), "0.2.4");
+            assert_eq!(bumper("0.0.3").bump_feature().to_string(), "0.0.4");
+        }
+
+        #[test]
+        fn fix() {
+            assert_eq!(bumper("1.2.3").bump_fix().to_string(), "1.2.4");
+            assert_eq!(bumper("0.2.3").bump_fix().to_string(), "0.2.4");
+            assert_eq!(bumper("0.0.3").bump_fix().to_string(), "0.0.4");
+        }
+
+        #[test]
+        fn release() {
+            assert_eq!(bumper("1.2.0-rc.2").release().unwrap().to_string(), "1.2.0");
+        }
+
+        #[test]
+        fn release_clears_build() {
+            assert_eq!(
+                bumper("1.2.0-rc.2+b7").release().unwrap().to_string(),
+                "1.2.0"
+            );
+        }
+
+        #[test]
+        fn release_and_build() {
+            assert_eq!(
+                bumper("1.2.0-rc.2+b7")
+                    .build(build("b8"))
+                    .release()
+                    .unwrap()
+                    .to_string(),
+                "1.2.0+b8"
+            );
+        }
+
+        #[test]
+        fn release_already_released() {
+            assert_eq!(bumper("1.2.0").release(), None);
+            assert_eq!(bumper("1.2.0+b7").release(), None);
+        }
+
+        fn bump_pre(version_str: &str, channel: Option<&str>) -> Option<String> {
+            bumper(version_str)
+                .bump_pre(channel.map(pre))
+                .unwrap()
+                .map(|v| v.to_string())
+        }
+
+        #[test]
+        fn pre_counter_overflow() {
+            let err = bumper("1.0.0-rc.18446744073709551615")
+                .bump_pre(None)
+                .unwrap_err();
+
+            assert_eq!(
+                err.to_string(),
+                "pre-release counter 18446744073709551615 overflows"
+            );
+        }
+
+        #[test]
+        fn pre_counter() {
+            assert_eq!(bump_pre("1.2.0-rc.1", None).unwrap(), "1.2.0-rc.2");
+        }
+
+        #[test]
+        fn pre_counter_nine_to_ten() {
+            assert_eq!(bump_pre("1.2.0-rc.9", None).unwrap(), "1.2.0-rc.10");
+        }
+
+        #[test]
+        fn pre_counter_only() {
+            assert_eq!(bump_pre("1.2.0-0", None).unwrap(), "1.2.0-1");
+        }
+
+        #[test]
+        fn pre_no_counter() {
+            assert_eq!(bump_pre("1.2.0-alpha", None).unwrap(), "1.2.0-alpha.1");
+        }
+
+        #[test]
+        fn pre_counter_not_last() {
+            assert_eq!(
+                bump_pre("1.2.0-alpha.1.x", None).unwrap(),
+                "1.2.0-alpha.2.x"
+            );
+            assert_eq!(
+                bump_pre("1.2.0-alpha.1.x", Some("alpha")).unwrap(),
+                "1.2.0-alpha.2.x"
+            );
+            assert_eq!(
+                bump_pre("1.2.0-alpha.1.x", Some("beta")).unwrap(),
+                "1.2.0-beta.1"
+            );
+        }
+
+        #[test]
+        fn pre_same_channel() {
+            assert_eq!(bump_pre("1.2.0-rc.1", Some("rc")).unwrap(), "1.2.0-rc.2");
+        }
+
+        #[test]
+        fn pre_same_channel_no_counter() {
+            assert_eq!(
+                bump_pre("1.2.0-beta", Some("beta")).unwrap(),
+                "1.2.0-beta.1"
+            );
+        }
+
+        #[test]
+        fn pre_switch_channel() {
+            assert_eq!(
+                bump_pre("1.2.0-alpha.3", Some("beta")).unwrap(),
+                "1.2.0-beta.1"
+            );
+        }
+
+        #[test]
+        fn pre_switch_channel_no_counter() {
+            assert_eq!(bump_pre("1.2.0-alpha", Some("rc")).unwrap(), "1.2.0-rc.1");
+        }
+
+        #[test]
+        fn pre_new_channel() {
+            assert_eq!(bump_pre("1.2.0", Some("rc")).unwrap(), "1.2.1-rc.1");
+        }
+
+        #[test]
+        fn pre_none() {
+            assert_eq!(bump_pre("1.2.0", None), None);
+        }
+
+        #[test]
+        fn pre_clears_build() {
+            assert_eq!(bump_pre("1.2.0-rc.1+b7", None).unwrap(), "1.2.0-rc.2");
+        }
+
+        #[test]
+        fn pre_and_build() {
+            assert_eq!(
+                bumper("1.2.0-rc.1+b7")
+                    .build(build("b8"))
+                    .bump_pre(None)
+                    .unwrap()
+                    .unwrap()
+                    .to_string(),
+                "1.2.0-rc.2+b8"
+            );
+        }
     }
 }
@@ -20,3 +20,168 @@ fn no_args() {
         .stdout("")
         .stderr(str::contains("USAGE:\n").and(str::contains("SUBCOMMANDS:\n")));
 }
+
+#[test]
+fn missing_manifest() {
+    cmd()
+        .arg("major")
+        .arg("does/not/exist/Cargo.toml")
+        .assert()
+        .code(3)
+        .stdout("")
+        .stderr(str::contains("I/O error on file does/not/exist/Cargo.toml"));
+}
+
+#[test]
+fn invalid_toml() {
+    let manifest = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(manifest.path(), "[package]\nversion = \n").unwrap();
+
+    cmd()
+        .arg("major")
+        .arg(manifest.path())
+        .assert()
+        .code(4)
+        .stdout("")
+        .stderr(str::contains("invalid TOML").and(str::contains("at line 2, column 11")));
+}
+
+#[test]
+fn missing_version() {
+    let manifest = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(manifest.path(), "[package]\nname = \"nope\"\n").unwrap();
+
+    cmd()
+        .arg("major")
+        .arg(manifest.path())
+        .assert()
+        .code(5)
+        .stdout("")
+        .stderr(str::contains(format!(
+            "cannot find version key 'package.version' in {}",
+            manifest.path().display()
+        )));
+}
+
+#[test]
+fn format_json() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("Cargo.toml"),
+        "[package]\nname = \"a\"\nversion = \"1.2.3\"\n",
+    )
+    .unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .args(["minor", "--format", "json"])
+        .assert()
+        .success()
+        .stdout(
+            "{\"bump\":\"minor\",\"previous\":\"1.2.3\",\"next\":\"1.3.0\",\
+             \"components\":{\"major\":1,\"minor\":3,\"patch\":0,\"pre\":null,\"build\":null},\
+             \"files\":[\"Cargo.toml\"],\"dry_run\":false}\n",
+        );
+}
+
+#[test]
+fn format_json_dry_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let manifest = "[package]\nname = \"a\"\nversion = \"1.2.3\"\n";
+    std::fs::write(dir.path().join("Cargo.toml"), manifest).unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .args(["pre", "--channel", "rc", "--dry-run"])
+        .args(["--format", "json"])
+        .assert()
+        .success()
+        .stdout(str::starts_with(
+            "{\"bump\":\"pre\",\"previous\":\"1.2.3\",\"next\":\"1.2.4-rc.1\",",
+        ))
+        .stdout(str::ends_with(
+            "\"files\":[\"Cargo.toml\"],\"dry_run\":true}\n",
+        ));
+
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("Cargo.toml")).unwrap(),
+        manifest
+    );
+}
+
+#[test]
+fn format_json_stdout() {
+    let manifest = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(manifest.path(), "[package]\nversion = \"1.2.3\"\n").unwrap();
+
+    cmd()
+        .args(["major", "--stdout", "--format", "json"])
+        .arg(manifest.path())
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(str::contains("--stdout cannot be used with --format json"));
+}
+
+#[test]
+fn export() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("Cargo.toml"),
+        "[package]\nname = \"a\"\nversion = \"1.2.3\"\n",
+    )
+    .unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .args(["pre", "--channel", "rc", "--export"])
+        .assert()
+        .success()
+        .stdout(
+            "export VERSIO_VERSION='1.2.4-rc.1'\n\
+             export VERSIO_MAJOR='1'\n\
+             export VERSIO_MINOR='2'\n\
+             export VERSIO_PATCH='4'\n\
+             export VERSIO_PRE='rc.1'\n\
+             export VERSIO_PREVIOUS='1.2.3'\n",
+        );
+}
+
+#[test]
+fn export_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("Cargo.toml"),
+        "[package]\nname = \"a\"\nversion = \"1.2.3\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("output"), "previous=line\n").unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .args(["minor", "--export-file", "output"])
+        .assert()
+        .success()
+        .stdout(str::starts_with("export VERSIO_VERSION='1.3.0'\n"));
+
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("output")).unwrap(),
+        "previous=line\n\
+         VERSIO_VERSION=1.3.0\n\
+         VERSIO_MAJOR=1\n\
+         VERSIO_MINOR=3\n\
+         VERSIO_PATCH=0\n\
+         VERSIO_PRE=\n\
+         VERSIO_PREVIOUS=1.2.3\n"
+    );
+}
+
+#[test]
+fn export_format_json() {
+    cmd()
+        .args(["minor", "--export", "--format", "json"])
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(str::contains("--export cannot be used with --format json"));
+}
@@ -0,0 +1,120 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use assert_cmd::prelude::*;
+use predicate::str;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+
+mod common;
+
+include!("common/macros.rs");
+
+cmd!("bump");
+
+fn project(dir: &Path) {
+    fs::write(
+        dir.join(".versio.toml"),
+        "[[source]]\nkind = \"file\"\npath = \"VERSION.txt\"\n\n\
+         [[source]]\nkind = \"pattern\"\npath = \"install.sh\"\n\
+         pattern = 'VERSION=(?P<version>\\S+)'\n",
+    )
+    .unwrap();
+    fs::write(dir.join("VERSION.txt"), "1.2.3\n").unwrap();
+    fs::write(dir.join("install.sh"), "VERSION=1.2.3\n").unwrap();
+}
+
+#[test]
+fn minor_from_nested_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    project(dir.path());
+    let nested = dir.path().join("src");
+    fs::create_dir(&nested).unwrap();
+
+    cmd()
+        .current_dir(&nested)
+        .arg("minor")
+        .assert()
+        .success()
+        .stdout("");
+
+    assert_eq!(
+        fs::read_to_string(dir.path().join("VERSION.txt")).unwrap(),
+        "1.3.0\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("install.sh")).unwrap(),
+        "VERSION=1.3.0\n"
+    );
+}
+
+#[test]
+fn dry_run() {
+    let dir = tempfile::tempdir().unwrap();
+    project(dir.path());
+
+    cmd()
+        .current_dir(dir.path())
+        .args(["patch", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(
+            "1.2.3 -> 1.2.4\n\
+             --- VERSION.txt\n+++ VERSION.txt\n@@ -1 +1 @@\n-1.2.3\n+1.2.4\n\
+             --- install.sh\n+++ install.sh\n@@ -1 +1 @@\n-VERSION=1.2.3\n+VERSION=1.2.4\n",
+        );
+}
+
+#[test]
+fn explicit_config() {
+    let dir = tempfile::tempdir().unwrap();
+    project(dir.path());
+
+    cmd()
+        .args(["set", "--config"])
+        .arg(dir.path().join(".versio.toml"))
+        .args(["--version", "2.0.0"])
+        .assert()
+        .success();
+
+    assert_eq!(
+        fs::read_to_string(dir.path().join("install.sh")).unwrap(),
+        "VERSION=2.0.0\n"
+    );
+}
+
+#[test]
+fn missing_config() {
+    let dir = tempfile::tempdir().unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("major")
+        .assert()
+        .failure()
+        .stderr(str::contains("cannot find .versio.toml"));
+}
+
+#[test]
+fn format_json() {
+    let dir = tempfile::tempdir().unwrap();
+    project(dir.path());
+
+    cmd()
+        .current_dir(dir.path())
+        .args(["--format", "json", "feature"])
+        .assert()
+        .success()
+        .stdout(
+            "{\"bump\":\"feature\",\"previous\":\"1.2.3\",\"next\":\"1.3.0\",\
+             \"components\":{\"major\":1,\"minor\":3,\"patch\":0,\"pre\":null,\"build\":null},\
+             \"files\":[\"VERSION.txt\",\"install.sh\"],\"dry_run\":false}\n",
+        );
+
+    assert_eq!(
+        fs::read_to_string(dir.path().join("install.sh")).unwrap(),
+        "VERSION=1.3.0\n"
+    );
+}
@@ -0,0 +1,67 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use assert_cmd::prelude::*;
+use predicate::str;
+use predicates::prelude::*;
+use std::fs;
+
+mod common;
+
+include!("common/macros.rs");
+
+cmd!("bump", "dockerfile");
+
+const DOCKERFILE: &str = include_str!("fixtures/docker/Dockerfile");
+
+#[test]
+fn minor_reports_lines() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("Dockerfile");
+    fs::write(&path, DOCKERFILE).unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("minor")
+        .assert()
+        .success()
+        .stdout("")
+        .stderr(str::contains(
+            "updating Dockerfile line 3: ARG VERSION=1.3.0",
+        ))
+        .stderr(str::contains(
+            "updating Dockerfile line 9: ARG VERSION=1.3.0",
+        ))
+        .stderr(str::contains(
+            "updating Dockerfile line 11: org.opencontainers.image.version=\"1.3.0\" \\",
+        ));
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        DOCKERFILE.replace("1.2.3", "1.3.0")
+    );
+}
+
+#[test]
+fn set_stdout() {
+    cmd()
+        .args(["set", "--version", "2.0.0-rc.1", "--stdout"])
+        .arg("docker/Dockerfile")
+        .assert()
+        .success()
+        .stdout(DOCKERFILE.replace("1.2.3", "2.0.0-rc.1"));
+}
+
+#[test]
+fn no_version() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("Dockerfile"), "FROM scratch\nARG VERSION\n").unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("patch")
+        .assert()
+        .failure()
+        .stderr(str::contains("cannot find the version in Dockerfile"));
+}
@@ -20,3 +20,66 @@ fn no_args() {
         .stdout("")
         .stderr(str::contains("USAGE:\n").and(str::contains("SUBCOMMANDS:\n")));
 }
+
+#[test]
+fn invalid_version() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), "nope\n").unwrap();
+
+    cmd()
+        .arg("major")
+        .arg(file.path())
+        .assert()
+        .code(7)
+        .stdout("")
+        .stderr(str::contains(format!(
+            "invalid version 'nope' in {}",
+            file.path().display()
+        )));
+}
+
+#[test]
+fn dry_run() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), "1.2.3\n").unwrap();
+
+    cmd()
+        .arg("minor")
+        .arg("--dry-run")
+        .arg(file.path())
+        .assert()
+        .success()
+        .stdout(format!(
+            "1.2.3 -> 1.3.0\n--- {path}\n+++ {path}\n@@ -1 +1 @@\n-1.2.3\n+1.3.0\n",
+            path = file.path().display()
+        ));
+
+    assert_eq!(std::fs::read_to_string(file.path()).unwrap(), "1.2.3\n");
+}
+
+#[test]
+fn dry_run_conflicts_with_commit() {
+    cmd()
+        .arg("minor")
+        .arg("--dry-run")
+        .arg("--commit")
+        .assert()
+        .failure()
+        .stderr(str::contains("cannot be used with"));
+}
+
+#[test]
+fn scheme_pep440() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), "1.2.3rc1\n").unwrap();
+
+    cmd()
+        .args(["release", "--scheme", "pep440"])
+        .arg(file.path())
+        .assert()
+        .success()
+        .stdout("")
+        .stderr("");
+
+    assert_eq!(std::fs::read_to_string(file.path()).unwrap(), "1.2.3\n");
+}
@@ -0,0 +1,54 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::fs;
+
+mod common;
+
+include!("common/macros.rs");
+
+cmd!("bump", "gradle");
+
+const PROPERTIES: &str = include_str!("fixtures/gradle/gradle.properties");
+
+#[test]
+fn set_stdout() {
+    cmd()
+        .args(["set", "--version", "2.0.0-rc.1", "--stdout"])
+        .arg("gradle/gradle.properties")
+        .assert()
+        .success()
+        .stdout(PROPERTIES.replace("version=1.2.3", "version=2.0.0-rc.1"));
+}
+
+#[test]
+fn major() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("gradle.properties");
+    fs::write(&path, PROPERTIES).unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .args(["major", "--build-metadata", "jvm"])
+        .assert()
+        .success()
+        .stdout("");
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        PROPERTIES.replace("version=1.2.3", "version=2.0.0+jvm")
+    );
+}
+
+#[test]
+fn dry_run() {
+    cmd()
+        .args(["patch", "--dry-run", "gradle/gradle.properties"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("1.2.3 -> 1.2.4\n"))
+        .stdout(predicate::str::contains("-version=1.2.3\n+version=1.2.4\n"));
+}
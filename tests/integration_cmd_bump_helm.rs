@@ -0,0 +1,88 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use assert_cmd::prelude::*;
+use predicate::str;
+use predicates::prelude::*;
+use std::fs;
+
+mod common;
+
+include!("common/macros.rs");
+
+cmd!("bump", "helm");
+
+const CHART: &str = r#"# Deploys the service
+apiVersion: v2
+name: service
+description: The service
+type: application
+# The chart version, bumped on every release
+version: 1.2.3
+appVersion: "0.4.0"
+dependencies:
+  - name: redis
+    version: 17.0.0
+    repository: https://charts.bitnami.com/bitnami
+"#;
+
+#[test]
+fn minor() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("Chart.yaml"), CHART).unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("minor")
+        .assert()
+        .success()
+        .stdout("");
+
+    assert_eq!(
+        fs::read_to_string(dir.path().join("Chart.yaml")).unwrap(),
+        CHART.replace("version: 1.2.3", "version: 1.3.0")
+    );
+}
+
+#[test]
+fn set_app_version_from() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("Chart.yaml"), CHART).unwrap();
+    fs::write(
+        dir.path().join("Cargo.toml"),
+        "[package]\nname = \"service\"\nversion = \"0.5.1\"\n",
+    )
+    .unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .args(["set", "--version", "2.0.0", "--stdout"])
+        .args(["--app-version-from", "Cargo.toml"])
+        .assert()
+        .success()
+        .stdout(
+            CHART
+                .replace("version: 1.2.3", "version: 2.0.0")
+                .replace("appVersion: \"0.4.0\"", "appVersion: \"0.5.1\""),
+        );
+}
+
+#[test]
+fn missing_version() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("Chart.yaml"),
+        "apiVersion: v2\nname: service\n",
+    )
+    .unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("patch")
+        .assert()
+        .code(5)
+        .stderr(str::contains(
+            "cannot find version key 'version' in Chart.yaml",
+        ));
+}
@@ -0,0 +1,66 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use assert_cmd::prelude::*;
+use predicate::str;
+use predicates::prelude::*;
+use std::fs;
+
+mod common;
+
+include!("common/macros.rs");
+
+cmd!("bump", "maven");
+
+const POM: &str = include_str!("fixtures/maven/pom.xml");
+
+#[test]
+fn minor_stdout() {
+    cmd()
+        .args(["minor", "--stdout", "maven/pom.xml"])
+        .assert()
+        .success()
+        .stdout(POM.replace("<version>1.2.3</version>", "<version>1.3.0</version>"));
+}
+
+#[test]
+fn pre_release() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pom.xml");
+    fs::write(&path, POM).unwrap();
+
+    cmd()
+        .args(["patch", "--pre-release", "SNAPSHOT"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout("");
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        POM.replace(
+            "<version>1.2.3</version>",
+            "<version>1.2.4-SNAPSHOT</version>"
+        )
+    );
+}
+
+#[test]
+fn inherited_version() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("pom.xml"),
+        "<project>\n  <parent>\n    <version>7.1.0</version>\n  </parent>\n</project>\n",
+    )
+    .unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("patch")
+        .assert()
+        .code(5)
+        .stderr(str::contains(
+            "cannot find version key 'project.version' in pom.xml",
+        ));
+}
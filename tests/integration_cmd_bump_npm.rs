@@ -0,0 +1,125 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use assert_cmd::prelude::*;
+use predicate::str;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+
+mod common;
+
+include!("common/macros.rs");
+
+cmd!("bump", "npm");
+
+const PACKAGE: &str = r#"{
+  "name": "client",
+  "version": "1.2.3",
+  "scripts": {
+    "build": "tsc"
+  },
+  "devDependencies": {
+    "typescript": "4.9.5"
+  }
+}
+"#;
+
+const LOCK: &str = r#"{
+  "name": "client",
+  "version": "1.2.3",
+  "lockfileVersion": 3,
+  "requires": true,
+  "packages": {
+    "": {
+      "name": "client",
+      "version": "1.2.3",
+      "devDependencies": {
+        "typescript": "4.9.5"
+      }
+    },
+    "node_modules/typescript": {
+      "version": "1.2.3",
+      "dev": true
+    }
+  }
+}
+"#;
+
+fn package(dir: &Path) {
+    fs::write(dir.join("package.json"), PACKAGE).unwrap();
+    fs::write(dir.join("package-lock.json"), LOCK).unwrap();
+}
+
+#[test]
+fn minor() {
+    let dir = tempfile::tempdir().unwrap();
+    package(dir.path());
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("minor")
+        .assert()
+        .success()
+        .stdout("");
+
+    assert_eq!(
+        fs::read_to_string(dir.path().join("package.json")).unwrap(),
+        PACKAGE.replace("1.2.3", "1.3.0")
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("package-lock.json")).unwrap(),
+        LOCK.replacen("1.2.3", "1.3.0", 2)
+    );
+}
+
+#[test]
+fn set_stdout() {
+    let dir = tempfile::tempdir().unwrap();
+    package(dir.path());
+
+    cmd()
+        .args(["set", "--version", "2.0.0-beta.1", "--stdout"])
+        .arg(dir.path().join("package.json"))
+        .assert()
+        .success()
+        .stdout(PACKAGE.replace("1.2.3", "2.0.0-beta.1"));
+
+    assert_eq!(
+        fs::read_to_string(dir.path().join("package-lock.json")).unwrap(),
+        LOCK
+    );
+}
+
+#[test]
+fn missing_version() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("package.json"), "{\"private\": true}\n").unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("patch")
+        .assert()
+        .code(5)
+        .stderr(str::contains(
+            "cannot find version key 'version' in package.json",
+        ));
+}
+
+#[test]
+fn invalid_json() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("package.json"),
+        "{\n  \"version\": 1.2.3\n}\n",
+    )
+    .unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("patch")
+        .assert()
+        .code(4)
+        .stderr(str::contains("invalid JSON in package.json at line 2"));
+}
@@ -0,0 +1,168 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use assert_cmd::prelude::*;
+use chrono::{Datelike, Local};
+use predicate::str;
+use predicates::prelude::*;
+use std::fs;
+
+mod common;
+
+include!("common/macros.rs");
+
+cmd!("bump", "pattern");
+
+const RUST: &str = r#"VERSION: &str = "(?P<version>[^"]+)""#;
+
+#[test]
+fn minor() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("version.rs");
+    fs::write(
+        &path,
+        "// the version\npub const VERSION: &str = \"1.2.3\";\r\n",
+    )
+    .unwrap();
+
+    cmd()
+        .args(["minor", "--regex", RUST])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout("");
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "// the version\npub const VERSION: &str = \"1.3.0\";\r\n"
+    );
+}
+
+#[test]
+fn stdout() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("install.sh");
+    fs::write(&path, "VERSION=1.2.3\necho \"$VERSION\"\n").unwrap();
+
+    cmd()
+        .args(["set", "--version", "2.0.0", "--stdout"])
+        .args(["-e", r"VERSION=(?P<version>\S+)"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout("VERSION=2.0.0\necho \"$VERSION\"\n");
+}
+
+#[test]
+fn no_match() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("README.md");
+    fs::write(&path, "cargo install versio\n").unwrap();
+
+    cmd()
+        .args(["patch", "--regex", r"versio@(?P<version>\S+)"])
+        .arg(&path)
+        .assert()
+        .failure()
+        .stderr(str::contains("does not match"));
+}
+
+#[test]
+fn max_matches() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("README.md");
+    fs::write(&path, "versio@1.2.3\n\nversio@1.2.3\n").unwrap();
+
+    cmd()
+        .args(["patch", "--regex", r"versio@(?P<version>\S+)"])
+        .arg(&path)
+        .assert()
+        .failure()
+        .stderr(str::contains("matches 2 times, but at most 1 is allowed"));
+
+    cmd()
+        .args(["patch", "--max-matches", "2"])
+        .args(["--regex", r"versio@(?P<version>\S+)"])
+        .arg(&path)
+        .assert()
+        .success();
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "versio@1.2.4\n\nversio@1.2.4\n"
+    );
+}
+
+#[test]
+fn missing_group() {
+    cmd()
+        .args(["patch", "--regex", r"VERSION=(\S+)", "install.sh"])
+        .assert()
+        .failure()
+        .stderr(str::contains("has no capture group named 'version'"));
+}
+
+#[test]
+fn pep440_post() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("__init__.py");
+    fs::write(&path, "__version__ = \"1.2.0rc1\"\n").unwrap();
+
+    cmd()
+        .args(["post", "--scheme", "pep440", "--stdout"])
+        .args(["-e", r#"__version__ = "(?P<version>[^"]+)""#])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout("__version__ = \"1.2.0rc1.post1\"\n");
+}
+
+#[test]
+fn post_semver() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("install.sh");
+    fs::write(&path, "VERSION=1.2.3\n").unwrap();
+
+    cmd()
+        .args(["post", "-e", r"VERSION=(?P<version>\S+)"])
+        .arg(&path)
+        .assert()
+        .failure()
+        .stderr(str::contains("semantic versions have no post-releases"));
+}
+
+#[test]
+fn calver() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("install.sh");
+    fs::write(&path, "VERSION=2000.1.3\n").unwrap();
+    let today = Local::today();
+
+    cmd()
+        .args(["calver", "-e", r"VERSION=(?P<version>\S+)"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout("");
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        format!("VERSION={}.{}.0\n", today.year(), today.month())
+    );
+}
+
+#[test]
+fn calver_format_invalid() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("install.sh");
+    fs::write(&path, "VERSION=2000.1.3\n").unwrap();
+
+    cmd()
+        .args(["calver", "--calver-format", "YYYY.MONTH"])
+        .args(["-e", r"VERSION=(?P<version>\S+)"])
+        .arg(&path)
+        .assert()
+        .failure()
+        .stderr(str::contains("unknown part 'MONTH'"));
+}
@@ -0,0 +1,177 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use assert_cmd::prelude::*;
+use predicate::str;
+use predicates::prelude::*;
+use std::fs;
+
+mod common;
+
+include!("common/macros.rs");
+
+cmd!("bump", "pyproject");
+
+const PROJECT: &str = r#"[build-system]
+requires = ["maturin>=1.0"]
+build-backend = "maturin"
+
+[project]
+name = "bindings"
+# Kept in step with the crate
+version = "1.2.3"
+requires-python = ">=3.8"
+"#;
+
+const POETRY: &str = r#"[tool.poetry]
+name = "bindings"
+version = "0.4.0"  # bumped by versio
+
+[tool.poetry.dependencies]
+python = "^3.8"
+"#;
+
+#[test]
+fn project() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pyproject.toml");
+    fs::write(&path, PROJECT).unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("minor")
+        .assert()
+        .success()
+        .stdout("");
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        PROJECT.replace("1.2.3", "1.3.0")
+    );
+}
+
+#[test]
+fn poetry_stdout() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pyproject.toml");
+    fs::write(&path, POETRY).unwrap();
+
+    cmd()
+        .args(["patch", "--stdout"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(POETRY.replace("0.4.0", "0.4.1"));
+}
+
+#[test]
+fn dynamic() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pyproject.toml");
+    let text = "[project]\nname = \"bindings\"\ndynamic = [\"version\"]\n";
+    fs::write(&path, text).unwrap();
+
+    cmd()
+        .arg("major")
+        .arg(&path)
+        .assert()
+        .code(10)
+        .stderr(str::contains(format!(
+            "version in {} is declared dynamic in project.dynamic",
+            path.display()
+        )));
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), text);
+}
+
+#[test]
+fn pep440_pre() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pyproject.toml");
+    fs::write(&path, PROJECT).unwrap();
+
+    cmd()
+        .args(["pre", "--channel", "rc"])
+        .arg(&path)
+        .assert()
+        .success();
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        PROJECT.replace("1.2.3", "1.2.4rc1")
+    );
+
+    cmd().arg("pre").arg(&path).assert().success();
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        PROJECT.replace("1.2.3", "1.2.4rc2")
+    );
+
+    cmd().arg("release").arg(&path).assert().success();
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        PROJECT.replace("1.2.3", "1.2.4")
+    );
+}
+
+#[test]
+fn pep440_dev_dry_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pyproject.toml");
+    fs::write(&path, POETRY).unwrap();
+
+    cmd()
+        .args(["dev", "--dry-run"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(str::starts_with("0.4.0 -> 0.4.1.dev1\n"));
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), POETRY);
+}
+
+#[test]
+fn pep440_set() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pyproject.toml");
+    fs::write(&path, PROJECT).unwrap();
+
+    cmd()
+        .args(["set", "--stdout", "--version", "2.0.0b1"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(PROJECT.replace("1.2.3", "2.0.0b1"));
+}
+
+#[test]
+fn semver_scheme() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pyproject.toml");
+    fs::write(&path, PROJECT).unwrap();
+
+    cmd()
+        .args(["pre", "--scheme", "semver", "--channel", "rc", "--stdout"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(PROJECT.replace("1.2.3", "1.2.4-rc.1"));
+}
+
+#[test]
+fn pep440_build_metadata() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pyproject.toml");
+    fs::write(&path, PROJECT).unwrap();
+
+    cmd()
+        .args(["minor", "--build-metadata", "abc"])
+        .arg(&path)
+        .assert()
+        .failure()
+        .stderr(str::contains(
+            "--build-metadata does not apply to PEP 440 versions",
+        ));
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), PROJECT);
+}
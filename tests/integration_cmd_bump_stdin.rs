@@ -20,3 +20,42 @@ fn no_args() {
         .stdout("")
         .stderr(str::contains("USAGE:\n").and(str::contains("SUBCOMMANDS:\n")));
 }
+
+#[test]
+fn format_json() {
+    cmd()
+        .args(["--format", "json", "set", "--major", "3"])
+        .with_stdin()
+        .buffer("1.2.3\n")
+        .assert()
+        .success()
+        .stdout(
+            "{\"bump\":\"set\",\"previous\":\"1.2.3\",\"next\":\"3.2.3\",\
+             \"components\":{\"major\":3,\"minor\":2,\"patch\":3,\"pre\":null,\"build\":null},\
+             \"files\":[],\"dry_run\":false}\n",
+        );
+}
+
+#[test]
+fn dry_run() {
+    cmd()
+        .args(["minor", "--dry-run"])
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(str::contains(
+            "cannot be used with 'versio bump stdin minor'",
+        ));
+}
+
+#[test]
+fn scheme_pep440() {
+    cmd()
+        .args(["pre", "--scheme", "pep440"])
+        .with_stdin()
+        .buffer("1.2.3rc1\n")
+        .assert()
+        .success()
+        .stderr("")
+        .stdout("1.2.3rc2\n");
+}
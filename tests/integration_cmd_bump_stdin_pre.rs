@@ -0,0 +1,44 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use assert_cmd::assert::Assert;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+mod common;
+
+include!("common/macros.rs");
+
+cmd!("bump", "stdin", "pre");
+
+fn cmd_with_stdin<S>(args: &[&str], buffer: S) -> Assert
+where
+    S: Into<Vec<u8>>,
+{
+    cmd().args(args).with_stdin().buffer(buffer).assert()
+}
+
+#[test]
+fn counter() {
+    cmd_with_stdin(&[], "1.2.0-rc.1\n")
+        .success()
+        .stderr("")
+        .stdout("1.2.0-rc.2\n");
+}
+
+#[test]
+fn channel() {
+    cmd_with_stdin(&["--channel", "beta"], "1.2.0-alpha.3\n")
+        .success()
+        .stderr("")
+        .stdout("1.2.0-beta.1\n");
+}
+
+#[test]
+fn no_pre_release() {
+    cmd_with_stdin(&[], "1.2.0\n")
+        .failure()
+        .stdout("")
+        .stderr(predicate::str::contains("no pre-release to bump"));
+}
@@ -0,0 +1,85 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use assert_cmd::prelude::*;
+use predicate::str;
+use predicates::prelude::*;
+
+mod common;
+
+include!("common/macros.rs");
+
+cmd!("check");
+
+#[test]
+fn no_args() {
+    cmd()
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(str::contains("USAGE:\n").and(str::contains("SUBCOMMANDS:\n")));
+}
+
+#[test]
+fn stdin_version() {
+    cmd()
+        .args(["stdin", "--version", "1.4.0"])
+        .with_stdin()
+        .buffer("1.4.0\n")
+        .assert()
+        .success()
+        .stdout("")
+        .stderr("");
+}
+
+#[test]
+fn file_requirement_unsatisfied() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), "2.0.0\n").unwrap();
+
+    cmd()
+        .args(["file", "--req", ">=1.4, <2"])
+        .arg(file.path())
+        .assert()
+        .code(8)
+        .stdout("")
+        .stderr(str::contains(format!(
+            "version '2.0.0' in {} does not match '>= 1.4, < 2'",
+            file.path().display()
+        )));
+}
+
+#[test]
+fn missing_expectation() {
+    cmd()
+        .arg("stdin")
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(str::contains("--version"));
+}
+
+#[test]
+fn stdin_format_json_mismatch() {
+    cmd()
+        .args(["stdin", "--req", "^2", "--format", "json"])
+        .with_stdin()
+        .buffer("1.4.0\n")
+        .assert()
+        .code(8)
+        .stdout("{\"version\":\"1.4.0\",\"expected\":\"^2\",\"matches\":false}\n")
+        .stderr(str::contains("version '1.4.0' does not match '^2'"));
+}
+
+#[test]
+fn stdin_scheme_pep440() {
+    cmd()
+        .args(["stdin", "--scheme", "pep440", "--version", "1.4.0rc1"])
+        .with_stdin()
+        .buffer("1.4rc1\n")
+        .assert()
+        .success()
+        .stdout("")
+        .stderr("");
+}
@@ -0,0 +1,103 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use assert_cmd::prelude::*;
+use predicate::str;
+use predicates::prelude::*;
+
+mod common;
+
+include!("common/macros.rs");
+
+cmd!("get");
+
+#[test]
+fn no_args() {
+    cmd()
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(str::contains("USAGE:\n").and(str::contains("SUBCOMMANDS:\n")));
+}
+
+#[test]
+fn stdin_major_minor() {
+    cmd()
+        .args(["stdin", "--major-minor"])
+        .with_stdin()
+        .buffer("1.2.3-rc.1\n")
+        .assert()
+        .success()
+        .stderr("")
+        .stdout("1.2\n");
+}
+
+#[test]
+fn conflicting_parts() {
+    cmd()
+        .args(["stdin", "--major", "--patch"])
+        .with_stdin()
+        .buffer("1.2.3\n")
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(str::contains("cannot be used with"));
+}
+
+#[test]
+fn stdin_format_json() {
+    cmd()
+        .args(["--format", "json", "stdin", "--major"])
+        .with_stdin()
+        .buffer("1.2.3-rc.1+b8\n")
+        .assert()
+        .success()
+        .stderr("")
+        .stdout(
+            "{\"version\":\"1.2.3-rc.1+b8\",\"components\":{\"major\":1,\"minor\":2,\
+             \"patch\":3,\"pre\":\"rc.1\",\"build\":\"b8\"}}\n",
+        );
+}
+
+#[test]
+fn stdin_export() {
+    cmd()
+        .args(["--export", "stdin"])
+        .with_stdin()
+        .buffer("1.2.3\n")
+        .assert()
+        .success()
+        .stderr("")
+        .stdout(
+            "export VERSIO_VERSION='1.2.3'\n\
+             export VERSIO_MAJOR='1'\n\
+             export VERSIO_MINOR='2'\n\
+             export VERSIO_PATCH='3'\n\
+             export VERSIO_PRE=''\n",
+        );
+}
+
+#[test]
+fn dry_run() {
+    cmd()
+        .args(["stdin", "--dry-run"])
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(str::contains(
+            "The argument '--dry-run' cannot be used with 'versio get stdin'",
+        ));
+}
+
+#[test]
+fn stdin_scheme_pep440() {
+    cmd()
+        .args(["stdin", "--scheme", "pep440", "--pre-release"])
+        .with_stdin()
+        .buffer("1.2rc1+b8\n")
+        .assert()
+        .success()
+        .stderr("")
+        .stdout("rc1\n");
+}
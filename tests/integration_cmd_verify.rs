@@ -0,0 +1,94 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use assert_cmd::prelude::*;
+use predicate::str;
+use predicates::prelude::*;
+
+mod common;
+
+include!("common/macros.rs");
+
+cmd!("verify");
+
+#[test]
+fn disagree() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("VERSION.txt"), "1.2.3\n").unwrap();
+    std::fs::write(dir.path().join("install.sh"), "VERSION=1.2.4\n").unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .args(["--file", "VERSION.txt"])
+        .args([r"--pattern=install.sh=VERSION=(?P<version>\S+)"])
+        .assert()
+        .code(9)
+        .stdout(
+            "KIND     PATH         VERSION\n\
+             file     VERSION.txt  1.2.3\n\
+             pattern  install.sh   1.2.4\n",
+        )
+        .stderr(str::contains(
+            "versions disagree: 1.2.3 in VERSION.txt, 1.2.4 in install.sh",
+        ));
+}
+
+#[test]
+fn invalid_pattern() {
+    cmd()
+        .args(["--pattern", "install.sh"])
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(str::contains("expected FILE=REGEX, found 'install.sh'"));
+}
+
+#[test]
+fn config() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join(".versio.toml"),
+        "[[source]]\nkind = \"file\"\npath = \"VERSION.txt\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("VERSION.txt"), "1.2.3\n").unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .assert()
+        .success()
+        .stdout("KIND  PATH         VERSION\nfile  VERSION.txt  1.2.3\n");
+}
+
+#[test]
+fn scheme() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("VERSION.txt"), "1.2.3rc1\n").unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .args(["--file", "VERSION.txt", "--scheme", "pep440"])
+        .assert()
+        .success()
+        .stdout("KIND  PATH         VERSION\nfile  VERSION.txt  1.2.3rc1\n");
+}
+
+#[test]
+fn format_json() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("VERSION.txt"), "1.2.3\n").unwrap();
+    std::fs::write(dir.path().join("install.sh"), "VERSION=1.2.3\n").unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .args(["--format", "json", "--file", "VERSION.txt"])
+        .args([r"--pattern=install.sh=VERSION=(?P<version>\S+)"])
+        .assert()
+        .success()
+        .stdout(
+            "{\"version\":\"1.2.3\",\"agree\":true,\"sources\":[\
+             {\"kind\":\"file\",\"path\":\"VERSION.txt\",\"version\":\"1.2.3\"},\
+             {\"kind\":\"pattern\",\"path\":\"install.sh\",\"version\":\"1.2.3\"}]}\n",
+        );
+}